@@ -0,0 +1,81 @@
+use actix_web::dev::Payload;
+use actix_web::error::InternalError;
+use actix_web::http::header;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest, HttpResponse};
+use infra::service::token::AuthCredential;
+use infra::types::session::AuthSessionExt;
+use infra::App;
+use rsession::Session;
+use serde_json::json;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use uuid::Uuid;
+
+/// The caller of an API request, authenticated by the session cookie or by a
+/// personal access token sent as `Authorization: Bearer <token>`.
+pub struct AuthUser(pub AuthCredential);
+
+impl AuthUser {
+    /// Rejects token callers whose token lacks `scope`.
+    pub fn require_scope(&self, scope: &str) -> Result<(), HttpResponse> {
+        if self.0.has_scope(scope) {
+            return Ok(());
+        }
+        Err(HttpResponse::Ok().json(json!({"code": 403, "message": format!("Token scope {} required", scope)})))
+    }
+}
+
+impl Deref for AuthUser {
+    type Target = infra::types::session::AuthSession;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.session
+    }
+}
+
+/// The uid to authorize reads with: anonymous unless logged in, or using a
+/// token that carries `repo:read`.
+pub fn read_user(user: &Option<AuthUser>) -> Option<Uuid> {
+    user.as_ref()
+        .filter(|x| x.0.has_scope("repo:read"))
+        .map(|x| x.uid)
+}
+
+fn not_login() -> actix_web::Error {
+    InternalError::from_response(
+        "Not login",
+        HttpResponse::Ok().json(json!({"code": 401, "message": "Not login"})),
+    )
+    .into()
+}
+
+impl FromRequest for AuthUser {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let bearer = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.strip_prefix("Bearer "))
+            .map(|x| x.trim().to_string());
+        let app = req.app_data::<Data<App>>().cloned();
+        let session = Session::from_request(req, payload).into_inner();
+        Box::pin(async move {
+            if let Some(token) = bearer {
+                let app = app.ok_or_else(not_login)?;
+                return match app.token_auth(&token).await {
+                    Ok(credential) => Ok(AuthUser(credential)),
+                    Err(_) => Err(not_login()),
+                };
+            }
+            match session?.to_auth().await {
+                Some(session) => Ok(AuthUser(AuthCredential { session, scopes: None })),
+                None => Err(not_login()),
+            }
+        })
+    }
+}
@@ -1,3 +1,4 @@
 pub mod context;
 pub mod login;
 pub mod register;
+pub mod extract;
@@ -2,20 +2,41 @@ use crate::auth::context::auth_context;
 use crate::auth::login::{auth_login, auth_logout};
 use crate::auth::register::auth_register;
 use actix_web::web;
-use actix_web::web::{get, post, scope, Data};
+use actix_web::web::{delete, get, patch, post, scope, Data};
 use infra::App;
 use rsession::framework::actix::ActixSessionMiddleware;
 use rsession::redis::RedisSessionStorage;
 use rsession::SessionBuilder;
 use std::net::SocketAddr;
 use tracing::{error, info};
-use crate::repo::branch::repo_branch;
+use crate::org::info::{org_create, org_info, org_list, org_update};
+use crate::org::member::{org_member_add, org_member_remove, org_member_update, org_members};
+use crate::org::team::{org_team_create, org_team_delete, org_team_member_add, org_team_member_remove, org_team_members, org_team_repo_grant, org_team_repo_revoke, org_team_repos, org_teams};
+use crate::repo::archive::repo_archive;
+use crate::repo::blame::repo_blame;
+use crate::repo::branch::{repo_branch, repo_branch_create, repo_branch_default, repo_branch_delete, repo_branch_rename};
 use crate::repo::cat_file::repo_cat_file;
 use crate::repo::dash::repo_dash;
 use crate::repo::tree::repo_tree;
+use crate::repo::collaborator::{repo_collaborator_invite, repo_collaborator_remove, repo_collaborator_update, repo_collaborators};
 use crate::repo::commits::repo_commits;
+use crate::repo::contents::repo_contents_commit;
+use crate::repo::diff::{repo_commit_diff, repo_compare, repo_mergeability};
+use crate::repo::fork::{repo_fork, repo_fork_network, repo_forks};
 use crate::repo::init::repo_init;
+use crate::repo::issue::{repo_issue, repo_issue_close, repo_issue_comment_create, repo_issue_comment_delete, repo_issue_comment_update, repo_issue_comments, repo_issue_create, repo_issue_events, repo_issue_reopen, repo_issue_update, repo_issues};
+use crate::repo::job::{repo_job_retry, repo_jobs};
+use crate::repo::label::{repo_label_create, repo_label_delete, repo_label_update, repo_labels};
 use crate::repo::list::repo_list;
+use crate::repo::milestone::{repo_milestone_create, repo_milestone_delete, repo_milestone_update, repo_milestones};
+use crate::repo::pull::{repo_pull, repo_pull_close, repo_pull_create, repo_pull_events, repo_pull_merge, repo_pull_reopen, repo_pull_update, repo_pulls};
+use crate::repo::pull_review::{repo_pull_comment_create, repo_pull_comment_resolve, repo_pull_comment_unresolve, repo_pull_comments, repo_pull_review_create, repo_pull_reviews};
+use crate::repo::protected_branch::{repo_protected_branch_create, repo_protected_branch_delete, repo_protected_branch_update, repo_protected_branches};
+use crate::repo::tag::{repo_tag_create, repo_tag_delete, repo_tags};
+use crate::repo::webhook::{repo_webhook_create, repo_webhook_deliveries, repo_webhook_delete, repo_webhook_delivery, repo_webhook_redeliver, repo_webhook_update, repo_webhooks};
+use crate::user::invitation::{user_invitation_accept, user_invitation_decline, user_invitations};
+use crate::user::ssh_key::{user_ssh_key_create, user_ssh_key_delete, user_ssh_keys};
+use crate::user::token::{user_token_create, user_token_revoke, user_tokens};
 
 #[derive(Clone)]
 pub struct ApiService {
@@ -56,6 +77,41 @@ impl ApiService {
                     .route("/logout", post().to(auth_logout))
                     .route("/context", post().to(auth_context)),
             )
+                .service(
+                    scope("/user")
+                        .route("/keys", get().to(user_ssh_keys))
+                        .route("/keys", post().to(user_ssh_key_create))
+                        .route("/keys/{uid}", delete().to(user_ssh_key_delete))
+                        .route("/tokens", get().to(user_tokens))
+                        .route("/tokens", post().to(user_token_create))
+                        .route("/tokens/{uid}", delete().to(user_token_revoke))
+                        .route("/invitations", get().to(user_invitations))
+                        .route("/invitations/{uid}/accept", post().to(user_invitation_accept))
+                        .route("/invitations/{uid}", delete().to(user_invitation_decline)),
+                )
+                .service(
+                    scope("/org")
+                        .route("/create", post().to(org_create))
+                        .route("/list", get().to(org_list))
+                        .service(
+                    scope("/{org}")
+                        .route("", get().to(org_info))
+                        .route("", patch().to(org_update))
+                        .route("/members", get().to(org_members))
+                        .route("/members", post().to(org_member_add))
+                        .route("/members/{username}", patch().to(org_member_update))
+                        .route("/members/{username}", delete().to(org_member_remove))
+                        .route("/teams", get().to(org_teams))
+                        .route("/teams", post().to(org_team_create))
+                        .route("/teams/{team}", delete().to(org_team_delete))
+                        .route("/teams/{team}/members", get().to(org_team_members))
+                        .route("/teams/{team}/members", post().to(org_team_member_add))
+                        .route("/teams/{team}/members/{username}", delete().to(org_team_member_remove))
+                        .route("/teams/{team}/repos", get().to(org_team_repos))
+                        .route("/teams/{team}/repos", post().to(org_team_repo_grant))
+                        .route("/teams/{team}/repos/{repo}", delete().to(org_team_repo_revoke))
+                        )
+                )
                 .service(
                     scope("/repo")
                         .route("/init", post().to(repo_init))
@@ -64,9 +120,75 @@ impl ApiService {
                     scope("/{owner}/{repo}")
                         .route("",get().to(repo_dash))
                         .route("/tree/{path:.*}",get().to(repo_tree))
+                        .route("/archive/{name:.*}", get().to(repo_archive))
+                        .route("/contents", post().to(repo_contents_commit))
                         .route("/cat_file/{path:.*}",get().to(repo_cat_file))
+                        .route("/blame/{path:.*}",get().to(repo_blame))
                         .route("/commits",get().to(repo_commits))
                         .route("/branches", get().to(repo_branch))
+                        .route("/branches", post().to(repo_branch_create))
+                        .route("/branches/rename", post().to(repo_branch_rename))
+                        .route("/branches/default", post().to(repo_branch_default))
+                        .route("/branches/{branch:.*}", delete().to(repo_branch_delete))
+                        .route("/protected_branches", get().to(repo_protected_branches))
+                        .route("/protected_branches", post().to(repo_protected_branch_create))
+                        .route("/protected_branches/{uid}", patch().to(repo_protected_branch_update))
+                        .route("/protected_branches/{uid}", delete().to(repo_protected_branch_delete))
+                        .route("/collaborators", get().to(repo_collaborators))
+                        .route("/collaborators", post().to(repo_collaborator_invite))
+                        .route("/collaborators/{username}", patch().to(repo_collaborator_update))
+                        .route("/collaborators/{username}", delete().to(repo_collaborator_remove))
+                        .route("/forks", get().to(repo_forks))
+                        .route("/forks", post().to(repo_fork))
+                        .route("/network", get().to(repo_fork_network))
+                        .route("/pulls", get().to(repo_pulls))
+                        .route("/pulls", post().to(repo_pull_create))
+                        .route("/pulls/{number}", get().to(repo_pull))
+                        .route("/pulls/{number}", patch().to(repo_pull_update))
+                        .route("/pulls/{number}/close", post().to(repo_pull_close))
+                        .route("/pulls/{number}/reopen", post().to(repo_pull_reopen))
+                        .route("/pulls/{number}/merge", post().to(repo_pull_merge))
+                        .route("/pulls/{number}/events", get().to(repo_pull_events))
+                        .route("/pulls/{number}/reviews", get().to(repo_pull_reviews))
+                        .route("/pulls/{number}/reviews", post().to(repo_pull_review_create))
+                        .route("/pulls/{number}/comments", get().to(repo_pull_comments))
+                        .route("/pulls/{number}/comments", post().to(repo_pull_comment_create))
+                        .route("/pulls/{number}/comments/{uid}/resolve", post().to(repo_pull_comment_resolve))
+                        .route("/pulls/{number}/comments/{uid}/unresolve", post().to(repo_pull_comment_unresolve))
+                        .route("/issues", get().to(repo_issues))
+                        .route("/issues", post().to(repo_issue_create))
+                        .route("/issues/{number}", get().to(repo_issue))
+                        .route("/issues/{number}", patch().to(repo_issue_update))
+                        .route("/issues/{number}/close", post().to(repo_issue_close))
+                        .route("/issues/{number}/reopen", post().to(repo_issue_reopen))
+                        .route("/issues/{number}/events", get().to(repo_issue_events))
+                        .route("/issues/{number}/comments", get().to(repo_issue_comments))
+                        .route("/issues/{number}/comments", post().to(repo_issue_comment_create))
+                        .route("/issues/{number}/comments/{uid}", patch().to(repo_issue_comment_update))
+                        .route("/issues/{number}/comments/{uid}", delete().to(repo_issue_comment_delete))
+                        .route("/labels", get().to(repo_labels))
+                        .route("/labels", post().to(repo_label_create))
+                        .route("/labels/{name}", patch().to(repo_label_update))
+                        .route("/labels/{name}", delete().to(repo_label_delete))
+                        .route("/milestones", get().to(repo_milestones))
+                        .route("/milestones", post().to(repo_milestone_create))
+                        .route("/milestones/{uid}", patch().to(repo_milestone_update))
+                        .route("/milestones/{uid}", delete().to(repo_milestone_delete))
+                        .route("/hooks", get().to(repo_webhooks))
+                        .route("/hooks", post().to(repo_webhook_create))
+                        .route("/hooks/{uid}", patch().to(repo_webhook_update))
+                        .route("/hooks/{uid}", delete().to(repo_webhook_delete))
+                        .route("/hooks/{uid}/deliveries", get().to(repo_webhook_deliveries))
+                        .route("/hooks/{uid}/deliveries/{delivery}", get().to(repo_webhook_delivery))
+                        .route("/hooks/{uid}/deliveries/{delivery}/redeliver", post().to(repo_webhook_redeliver))
+                        .route("/jobs", get().to(repo_jobs))
+                        .route("/jobs/{uid}/retry", post().to(repo_job_retry))
+                        .route("/tags", get().to(repo_tags))
+                        .route("/tags", post().to(repo_tag_create))
+                        .route("/tags/{tag:.*}", delete().to(repo_tag_delete))
+                        .route("/commit/{sha}", get().to(repo_commit_diff))
+                        .route("/compare/{spec:.*}/mergeability", get().to(repo_mergeability))
+                        .route("/compare/{spec:.*}", get().to(repo_compare))
                         )
                 )
 
@@ -77,6 +199,8 @@ impl ApiService {
     }
 }
 mod auth;
+mod org;
 mod repo;
+mod user;
 mod error;
 // mod dist;
\ No newline at end of file
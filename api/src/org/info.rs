@@ -0,0 +1,58 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::organization::{OrganizationCreateParam, OrganizationUpdateParam};
+use serde_json::json;
+
+pub async fn org_create(
+    app: Data<App>,
+    param: Json<OrganizationCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.org_create(user.uid, param.into_inner()).await {
+        Ok(org) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": org})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_list(
+    app: Data<App>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.org_list(user.uid).await {
+        Ok(orgs) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": orgs})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_info(
+    app: Data<App>,
+    paths: Path<String>,
+) -> impl Responder {
+    match app.org_info(paths.into_inner()).await {
+        Ok(org) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": org})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_update(
+    app: Data<App>,
+    paths: Path<String>,
+    param: Json<OrganizationUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    match app.org_update(user.uid, paths.into_inner(), param.into_inner()).await {
+        Ok(org) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": org})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,66 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::organization::{OrganizationMemberParam, OrganizationMemberUpdateParam};
+use serde_json::json;
+
+pub async fn org_members(
+    app: Data<App>,
+    paths: Path<String>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.org_members(user.uid, paths.into_inner()).await {
+        Ok(members) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": members})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_member_add(
+    app: Data<App>,
+    paths: Path<String>,
+    param: Json<OrganizationMemberParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    match app.org_member_add(user.uid, paths.into_inner(), param.into_inner()).await {
+        Ok(member) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": member})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_member_update(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<OrganizationMemberUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, username) = paths.into_inner();
+    match app.org_member_update(user.uid, org, username, param.into_inner()).await {
+        Ok(member) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": member})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_member_remove(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, username) = paths.into_inner();
+    match app.org_member_remove(user.uid, org, username).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
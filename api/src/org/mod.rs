@@ -0,0 +1,3 @@
+pub mod info;
+pub mod member;
+pub mod team;
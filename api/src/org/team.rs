@@ -0,0 +1,142 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::team::{TeamCreateParam, TeamMemberParam, TeamRepositoryParam};
+use serde_json::json;
+
+pub async fn org_teams(
+    app: Data<App>,
+    paths: Path<String>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.team_list(user.uid, paths.into_inner()).await {
+        Ok(teams) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": teams})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_create(
+    app: Data<App>,
+    paths: Path<String>,
+    param: Json<TeamCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    match app.team_create(user.uid, paths.into_inner(), param.into_inner()).await {
+        Ok(team) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": team})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_delete(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, team) = paths.into_inner();
+    match app.team_delete(user.uid, org, team).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_members(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    let (org, team) = paths.into_inner();
+    match app.team_members(user.uid, org, team).await {
+        Ok(members) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": members})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_member_add(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<TeamMemberParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, team) = paths.into_inner();
+    match app.team_member_add(user.uid, org, team, param.into_inner()).await {
+        Ok(member) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": member})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_member_remove(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, team, username) = paths.into_inner();
+    match app.team_member_remove(user.uid, org, team, username).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_repos(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    let (org, team) = paths.into_inner();
+    match app.team_repositories(user.uid, org, team).await {
+        Ok(repos) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": repos})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_repo_grant(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<TeamRepositoryParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, team) = paths.into_inner();
+    match app.team_repository_grant(user.uid, org, team, param.into_inner()).await {
+        Ok(grant) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": grant})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn org_team_repo_revoke(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (org, team, repo) = paths.into_inner();
+    match app.team_repository_revoke(user.uid, org, team, repo).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
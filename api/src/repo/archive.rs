@@ -0,0 +1,32 @@
+use crate::auth::extract::{read_user, AuthUser};
+use actix_web::body::BodyStream;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::web::{Bytes, Data, Path, Query};
+use actix_web::{HttpResponse, Responder};
+use futures_util::stream;
+use infra::service::archive::RepositoryArchiveParam;
+use infra::App;
+use serde_json::json;
+
+pub async fn repo_archive(
+    app: Data<App>,
+    path: Path<(String, String, String)>,
+    param: Query<RepositoryArchiveParam>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, name) = path.into_inner();
+    let archive = match app.repository_archive(read_user(&user), repo, owner, name, param.into_inner()).await {
+        Ok(archive) => archive,
+        Err(e) => return HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    };
+    let body = stream::unfold(archive.body, |mut rx| async move {
+        rx.recv().await.map(|x| (x.map(Bytes::from), rx))
+    });
+    HttpResponse::Ok()
+        .content_type(archive.content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(archive.file_name)],
+        })
+        .body(BodyStream::new(body))
+}
@@ -0,0 +1,27 @@
+use crate::auth::extract::{read_user, AuthUser};
+use std::collections::HashMap;
+use actix_web::{HttpResponse, Responder};
+use actix_web::web::{Data, Path, Query};
+use serde_json::json;
+use infra::App;
+
+pub async fn repo_blame(
+    path: Path<(String,String,String)>,
+    app: Data<App>,
+    query: Query<HashMap<String,String>>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner,repo,path) = path.into_inner();
+    let repo = match app.repository_dash(read_user(&user), repo, owner).await {
+        Ok(x) => x.repo,
+        Err(e) => return HttpResponse::Ok()
+            .json(json!({ "code": 500, "message": e.to_string()})),
+    };
+    let branch = query.get("branch").cloned();
+    let commit = query.get("commit").cloned();
+    match app.blame(repo,path.as_ref(),branch,commit).await {
+        Ok(x) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": x})),
+        Err(e) => HttpResponse::Ok()
+            .json(json!({ "code": 500, "message": e.to_string()})),
+    }
+}
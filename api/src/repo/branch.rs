@@ -1,17 +1,83 @@
+use crate::auth::extract::{read_user, AuthUser};
 use crate::App;
-use actix_web::web::{Data, Path};
+use actix_web::web::{Data, Json, Path};
 use actix_web::{HttpResponse, Responder};
+use infra::service::branch::{RepositoryBranchCreateParam, RepositoryBranchDefaultParam, RepositoryBranchRenameParam};
 use serde_json::json;
 
 
 pub async fn repo_branch(
     app: Data<App>,
     paths: Path<(String, String)>,
+    user: Option<AuthUser>,
 ) -> impl Responder {
     let (owner, repo) = paths.into_inner();
-    let result = app.repository_branch(repo,owner).await;
+    let result = app.repository_branch(read_user(&user), repo, owner).await;
     match result {
         Ok(repo) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": repo})),
         Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
     }
 }
+
+pub async fn repo_branch_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<RepositoryBranchCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_branch_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(branch) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": branch})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_branch_delete(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, branch) = paths.into_inner();
+    match app.repository_branch_delete(user.uid, repo, owner, branch).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_branch_rename(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<RepositoryBranchRenameParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_branch_rename(user.uid, repo, owner, param.into_inner()).await {
+        Ok(branch) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": branch})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_branch_default(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<RepositoryBranchDefaultParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_branch_set_default(user.uid, repo, owner, param.into_inner()).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::auth::extract::{read_user, AuthUser};
 use std::collections::HashMap;
 use actix_web::{HttpResponse, Responder};
 use actix_web::web::{Data, Path, Query};
@@ -7,10 +8,11 @@ use infra::App;
 pub async fn repo_cat_file(
     path: Path<(String,String,String)>,
     app: Data<App>,
-    query: Query<HashMap<String,String>>
+    query: Query<HashMap<String,String>>,
+    user: Option<AuthUser>,
 ) -> impl Responder {
     let (owner,repo,path) = path.into_inner();
-    let repo = match app.repository_dash(repo,owner).await {
+    let repo = match app.repository_dash(read_user(&user), repo, owner).await {
         Ok(x) => x.repo,
         Err(e) => return HttpResponse::Ok()
             .json(json!({ "code": 500, "message": e.to_string()})),
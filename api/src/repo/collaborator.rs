@@ -0,0 +1,65 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::collaborator::{CollaboratorInviteParam, CollaboratorUpdateParam};
+use serde_json::json;
+
+pub async fn repo_collaborators(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.repository_collaborators(read_user(&user), repo, owner).await {
+        Ok(collaborators) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": collaborators})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_collaborator_invite(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<CollaboratorInviteParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_collaborator_invite(user.uid, repo, owner, param.into_inner()).await {
+        Ok(invitation) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": invitation})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_collaborator_update(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    param: Json<CollaboratorUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, username) = paths.into_inner();
+    match app.repository_collaborator_update(user.uid, repo, owner, username, param.into_inner()).await {
+        Ok(collaborator) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": collaborator})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_collaborator_remove(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, username) = paths.into_inner();
+    match app.repository_collaborator_remove(user.uid, repo, owner, username).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
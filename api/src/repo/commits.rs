@@ -1,3 +1,4 @@
+use crate::auth::extract::{read_user, AuthUser};
 use actix_web::{HttpResponse, Responder};
 use actix_web::web::{Data, Path, Query};
 use serde_json::json;
@@ -7,11 +8,12 @@ use infra::types::pager::QueryPager;
 pub async fn repo_commits(
     path: Path<(String, String)>,
     query: Query<QueryPager>,
-    app: Data<App>
+    app: Data<App>,
+    user: Option<AuthUser>,
 ) -> impl Responder {
     let (owner, repo) = path.into_inner();
     let pager = query.into_inner();
-    match app.repository_commits(repo, owner, pager.page, pager.limit).await {
+    match app.repository_commits(read_user(&user), repo, owner, pager.page, pager.limit).await {
         Ok(commits) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": commits})),
         Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
     }
@@ -0,0 +1,22 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::contents::RepositoryContentsParam;
+use serde_json::json;
+
+pub async fn repo_contents_commit(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<RepositoryContentsParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_contents_commit(user.uid, repo, owner, param.into_inner()).await {
+        Ok(result) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": result})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,66 @@
+use crate::auth::extract::{read_user, AuthUser};
+use actix_web::{HttpResponse, Responder};
+use actix_web::web::{Data, Path};
+use git::diff::compare::GitDiffCompareParam;
+use serde_json::json;
+use infra::App;
+
+pub async fn repo_commit_diff(
+    path: Path<(String, String, String)>,
+    app: Data<App>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, sha) = path.into_inner();
+    match app.repository_commit_diff(read_user(&user), repo, owner, sha).await {
+        Ok(diff) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": diff})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_compare(
+    path: Path<(String, String, String)>,
+    app: Data<App>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, spec) = path.into_inner();
+    let Some(param) = parse_compare_spec(&spec) else {
+        return HttpResponse::Ok().json(json!({"code": 400, "message": "Expected {base}...{head} or {base}..{head}"}));
+    };
+    match app.repository_compare(read_user(&user), repo, owner, param).await {
+        Ok(diff) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": diff})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_mergeability(
+    path: Path<(String, String, String)>,
+    app: Data<App>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, spec) = path.into_inner();
+    let Some(param) = parse_compare_spec(&spec).filter(|x| x.merge_base) else {
+        return HttpResponse::Ok().json(json!({"code": 400, "message": "Expected {base}...{head}"}));
+    };
+    match app.repository_mergeability(read_user(&user), repo, owner, param.base, param.head).await {
+        Ok(result) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": result})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub(crate) fn parse_compare_spec(spec: &str) -> Option<GitDiffCompareParam> {
+    let (base, head, merge_base) = match spec.split_once("...") {
+        Some((base, head)) => (base, head, true),
+        None => {
+            let (base, head) = spec.split_once("..")?;
+            (base, head, false)
+        }
+    };
+    if base.is_empty() || head.is_empty() {
+        return None;
+    }
+    Some(GitDiffCompareParam {
+        base: base.to_string(),
+        head: head.to_string(),
+        merge_base,
+    })
+}
@@ -0,0 +1,46 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::fork::RepositoryForkParam;
+use serde_json::json;
+
+pub async fn repo_fork(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<RepositoryForkParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_fork(user.uid, repo, owner, param.into_inner()).await {
+        Ok(fork) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": fork})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_forks(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.repository_forks(read_user(&user), repo, owner).await {
+        Ok(forks) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": forks})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_fork_network(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.repository_fork_network(read_user(&user), repo, owner).await {
+        Ok(network) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": network})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
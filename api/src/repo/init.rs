@@ -1,20 +1,18 @@
+use crate::auth::extract::AuthUser;
 use actix_web::HttpResponse;
 use actix_web::web::{Data, Json};
-use rsession::Session;
 use serde_json::json;
 use infra::App;
 use infra::service::repository::RepositoryInitParam;
-use infra::types::session::AuthSessionExt;
 
 pub async fn repo_init(
     param: Json<RepositoryInitParam>,
     app: Data<App>,
-    session: Session,
+    user: AuthUser,
 ) -> impl actix_web::Responder {
-    let Some(user) = session.to_auth().await else {
-        return HttpResponse::Ok()
-            .json(json!({"code": 401, "message": "Not login"}))
-    };
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
     match app.repository_init(user.uid, param.into_inner()).await {
         Ok(repo) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": repo})),
         Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
@@ -0,0 +1,169 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path, Query};
+use actix_web::{HttpResponse, Responder};
+use infra::service::issue::{IssueCreateParam, IssueFilter, IssueUpdateParam};
+use infra::service::issue_comment::IssueCommentParam;
+use infra::types::pager::QueryPager;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn repo_issues(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    pager: Query<QueryPager>,
+    filter: Query<IssueFilter>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.issue_list(read_user(&user), repo, owner, pager.into_inner(), filter.into_inner()).await {
+        Ok(issues) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": issues})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_get(read_user(&user), repo, owner, number).await {
+        Ok(issue) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": issue})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<IssueCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.issue_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(issue) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": issue})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_update(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    param: Json<IssueUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_update(user.uid, repo, owner, number, param.into_inner()).await {
+        Ok(issue) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": issue})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_close(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_close(user.uid, repo, owner, number).await {
+        Ok(issue) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": issue})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_reopen(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_reopen(user.uid, repo, owner, number).await {
+        Ok(issue) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": issue})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_comments(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    pager: Query<QueryPager>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_comments(read_user(&user), repo, owner, number, pager.into_inner()).await {
+        Ok(comments) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": comments})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_comment_create(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    param: Json<IssueCommentParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_comment_create(user.uid, repo, owner, number, param.into_inner()).await {
+        Ok(comment) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": comment})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_comment_update(
+    app: Data<App>,
+    paths: Path<(String, String, i64, Uuid)>,
+    param: Json<IssueCommentParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number, uid) = paths.into_inner();
+    match app.issue_comment_update(user.uid, repo, owner, number, uid, param.into_inner()).await {
+        Ok(comment) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": comment})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_comment_delete(
+    app: Data<App>,
+    paths: Path<(String, String, i64, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number, uid) = paths.into_inner();
+    match app.issue_comment_delete(user.uid, repo, owner, number, uid).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_issue_events(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.issue_events(read_user(&user), repo, owner, number).await {
+        Ok(events) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": events})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
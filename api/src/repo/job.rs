@@ -0,0 +1,40 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Path, Query};
+use actix_web::{HttpResponse, Responder};
+use infra::service::job::JobFilter;
+use infra::types::pager::QueryPager;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn repo_jobs(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    pager: Query<QueryPager>,
+    filter: Query<JobFilter>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.job_list(user.uid, repo, owner, pager.into_inner(), filter.into_inner()).await {
+        Ok(jobs) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": jobs})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_job_retry(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.job_retry(user.uid, repo, owner, uid).await {
+        Ok(job) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": job})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,65 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::label::{LabelCreateParam, LabelUpdateParam};
+use serde_json::json;
+
+pub async fn repo_labels(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.label_list(read_user(&user), repo, owner).await {
+        Ok(labels) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": labels})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_label_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<LabelCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.label_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(label) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": label})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_label_update(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    param: Json<LabelUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, name) = paths.into_inner();
+    match app.label_update(user.uid, repo, owner, name, param.into_inner()).await {
+        Ok(label) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": label})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_label_delete(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, name) = paths.into_inner();
+    match app.label_delete(user.uid, repo, owner, name).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::auth::extract::{read_user, AuthUser};
 use actix_web::{HttpResponse, Responder};
 use actix_web::web::{Data, Query};
 use serde_json::json;
@@ -8,9 +9,10 @@ use infra::types::pager::QueryPager;
 pub async fn repo_list(
     pager: Query<QueryPager>,
     query: Query<RepositoryFilter>,
-    app: Data<App>
+    app: Data<App>,
+    user: Option<AuthUser>,
 ) -> impl Responder {
-    match app.repository_list(pager.into_inner(), query.into_inner()).await {
+    match app.repository_list(read_user(&user), pager.into_inner(), query.into_inner()).await {
         Ok(repos) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": repos})),
         Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
     }
@@ -0,0 +1,67 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path, Query};
+use actix_web::{HttpResponse, Responder};
+use infra::service::milestone::{MilestoneCreateParam, MilestoneFilter, MilestoneUpdateParam};
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn repo_milestones(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    filter: Query<MilestoneFilter>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.milestone_list(read_user(&user), repo, owner, filter.into_inner()).await {
+        Ok(milestones) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": milestones})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_milestone_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<MilestoneCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.milestone_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(milestone) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": milestone})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_milestone_update(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    param: Json<MilestoneUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.milestone_update(user.uid, repo, owner, uid, param.into_inner()).await {
+        Ok(milestone) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": milestone})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_milestone_delete(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.milestone_delete(user.uid, repo, owner, uid).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
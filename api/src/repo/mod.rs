@@ -4,4 +4,19 @@ pub mod dash;
 pub mod tree;
 pub mod commits;
 pub mod branch;
-pub mod cat_file;
\ No newline at end of file
+pub mod cat_file;
+pub mod diff;
+pub mod blame;
+pub mod tag;
+pub mod protected_branch;
+pub mod collaborator;
+pub mod fork;
+pub mod pull;
+pub mod pull_review;
+pub mod issue;
+pub mod label;
+pub mod milestone;
+pub mod webhook;
+pub mod job;
+pub mod archive;
+pub mod contents;
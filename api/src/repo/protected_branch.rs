@@ -0,0 +1,66 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::protected_branch::{ProtectedBranchCreateParam, ProtectedBranchUpdateParam};
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn repo_protected_branches(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.protected_branch_list(read_user(&user), repo, owner).await {
+        Ok(rules) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": rules})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_protected_branch_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<ProtectedBranchCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.protected_branch_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(rule) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": rule})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_protected_branch_update(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    param: Json<ProtectedBranchUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.protected_branch_update(user.uid, repo, owner, uid, param.into_inner()).await {
+        Ok(rule) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": rule})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_protected_branch_delete(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.protected_branch_delete(user.uid, repo, owner, uid).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
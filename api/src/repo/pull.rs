@@ -0,0 +1,124 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path, Query};
+use actix_web::{HttpResponse, Responder};
+use infra::service::pull_merge::PullRequestMergeParam;
+use infra::service::pull_request::{PullRequestCreateParam, PullRequestFilter, PullRequestUpdateParam};
+use infra::types::pager::QueryPager;
+use serde_json::json;
+
+pub async fn repo_pulls(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    pager: Query<QueryPager>,
+    filter: Query<PullRequestFilter>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.pull_request_list(read_user(&user), repo, owner, pager.into_inner(), filter.into_inner()).await {
+        Ok(pulls) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": pulls})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_get(read_user(&user), repo, owner, number).await {
+        Ok(pull) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": pull})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<PullRequestCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.pull_request_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(pull) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": pull})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_update(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    param: Json<PullRequestUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_update(user.uid, repo, owner, number, param.into_inner()).await {
+        Ok(pull) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": pull})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_close(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_close(user.uid, repo, owner, number).await {
+        Ok(pull) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": pull})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_reopen(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_reopen(user.uid, repo, owner, number).await {
+        Ok(pull) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": pull})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_merge(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    param: Json<PullRequestMergeParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_merge(user.uid, repo, owner, number, param.into_inner()).await {
+        Ok(result) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": result})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_events(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_events(read_user(&user), repo, owner, number).await {
+        Ok(events) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": events})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,93 @@
+use crate::auth::extract::{read_user, AuthUser};
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::pull_review::{PullRequestCommentParam, PullRequestReviewParam};
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn repo_pull_reviews(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_reviews(read_user(&user), repo, owner, number).await {
+        Ok(reviews) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": reviews})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_review_create(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    param: Json<PullRequestReviewParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_review_create(user.uid, repo, owner, number, param.into_inner()).await {
+        Ok(review) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": review})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_comments(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_comments(read_user(&user), repo, owner, number).await {
+        Ok(threads) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": threads})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_comment_create(
+    app: Data<App>,
+    paths: Path<(String, String, i64)>,
+    param: Json<PullRequestCommentParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number) = paths.into_inner();
+    match app.pull_request_comment_create(user.uid, repo, owner, number, param.into_inner()).await {
+        Ok(comment) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": comment})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_comment_resolve(
+    app: Data<App>,
+    paths: Path<(String, String, i64, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number, uid) = paths.into_inner();
+    match app.pull_request_comment_resolve(user.uid, repo, owner, number, uid, true).await {
+        Ok(thread) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": thread})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_pull_comment_unresolve(
+    app: Data<App>,
+    paths: Path<(String, String, i64, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, number, uid) = paths.into_inner();
+    match app.pull_request_comment_resolve(user.uid, repo, owner, number, uid, false).await {
+        Ok(thread) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": thread})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,49 @@
+use crate::auth::extract::{read_user, AuthUser};
+use actix_web::{HttpResponse, Responder};
+use actix_web::web::{Data, Json, Path};
+use serde_json::json;
+use infra::App;
+use infra::service::tag::RepositoryTagCreateParam;
+
+pub async fn repo_tags(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: Option<AuthUser>,
+) -> impl Responder {
+    let (owner, repo) = paths.into_inner();
+    match app.repository_tags(read_user(&user), repo, owner).await {
+        Ok(tags) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": tags})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_tag_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<RepositoryTagCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.repository_tag_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(tag) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": tag})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_tag_delete(
+    app: Data<App>,
+    paths: Path<(String, String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("repo:write") {
+        return response;
+    }
+    let (owner, repo, tag) = paths.into_inner();
+    match app.repository_tag_delete(user.uid, repo, owner, tag).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,116 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path, Query};
+use actix_web::{HttpResponse, Responder};
+use infra::service::webhook::{WebhookCreateParam, WebhookUpdateParam};
+use infra::types::pager::QueryPager;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn repo_webhooks(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.webhook_list(user.uid, repo, owner).await {
+        Ok(hooks) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": hooks})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_webhook_create(
+    app: Data<App>,
+    paths: Path<(String, String)>,
+    param: Json<WebhookCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo) = paths.into_inner();
+    match app.webhook_create(user.uid, repo, owner, param.into_inner()).await {
+        Ok(hook) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": hook})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_webhook_update(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    param: Json<WebhookUpdateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.webhook_update(user.uid, repo, owner, uid, param.into_inner()).await {
+        Ok(hook) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": hook})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_webhook_delete(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.webhook_delete(user.uid, repo, owner, uid).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_webhook_deliveries(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid)>,
+    pager: Query<QueryPager>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid) = paths.into_inner();
+    match app.webhook_deliveries(user.uid, repo, owner, uid, pager.into_inner()).await {
+        Ok(deliveries) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": deliveries})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_webhook_delivery(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid, delivery) = paths.into_inner();
+    match app.webhook_delivery(user.uid, repo, owner, uid, delivery).await {
+        Ok(delivery) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": delivery})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn repo_webhook_redeliver(
+    app: Data<App>,
+    paths: Path<(String, String, Uuid, Uuid)>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("admin") {
+        return response;
+    }
+    let (owner, repo, uid, delivery) = paths.into_inner();
+    match app.webhook_redeliver(user.uid, repo, owner, uid, delivery).await {
+        Ok(delivery) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": delivery})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,47 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Path};
+use actix_web::{HttpResponse, Responder};
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn user_invitations(
+    app: Data<App>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.collaborator_invitations(user.uid).await {
+        Ok(invitations) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": invitations})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn user_invitation_accept(
+    app: Data<App>,
+    paths: Path<Uuid>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.collaborator_invitation_accept(user.uid, paths.into_inner()).await {
+        Ok(collaborator) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": collaborator})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn user_invitation_decline(
+    app: Data<App>,
+    paths: Path<Uuid>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.collaborator_invitation_decline(user.uid, paths.into_inner()).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod ssh_key;
+pub mod token;
+pub mod invitation;
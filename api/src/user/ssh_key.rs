@@ -0,0 +1,48 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::ssh_key::SshKeyCreateParam;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn user_ssh_keys(
+    app: Data<App>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.ssh_key_list(user.uid).await {
+        Ok(keys) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": keys})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn user_ssh_key_create(
+    app: Data<App>,
+    param: Json<SshKeyCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.ssh_key_create(user.uid, param.into_inner()).await {
+        Ok(key) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": key})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn user_ssh_key_delete(
+    app: Data<App>,
+    paths: Path<Uuid>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.ssh_key_delete(user.uid, paths.into_inner()).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
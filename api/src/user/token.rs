@@ -0,0 +1,48 @@
+use crate::auth::extract::AuthUser;
+use crate::App;
+use actix_web::web::{Data, Json, Path};
+use actix_web::{HttpResponse, Responder};
+use infra::service::token::TokenCreateParam;
+use serde_json::json;
+use uuid::Uuid;
+
+pub async fn user_tokens(
+    app: Data<App>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.token_list(user.uid).await {
+        Ok(tokens) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": tokens})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn user_token_create(
+    app: Data<App>,
+    param: Json<TokenCreateParam>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.token_create(user.uid, param.into_inner()).await {
+        Ok(token) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK", "data": token})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
+
+pub async fn user_token_revoke(
+    app: Data<App>,
+    paths: Path<Uuid>,
+    user: AuthUser,
+) -> impl Responder {
+    if let Err(response) = user.require_scope("user") {
+        return response;
+    }
+    match app.token_revoke(user.uid, paths.into_inner()).await {
+        Ok(_) => HttpResponse::Ok().json(json!({"code": 200, "message": "OK"})),
+        Err(e) => HttpResponse::Ok().json(json!({"code": 500, "message": e.to_string()})),
+    }
+}
@@ -15,6 +15,11 @@ pub async fn main() -> Result<(), Box<dyn std::error::Error>> {
         db: pgsql_client,
         cache: redis_client.clone().into_storage(),
     };
+    let workers = std::env::var("JZFS_JOB_WORKERS")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(4);
+    app.job_workers(workers);
     let session_storage = RedisSessionStorage::new(redis_client.single(), RandKey::RandomSha256(128))
         .set_prefix("session:");
     let session_builder = SessionBuilder::new()
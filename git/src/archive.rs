@@ -0,0 +1,208 @@
+use crate::{rev_commit, AppGit};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use git2::{ObjectType, Oid, TreeWalkMode, TreeWalkResult};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GitArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+impl GitArchiveFormat {
+    /// Splits a file name such as `main.tar.gz` into the name and format.
+    pub fn split(name: &str) -> Option<(&str, GitArchiveFormat)> {
+        [GitArchiveFormat::Zip, GitArchiveFormat::TarGz]
+            .into_iter()
+            .find_map(|x| Some((name.strip_suffix(x.extension())?.strip_suffix('.')?, x)))
+            .filter(|(name, _)| !name.is_empty())
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            GitArchiveFormat::Zip => "zip",
+            GitArchiveFormat::TarGz => "tar.gz",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            GitArchiveFormat::Zip => "application/zip",
+            GitArchiveFormat::TarGz => "application/gzip",
+        }
+    }
+}
+
+/// What an archive is made from: the commit a revision resolves to, whose
+/// tree and commit time fully determine the archive for a given prefix.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitArchiveTarget {
+    pub commit: String,
+    pub tree: String,
+    pub time: i64,
+}
+
+enum ArchiveEntry {
+    Directory,
+    File { oid: Oid, mode: u32 },
+    Symlink { oid: Oid },
+}
+
+impl AppGit {
+    pub fn archive_target(&self, rev: &str) -> anyhow::Result<GitArchiveTarget> {
+        let repo = self.git()?;
+        let commit = rev_commit(&repo, rev)?;
+        Ok(GitArchiveTarget {
+            commit: commit.id().to_string(),
+            tree: commit.tree_id().to_string(),
+            time: commit.time().seconds(),
+        })
+    }
+
+    /// Writes the tree of `target` to `out` as an archive with every path
+    /// under `prefix`, which is empty or ends with `/`. Blobs are read from the
+    /// object database one at a time and written as they are read, nothing is
+    /// checked out. Submodules become empty directories, as with `git archive`.
+    pub fn archive(&self, target: &GitArchiveTarget, format: GitArchiveFormat, prefix: &str, out: impl Write) -> anyhow::Result<()> {
+        let repo = self.git()?;
+        let tree = repo.find_tree(Oid::from_str(&target.tree)?)?;
+        let mut entries = vec![];
+        if !prefix.is_empty() {
+            entries.push((prefix.to_string(), ArchiveEntry::Directory));
+        }
+        tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+            let Some(name) = entry.name() else {
+                return TreeWalkResult::Skip;
+            };
+            let path = format!("{}{}{}", prefix, root, name);
+            let mode = entry.filemode() as u32;
+            let entry = match entry.kind() {
+                Some(ObjectType::Tree) | Some(ObjectType::Commit) => ArchiveEntry::Directory,
+                Some(ObjectType::Blob) if mode == 0o120000 => ArchiveEntry::Symlink { oid: entry.id() },
+                Some(ObjectType::Blob) => ArchiveEntry::File {
+                    oid: entry.id(),
+                    mode: if mode & 0o111 != 0 { 0o755 } else { 0o644 },
+                },
+                _ => return TreeWalkResult::Skip,
+            };
+            match entry {
+                ArchiveEntry::Directory => entries.push((format!("{}/", path), entry)),
+                _ => entries.push((path, entry)),
+            }
+            TreeWalkResult::Ok
+        })?;
+
+        match format {
+            GitArchiveFormat::Zip => {
+                let time = chrono::DateTime::from_timestamp(target.time, 0).unwrap_or_default().naive_utc();
+                let options = SimpleFileOptions::default()
+                    .compression_method(CompressionMethod::Deflated)
+                    .last_modified_time(zip::DateTime::try_from(time).unwrap_or_default());
+                let mut zip = ZipWriter::new_stream(out);
+                for (path, entry) in entries {
+                    match entry {
+                        ArchiveEntry::Directory => zip.add_directory(path, options.unix_permissions(0o755))?,
+                        ArchiveEntry::File { oid, mode } => {
+                            zip.start_file(path, options.unix_permissions(mode))?;
+                            zip.write_all(repo.find_blob(oid)?.content())?;
+                        }
+                        ArchiveEntry::Symlink { oid } => {
+                            let link = String::from_utf8_lossy(repo.find_blob(oid)?.content()).into_owned();
+                            zip.add_symlink(path, link, options)?;
+                        }
+                    }
+                }
+                zip.finish()?.flush()?;
+            }
+            GitArchiveFormat::TarGz => {
+                let mut tar = tar::Builder::new(GzEncoder::new(out, Compression::default()));
+                for (path, entry) in entries {
+                    let mut header = tar::Header::new_gnu();
+                    header.set_mtime(target.time.max(0) as u64);
+                    match entry {
+                        ArchiveEntry::Directory => {
+                            header.set_entry_type(tar::EntryType::Directory);
+                            header.set_mode(0o755);
+                            header.set_size(0);
+                            tar.append_data(&mut header, path, std::io::empty())?;
+                        }
+                        ArchiveEntry::File { oid, mode } => {
+                            let blob = repo.find_blob(oid)?;
+                            header.set_entry_type(tar::EntryType::Regular);
+                            header.set_mode(mode);
+                            header.set_size(blob.size() as u64);
+                            tar.append_data(&mut header, path, blob.content())?;
+                        }
+                        ArchiveEntry::Symlink { oid } => {
+                            let link = String::from_utf8_lossy(repo.find_blob(oid)?.content()).into_owned();
+                            header.set_entry_type(tar::EntryType::Symlink);
+                            header.set_mode(0o777);
+                            header.set_size(0);
+                            tar.append_link(&mut header, path, link)?;
+                        }
+                    }
+                }
+                tar.into_inner()?.finish()?.flush()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+    use std::io::{Cursor, Read};
+
+    #[test]
+    fn test_git_archive_format_split() {
+        assert_eq!(GitArchiveFormat::split("main.zip"), Some(("main", GitArchiveFormat::Zip)));
+        assert_eq!(GitArchiveFormat::split("v1.2.tar.gz"), Some(("v1.2", GitArchiveFormat::TarGz)));
+        assert_eq!(GitArchiveFormat::split("feature/x.tar.gz"), Some(("feature/x", GitArchiveFormat::TarGz)));
+        assert_eq!(GitArchiveFormat::split(".zip"), None);
+        assert_eq!(GitArchiveFormat::split("main.tar"), None);
+    }
+
+    #[test]
+    fn test_git_archive() {
+        let test = TestRepo::new();
+        test.commit("main", &[("a.txt", Some("a\n")), ("dir/b.txt", Some("b\n"))], "first");
+        let target = test.git.archive_target("main").unwrap();
+
+        let mut zip = vec![];
+        test.git.archive(&target, GitArchiveFormat::Zip, "repo-main/", &mut zip).unwrap();
+        let mut zip = zip::ZipArchive::new(Cursor::new(zip)).unwrap();
+        let mut names = zip.file_names().map(|x| x.unwrap().into_owned()).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["repo-main/", "repo-main/a.txt", "repo-main/dir/", "repo-main/dir/b.txt"]);
+        let mut content = String::new();
+        zip.by_name("repo-main/dir/b.txt").unwrap().read_to_string(&mut content).unwrap();
+        assert_eq!(content, "b\n");
+
+        let mut tar_gz = vec![];
+        test.git.archive(&target, GitArchiveFormat::TarGz, "", &mut tar_gz).unwrap();
+        let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(Cursor::new(tar_gz)));
+        let mut files = vec![];
+        for entry in tar.entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            assert_eq!(entry.header().mtime().unwrap(), target.time as u64);
+            files.push((entry.path().unwrap().to_string_lossy().into_owned(), content));
+        }
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                ("a.txt".to_string(), "a\n".to_string()),
+                ("dir/".to_string(), String::new()),
+                ("dir/b.txt".to_string(), "b\n".to_string()),
+            ]
+        );
+    }
+}
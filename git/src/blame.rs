@@ -0,0 +1,145 @@
+use crate::AppGit;
+use crate::tree::msg_tree::GitTreeAuthors;
+use git2::{BlameOptions, Oid};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::path::Path;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitBlameParam {
+    pub path: String,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitBlameResult {
+    pub path: String,
+    pub commit: String,
+    pub hunks: Vec<GitBlameHunk>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitBlameHunk {
+    pub start_line: usize,
+    pub lines: usize,
+    pub commit: String,
+    pub author: GitTreeAuthors,
+    pub timestamp: i64,
+    pub message: String,
+    pub original_path: Option<String>,
+    pub original_start_line: usize,
+    pub content: Vec<String>,
+}
+
+impl AppGit {
+    pub fn blame(&self, param: GitBlameParam) -> anyhow::Result<GitBlameResult> {
+        let repo = self.git()?;
+        let refs = match param.branch {
+            Some(branch) => repo
+                .find_branch(&branch, git2::BranchType::Local)?
+                .into_reference(),
+            None => repo.head()?,
+        };
+        let commit = match param.commit {
+            Some(oid) => repo.find_commit(Oid::from_str(&oid)?)?,
+            None => repo.find_commit(
+                refs.target()
+                    .ok_or(anyhow::anyhow!("Failed to get target from reference"))?,
+            )?,
+        };
+        let path = Path::new(&param.path);
+        let object = commit.tree()?.get_path(path)?.to_object(&repo)?;
+        let blob = object
+            .as_blob()
+            .ok_or(anyhow::anyhow!("Failed to get blob from object"))?;
+        if blob.is_binary() {
+            return Err(anyhow::anyhow!("Cannot blame binary file"));
+        }
+        let content = String::from_utf8_lossy(blob.content());
+        let lines = content.lines().collect::<Vec<_>>();
+
+        let blame = repo.blame_file(path, Some(BlameOptions::new().newest_commit(commit.id())))?;
+        let mut messages: HashMap<Oid, (String, i64)> = HashMap::new();
+        let mut hunks = vec![];
+        for hunk in blame.iter() {
+            let oid = hunk.final_commit_id();
+            if let Entry::Vacant(entry) = messages.entry(oid) {
+                let commit = repo.find_commit(oid)?;
+                entry.insert((
+                    commit.message().unwrap_or("?").to_string(),
+                    commit.time().seconds(),
+                ));
+            }
+            let (message, timestamp) = messages[&oid].clone();
+            let signature = hunk.final_signature();
+            let start_line = hunk.final_start_line();
+            let content = lines
+                .iter()
+                .skip(start_line.saturating_sub(1))
+                .take(hunk.lines_in_hunk())
+                .map(|x| x.to_string())
+                .collect();
+            hunks.push(GitBlameHunk {
+                start_line,
+                lines: hunk.lines_in_hunk(),
+                commit: oid.to_string(),
+                author: GitTreeAuthors {
+                    name: signature.name().unwrap_or("?").to_string(),
+                    email: signature.email().unwrap_or("?").to_string(),
+                    time: signature.when().seconds(),
+                },
+                timestamp,
+                message,
+                original_path: hunk.path().map(|x| x.to_string_lossy().to_string()),
+                original_start_line: hunk.orig_start_line(),
+                content,
+            });
+        }
+        Ok(GitBlameResult {
+            path: param.path,
+            commit: commit.id().to_string(),
+            hunks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_blame() {
+        let test = TestRepo::new();
+        let first = test.commit("main", &[("a.txt", Some("one\ntwo\nthree\n"))], "first");
+        let second = test.commit("main", &[("a.txt", Some("one\n2\nthree\n"))], "second");
+        let blame = test
+            .git
+            .blame(GitBlameParam {
+                path: "a.txt".to_string(),
+                branch: None,
+                commit: None,
+            })
+            .unwrap();
+        assert_eq!(blame.commit, second.to_string());
+        assert_eq!(blame.hunks.len(), 3);
+        assert_eq!(blame.hunks[0].commit, first.to_string());
+        assert_eq!(blame.hunks[1].commit, second.to_string());
+        assert_eq!(blame.hunks[1].start_line, 2);
+        assert_eq!(blame.hunks[1].message, "second");
+        assert_eq!(blame.hunks[1].content, vec!["2".to_string()]);
+
+        let blame = test
+            .git
+            .blame(GitBlameParam {
+                path: "a.txt".to_string(),
+                branch: None,
+                commit: Some(first.to_string()),
+            })
+            .unwrap();
+        assert_eq!(blame.hunks.len(), 1);
+        assert_eq!(blame.hunks[0].lines, 3);
+    }
+}
@@ -1,8 +1,9 @@
-use crate::AppGit;
+use crate::commit::edit::{GitCommitEditParam, GitFileAction};
+use crate::receive::policy::GitCommitPolicy;
 use crate::tree::msg_tree::GitTreeAuthors;
-use git2::*;
+use crate::AppGit;
+use git2::BranchType;
 use serde::{Deserialize, Serialize};
-use std::i32;
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct GitBlobInsertDataParam {
@@ -16,83 +17,48 @@ pub struct GitBlobInsertDataParam {
 }
 
 impl AppGit {
+    /// Writes one file on `branch`, creating or replacing it, as a commit on
+    /// the branch's current head; the branch is created if it does not exist.
     pub fn insert_blob(&self, param: GitBlobInsertDataParam) -> anyhow::Result<String> {
         let repo = self.git()?;
-        let oid = repo.blob(&param.content)?;
-        let branch_check = Branch::name_is_valid(&format!("refs/heads/{}", param.branch));
-        match branch_check {
-            Err(e) => {
-                return Err(anyhow::anyhow!("Invalid branch name: {}", e));
-            }
-            Ok(false) => {
-                return Err(anyhow::anyhow!("Invalid branch name"));
-            }
-            _ => {}
-        }
-        let parent_commit = match repo.find_branch(&param.branch, BranchType::Local) {
-            Ok(branch) => {
-                let commit = branch.get().peel_to_commit()?;
-                Some(commit)
-            }
-            Err(e) => {
-                if e.code() == ErrorCode::NotFound {
-                    repo.set_head(&format!("refs/heads/{}", param.branch))?;
-                    None
-                } else {
-                    return Err(anyhow::anyhow!("find_branch error"));
-                }
-            }
-        };
-        let tree = parent_commit.clone().map(|x| x.tree());
-        let mut tree_builder = match tree {
-            Some(Ok(tree)) => repo.treebuilder(Some(&tree))?,
-            _ => repo.treebuilder(None)?,
+        let path = match param.path.trim_matches('/') {
+            "" => param.file_name,
+            dir => format!("{}/{}", dir, param.file_name),
         };
-        let file_mode = FileMode::Blob;
-        tree_builder.insert(
-            match param.path.is_empty() {
-                true => param.file_name,
-                false => format!("{}/{}", param.path, param.file_name),
-            },
-            oid,
-            i32::from(file_mode),
-        )?;
-        let new_tree_oid = tree_builder.write()?;
-        let new_tree = repo.find_tree(new_tree_oid)?;
-        let author = git2::Signature::new(
-            &param.author.name,
-            &param.author.email,
-            &Time::new(param.author.time, 0),
-        )?;
-        let committer = git2::Signature::new(
-            &param.committer.name,
-            &param.committer.email,
-            &Time::new(param.author.time, 0),
-        )?;
-        let commit_oid = match parent_commit {
-            Some(parent_commit) => {
-                let commit_oid = repo.commit(
-                    Some(&format!("refs/heads/{}", param.branch)),
-                    &author,
-                    &committer,
-                    &param.message,
-                    &new_tree,
-                    &[&parent_commit],
-                )?;
-                commit_oid
+        let parent = repo
+            .find_branch(&param.branch, BranchType::Local)
+            .ok()
+            .map(|x| x.get().peel_to_commit())
+            .transpose()?;
+        let exists = parent
+            .as_ref()
+            .map(|x| x.tree())
+            .transpose()?
+            .is_some_and(|x| x.get_path(path.as_ref()).is_ok());
+        let action = if exists {
+            GitFileAction::Update {
+                path,
+                content: param.content,
+                executable: None,
             }
-            None => {
-                let commit_oid = repo.commit(
-                    Some(&format!("refs/heads/{}", param.branch)),
-                    &author,
-                    &committer,
-                    &param.message,
-                    &new_tree,
-                    &[],
-                )?;
-                commit_oid
+        } else {
+            GitFileAction::Create {
+                path,
+                content: param.content,
+                executable: false,
             }
         };
-        Ok(commit_oid.to_string())
+        let policy = GitCommitPolicy::from_env();
+        self.commit_edit(
+            GitCommitEditParam {
+                branch: param.branch,
+                parent: parent.map(|x| x.id().to_string()),
+                message: param.message,
+                actions: vec![action],
+                author: param.author,
+                committer: param.committer,
+            },
+            &policy,
+        )
     }
 }
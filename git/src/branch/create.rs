@@ -0,0 +1,70 @@
+use crate::branch::list::GitBranchListResult;
+use crate::{AppGit, rev_commit};
+use git2::{Branch, BranchType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitBranchCreateParam {
+    pub name: String,
+    /// Branch, tag or commit the new branch starts from.
+    pub from: String,
+}
+
+impl AppGit {
+    pub fn branch_create(&self, param: GitBranchCreateParam) -> anyhow::Result<GitBranchListResult> {
+        let repo = self.git()?;
+        if !Branch::name_is_valid(&param.name)? {
+            return Err(anyhow::anyhow!("Invalid branch name"));
+        }
+        if repo.find_branch(&param.name, BranchType::Local).is_ok() {
+            return Err(anyhow::anyhow!("Branch already exists"));
+        }
+        let commit = rev_commit(&repo, &param.from)?;
+        let branch = repo.branch(&param.name, &commit, false)?;
+        GitBranchListResult::try_from(&branch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_branch_ops() {
+        let test = TestRepo::new();
+        let first = test.commit("main", &[("a.txt", Some("a\n"))], "first");
+        test.commit("main", &[("a.txt", Some("b\n"))], "second");
+
+        let branch = test
+            .git
+            .branch_create(GitBranchCreateParam {
+                name: "feature/x".to_string(),
+                from: first.to_string(),
+            })
+            .unwrap();
+        assert_eq!(branch.head, first.to_string());
+        assert!(!branch.default);
+        assert!(
+            test.git
+                .branch_create(GitBranchCreateParam {
+                    name: "feature/x".to_string(),
+                    from: "main".to_string(),
+                })
+                .is_err()
+        );
+
+        let renamed = test.git.branch_rename("feature/x", "feature/y").unwrap();
+        assert_eq!(renamed.name, "feature/y");
+        assert_eq!(renamed.head, first.to_string());
+
+        assert!(test.git.branch_delete("main").is_err());
+        test.git.branch_set_default("feature/y").unwrap();
+        let renamed = test.git.branch_rename("feature/y", "trunk").unwrap();
+        assert!(renamed.default);
+        test.git.branch_delete("main").unwrap();
+        let branches = test.git.branch_list().unwrap();
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0].name, "trunk");
+    }
+}
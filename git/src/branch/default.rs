@@ -0,0 +1,17 @@
+use crate::AppGit;
+use git2::BranchType;
+
+impl AppGit {
+    /// Points HEAD, and therefore the repository's default branch, at `name`.
+    pub fn branch_set_default(&self, name: &str) -> anyhow::Result<()> {
+        let repo = self.git()?;
+        let branch = repo.find_branch(name, BranchType::Local)?;
+        let refname = branch
+            .get()
+            .name()
+            .ok_or(anyhow::anyhow!("Invalid branch name"))?
+            .to_string();
+        repo.set_head(&refname)?;
+        Ok(())
+    }
+}
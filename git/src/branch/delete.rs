@@ -0,0 +1,14 @@
+use crate::AppGit;
+use git2::BranchType;
+
+impl AppGit {
+    pub fn branch_delete(&self, name: &str) -> anyhow::Result<()> {
+        let repo = self.git()?;
+        let mut branch = repo.find_branch(name, BranchType::Local)?;
+        if branch.is_head() {
+            return Err(anyhow::anyhow!("Cannot delete the default branch"));
+        }
+        branch.delete()?;
+        Ok(())
+    }
+}
@@ -1 +1,5 @@
+pub mod create;
+pub mod default;
+pub mod delete;
 pub mod list;
+pub mod rename;
@@ -0,0 +1,24 @@
+use crate::AppGit;
+use crate::branch::list::GitBranchListResult;
+use git2::{Branch, BranchType};
+
+impl AppGit {
+    /// Renames a local branch; HEAD follows the branch when it is the default one.
+    pub fn branch_rename(&self, name: &str, new_name: &str) -> anyhow::Result<GitBranchListResult> {
+        let repo = self.git()?;
+        if !Branch::name_is_valid(new_name)? {
+            return Err(anyhow::anyhow!("Invalid branch name"));
+        }
+        if repo.find_branch(new_name, BranchType::Local).is_ok() {
+            return Err(anyhow::anyhow!("Branch already exists"));
+        }
+        let is_head = repo.find_branch(name, BranchType::Local)?.is_head();
+        let branch = repo
+            .find_branch(name, BranchType::Local)?
+            .rename(new_name, false)?;
+        if is_head && !branch.is_head() {
+            repo.set_head(&format!("refs/heads/{}", new_name))?;
+        }
+        GitBranchListResult::try_from(&branch)
+    }
+}
@@ -0,0 +1,407 @@
+use crate::receive::policy::GitCommitPolicy;
+use crate::tree::msg_tree::GitTreeAuthors;
+use crate::AppGit;
+use git2::{Branch, ErrorCode, FileMode, Index, IndexEntry, IndexTime, Oid, Repository, Signature, Time};
+use serde::{Deserialize, Serialize};
+
+/// A change to one file of a commit made with [`AppGit::commit_edit`]. Paths
+/// are relative to the root of the tree and may be nested, e.g. `src/lib.rs`.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum GitFileAction {
+    /// Adds a file; fails if the path is taken.
+    Create {
+        path: String,
+        content: Vec<u8>,
+        #[serde(default)]
+        executable: bool,
+    },
+    /// Replaces the content of a file, keeping its mode unless `executable`
+    /// is given.
+    Update {
+        path: String,
+        content: Vec<u8>,
+        #[serde(default)]
+        executable: Option<bool>,
+    },
+    Delete { path: String },
+    /// Renames a file, keeping its mode, optionally replacing its content.
+    Move {
+        from: String,
+        path: String,
+        #[serde(default)]
+        content: Option<Vec<u8>>,
+    },
+    /// Sets or clears the executable bit of a file.
+    Chmod { path: String, executable: bool },
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitCommitEditParam {
+    pub branch: String,
+    /// The commit `branch` is expected to point at; the commit fails if it
+    /// moved. When the branch does not exist it is created, from this commit
+    /// or, without one, as a root commit.
+    pub parent: Option<String>,
+    pub message: String,
+    pub actions: Vec<GitFileAction>,
+    pub author: GitTreeAuthors,
+    pub committer: GitTreeAuthors,
+}
+
+/// Checks a path given by a client and strips surrounding slashes.
+fn edit_path(path: &str) -> anyhow::Result<String> {
+    let trimmed = path.trim_matches('/');
+    let invalid = trimmed.is_empty()
+        || trimmed.contains(['\\', '\0'])
+        || trimmed
+            .split('/')
+            .any(|x| x.is_empty() || x == "." || x == ".." || x.eq_ignore_ascii_case(".git"));
+    if invalid {
+        return Err(anyhow::anyhow!("Invalid path: {}", path));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn file_mode(executable: bool) -> u32 {
+    if executable {
+        i32::from(FileMode::BlobExecutable) as u32
+    } else {
+        i32::from(FileMode::Blob) as u32
+    }
+}
+
+fn is_regular(mode: u32) -> bool {
+    mode == file_mode(false) || mode == file_mode(true)
+}
+
+/// The tree being edited, kept as an in-memory index so nested paths, and
+/// files replacing directories or the other way round, are handled by git.
+struct EditIndex<'a> {
+    repo: &'a Repository,
+    index: Index,
+}
+
+impl EditIndex<'_> {
+    fn file(&self, path: &str) -> Option<IndexEntry> {
+        self.index.get_path(path.as_ref(), 0)
+    }
+
+    fn existing(&self, path: &str) -> anyhow::Result<IndexEntry> {
+        self.file(path).ok_or_else(|| anyhow::anyhow!("File not found: {}", path))
+    }
+
+    /// Fails unless `path` is free: neither a file nor a directory, and not
+    /// under a file.
+    fn vacant(&mut self, path: &str) -> anyhow::Result<()> {
+        if self.file(path).is_some() || self.index.find_prefix(format!("{}/", path)).is_ok() {
+            return Err(anyhow::anyhow!("Path already exists: {}", path));
+        }
+        let mut parent = path;
+        while let Some((dir, _)) = parent.rsplit_once('/') {
+            if self.file(dir).is_some() {
+                return Err(anyhow::anyhow!("Path is inside a file: {}", path));
+            }
+            parent = dir;
+        }
+        Ok(())
+    }
+
+    fn put(&mut self, path: &str, id: Oid, mode: u32) -> anyhow::Result<()> {
+        self.index.add(&IndexEntry {
+            ctime: IndexTime::new(0, 0),
+            mtime: IndexTime::new(0, 0),
+            dev: 0,
+            ino: 0,
+            mode,
+            uid: 0,
+            gid: 0,
+            file_size: 0,
+            id,
+            flags: 0,
+            flags_extended: 0,
+            path: path.as_bytes().to_vec(),
+        })?;
+        Ok(())
+    }
+
+    fn apply(&mut self, action: &GitFileAction) -> anyhow::Result<()> {
+        match action {
+            GitFileAction::Create { path, content, executable } => {
+                let path = edit_path(path)?;
+                self.vacant(&path)?;
+                let id = self.repo.blob(content)?;
+                self.put(&path, id, file_mode(*executable))
+            }
+            GitFileAction::Update { path, content, executable } => {
+                let path = edit_path(path)?;
+                let entry = self.existing(&path)?;
+                if !is_regular(entry.mode) {
+                    return Err(anyhow::anyhow!("Not a regular file: {}", path));
+                }
+                let mode = executable.map(file_mode).unwrap_or(entry.mode);
+                let id = self.repo.blob(content)?;
+                self.put(&path, id, mode)
+            }
+            GitFileAction::Delete { path } => {
+                let path = edit_path(path)?;
+                self.existing(&path)?;
+                self.index.remove_path(path.as_ref())?;
+                Ok(())
+            }
+            GitFileAction::Move { from, path, content } => {
+                let from = edit_path(from)?;
+                let path = edit_path(path)?;
+                let entry = self.existing(&from)?;
+                self.index.remove_path(from.as_ref())?;
+                self.vacant(&path)?;
+                let id = match content {
+                    Some(_) if !is_regular(entry.mode) => {
+                        return Err(anyhow::anyhow!("Not a regular file: {}", from));
+                    }
+                    Some(content) => self.repo.blob(content)?,
+                    None => entry.id,
+                };
+                self.put(&path, id, entry.mode)
+            }
+            GitFileAction::Chmod { path, executable } => {
+                let path = edit_path(path)?;
+                let entry = self.existing(&path)?;
+                if !is_regular(entry.mode) {
+                    return Err(anyhow::anyhow!("Not a regular file: {}", path));
+                }
+                self.put(&path, entry.id, file_mode(*executable))
+            }
+        }
+    }
+}
+
+fn signature(authors: &GitTreeAuthors) -> anyhow::Result<Signature<'static>> {
+    Ok(Signature::new(&authors.name, &authors.email, &Time::new(authors.time, 0))?)
+}
+
+impl AppGit {
+    /// Applies `actions` in order to the tree of `parent` and commits the
+    /// result on `branch`, without a working tree. The branch is moved with a
+    /// compare-and-swap, so nothing is written if it moved in the meantime,
+    /// and only if the commit passes `policy`. Returns the new commit.
+    pub fn commit_edit(&self, param: GitCommitEditParam, policy: &GitCommitPolicy) -> anyhow::Result<String> {
+        let repo = self.git()?;
+        let refname = format!("refs/heads/{}", param.branch);
+        if !Branch::name_is_valid(&refname)? {
+            return Err(anyhow::anyhow!("Invalid branch name: {}", param.branch));
+        }
+        if param.actions.is_empty() {
+            return Err(anyhow::anyhow!("Nothing to commit"));
+        }
+        let current = match repo.refname_to_id(&refname) {
+            Ok(current) => Some(current),
+            Err(e) if e.code() == ErrorCode::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+        let parent = param.parent.as_deref().map(Oid::from_str).transpose()?;
+        if current.is_some() && current != parent {
+            return Err(anyhow::anyhow!("Branch has moved, refresh and try again"));
+        }
+        let parent = parent.map(|x| repo.find_commit(x)).transpose()?;
+
+        let mut edit = EditIndex {
+            repo: &repo,
+            index: Index::new()?,
+        };
+        if let Some(parent) = &parent {
+            edit.index.read_tree(&parent.tree()?)?;
+        }
+        for action in &param.actions {
+            edit.apply(action)?;
+        }
+        let tree = repo.find_tree(edit.index.write_tree_to(&repo)?)?;
+        if parent.as_ref().is_some_and(|x| x.tree_id() == tree.id()) {
+            return Err(anyhow::anyhow!("Nothing to commit"));
+        }
+
+        let parents = parent.iter().collect::<Vec<_>>();
+        let oid = repo.commit(
+            None,
+            &signature(&param.author)?,
+            &signature(&param.committer)?,
+            &param.message,
+            &tree,
+            &parents,
+        )?;
+        if let Some(violation) = policy.violation(&repo, &repo.find_commit(oid)?)? {
+            return Err(anyhow::anyhow!(violation));
+        }
+        let log = format!("commit: {}", param.message.lines().next().unwrap_or_default());
+        let moved = match current {
+            Some(current) => repo.reference_matching(&refname, oid, true, current, &log).map(|_| ()),
+            None => repo.reference(&refname, oid, false, &log).map(|_| ()),
+        };
+        if let Err(e) = moved {
+            return Err(match e.code() {
+                ErrorCode::Modified | ErrorCode::Exists => anyhow::anyhow!("Branch has moved, refresh and try again"),
+                _ => e.into(),
+            });
+        }
+        // the first commit of an empty repository gives HEAD its branch
+        if repo.head().is_err_and(|x| x.code() == ErrorCode::UnbornBranch) {
+            repo.set_head(&refname)?;
+        }
+        Ok(oid.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    fn authors() -> GitTreeAuthors {
+        GitTreeAuthors {
+            name: "tester".to_string(),
+            email: "tester@example.com".to_string(),
+            time: 1_700_000_000,
+        }
+    }
+
+    fn edit(branch: &str, parent: Option<String>, actions: Vec<GitFileAction>) -> GitCommitEditParam {
+        GitCommitEditParam {
+            branch: branch.to_string(),
+            parent,
+            message: "edit".to_string(),
+            actions,
+            author: authors(),
+            committer: authors(),
+        }
+    }
+
+    #[test]
+    fn test_git_commit_edit() {
+        let test = TestRepo::new();
+        let base = test
+            .commit("main", &[("a.txt", Some("a\n")), ("dir/b.txt", Some("b\n"))], "first")
+            .to_string();
+        let sha = test
+            .git
+            .commit_edit(
+                edit(
+                    "main",
+                    Some(base.clone()),
+                    vec![
+                        GitFileAction::Create {
+                            path: "src/bin/run.sh".to_string(),
+                            content: b"#!/bin/sh\n".to_vec(),
+                            executable: true,
+                        },
+                        GitFileAction::Update {
+                            path: "a.txt".to_string(),
+                            content: b"a2\n".to_vec(),
+                            executable: None,
+                        },
+                        GitFileAction::Move {
+                            from: "dir/b.txt".to_string(),
+                            path: "docs/b.md".to_string(),
+                            content: None,
+                        },
+                    ],
+                ),
+                &GitCommitPolicy::default(),
+            )
+            .unwrap();
+
+        let repo = test.git.git().unwrap();
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap().to_string(), sha);
+        let commit = repo.find_commit(Oid::from_str(&sha).unwrap()).unwrap();
+        assert_eq!(commit.parent_id(0).unwrap().to_string(), base);
+        let tree = commit.tree().unwrap();
+        let script = tree.get_path("src/bin/run.sh".as_ref()).unwrap();
+        assert_eq!(script.filemode(), i32::from(FileMode::BlobExecutable));
+        let a = tree.get_path("a.txt".as_ref()).unwrap().to_object(&repo).unwrap();
+        assert_eq!(a.as_blob().unwrap().content(), b"a2\n");
+        assert!(tree.get_path("docs/b.md".as_ref()).is_ok());
+        assert!(tree.get_path("dir".as_ref()).is_err());
+
+        // the branch moved past `base`, so a commit against it is refused
+        let moved = test.git.commit_edit(
+            edit(
+                "main",
+                Some(base),
+                vec![GitFileAction::Delete { path: "a.txt".to_string() }],
+            ),
+            &GitCommitPolicy::default(),
+        );
+        assert!(moved.unwrap_err().to_string().contains("moved"));
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap().to_string(), sha);
+    }
+
+    #[test]
+    fn test_git_commit_edit_invalid() {
+        let test = TestRepo::new();
+        let base = test.commit("main", &[("a.txt", Some("a\n"))], "first").to_string();
+        let invalid = [
+            GitFileAction::Create {
+                path: "a.txt".to_string(),
+                content: vec![],
+                executable: false,
+            },
+            GitFileAction::Create {
+                path: "a.txt/b.txt".to_string(),
+                content: vec![],
+                executable: false,
+            },
+            GitFileAction::Create {
+                path: "../b.txt".to_string(),
+                content: vec![],
+                executable: false,
+            },
+            GitFileAction::Delete { path: "missing.txt".to_string() },
+            GitFileAction::Chmod {
+                path: "a.txt".to_string(),
+                executable: false,
+            },
+        ];
+        for action in invalid {
+            assert!(test.git.commit_edit(edit("main", Some(base.clone()), vec![action]), &GitCommitPolicy::default()).is_err());
+        }
+
+        // a missing branch is created from the parent
+        let sha = test
+            .git
+            .commit_edit(
+                edit(
+                    "feature",
+                    Some(base.clone()),
+                    vec![GitFileAction::Delete { path: "a.txt".to_string() }],
+                ),
+                &GitCommitPolicy::default(),
+            )
+            .unwrap();
+        let repo = test.git.git().unwrap();
+        assert_eq!(repo.refname_to_id("refs/heads/feature").unwrap().to_string(), sha);
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap().to_string(), base);
+    }
+
+    #[test]
+    fn test_git_commit_edit_policy() {
+        let test = TestRepo::new();
+        let base = test.commit("main", &[("a.txt", Some("a\n"))], "first").to_string();
+        let policy = GitCommitPolicy {
+            max_file_size: 4,
+            require_subject: false,
+            max_subject: Some(3),
+        };
+        let create = |content: &[u8]| GitFileAction::Create {
+            path: "b.txt".to_string(),
+            content: content.to_vec(),
+            executable: false,
+        };
+        let large = test.git.commit_edit(edit("main", Some(base.clone()), vec![create(b"too large\n")]), &policy);
+        assert!(large.unwrap_err().to_string().contains("file b.txt"));
+        // "edit" is one character over the subject limit
+        let long = test.git.commit_edit(edit("main", Some(base.clone()), vec![create(b"b\n")]), &policy);
+        assert!(long.unwrap_err().to_string().contains("subject longer"));
+
+        let repo = test.git.git().unwrap();
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap().to_string(), base);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::AppGit;
+use crate::{rev_commit, AppGit};
 use git2::Oid;
 use serde::{Deserialize, Serialize};
 
@@ -27,6 +27,36 @@ pub struct GitCommit {
     pub timestamp: i64,
 }
 
+impl From<&git2::Commit<'_>> for GitCommit {
+    fn from(commit: &git2::Commit<'_>) -> Self {
+        GitCommit {
+            hash: commit.id().to_string(),
+            author: commit
+                .author()
+                .name()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            email: commit
+                .author()
+                .email()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            committer: commit
+                .committer()
+                .name()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            committer_email: commit
+                .committer()
+                .email()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+            message: commit.message().map(|s| s.to_string()).unwrap_or_default(),
+            timestamp: commit.time().seconds(),
+        }
+    }
+}
+
 impl AppGit {
     pub fn commit_list(&self, param: GitCommitListParam) -> anyhow::Result<GitCommitListResult> {
         let repo = self.git()?;
@@ -71,31 +101,7 @@ impl AppGit {
                 }
             }
             let commit = repo.find_commit(oid)?;
-            commits.push(GitCommit {
-                hash: commit.id().to_string(),
-                author: commit
-                    .author()
-                    .name()
-                    .map(|s| s.to_string())
-                    .unwrap_or_default(),
-                email: commit
-                    .author()
-                    .email()
-                    .map(|s| s.to_string())
-                    .unwrap_or_default(),
-                committer: commit
-                    .committer()
-                    .name()
-                    .map(|s| s.to_string())
-                    .unwrap_or_default(),
-                committer_email: commit
-                    .committer()
-                    .email()
-                    .map(|s| s.to_string())
-                    .unwrap_or_default(),
-                message: commit.message().map(|s| s.to_string()).unwrap_or_default(),
-                timestamp: commit.time().seconds(),
-            });
+            commits.push(GitCommit::from(&commit));
 
             count += 1;
             if count >= limit {
@@ -108,6 +114,45 @@ impl AppGit {
             total,
         })
     }
+
+    /// Commits reachable from `to` but not from `from`, oldest first. A `from`
+    /// that is missing or no longer exists yields the whole history of `to`.
+    pub fn commit_range(&self, from: Option<&str>, to: &str) -> anyhow::Result<Vec<GitCommit>> {
+        self.commit_range_excluding(to, from.as_slice())
+    }
+
+    /// Commits reachable from `to` but from none of `exclude`, oldest first.
+    /// Revisions in `exclude` that do not resolve are ignored.
+    pub fn commit_range_excluding<S: AsRef<str>>(&self, to: &str, exclude: &[S]) -> anyhow::Result<Vec<GitCommit>> {
+        let repo = self.git()?;
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(rev_commit(&repo, to)?.id())?;
+        for commit in exclude.iter().filter_map(|x| rev_commit(&repo, x.as_ref()).ok()) {
+            revwalk.hide(commit.id())?;
+        }
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        let mut commits = vec![];
+        for oid in revwalk {
+            commits.push(GitCommit::from(&repo.find_commit(oid?)?));
+        }
+        Ok(commits)
+    }
+
+    /// Commits of `to` left after excluding the revisions of `exclude` one by
+    /// one: the n-th list holds those reachable from none of the first n. A
+    /// commit that is in a list but not in the next one is therefore reached
+    /// by that next revision before any later one.
+    pub fn commit_range_split<S: AsRef<str>>(&self, to: &str, exclude: &[S]) -> anyhow::Result<Vec<Vec<GitCommit>>> {
+        let mut ranges: Vec<Vec<GitCommit>> = vec![];
+        for n in 1..=exclude.len() {
+            let range = match ranges.last() {
+                Some(last) if last.is_empty() => vec![],
+                _ => self.commit_range_excluding(to, &exclude[..n])?,
+            };
+            ranges.push(range);
+        }
+        Ok(ranges)
+    }
 }
 
 #[cfg(test)]
@@ -127,4 +172,64 @@ mod tests {
         });
         dbg!(commits.ok());
     }
+
+    #[test]
+    fn test_git_commit_range() {
+        let test = crate::test_repo::TestRepo::new();
+        let first = test.commit("main", &[("a.txt", Some("a\n"))], "first");
+        let second = test.commit("main", &[("a.txt", Some("b\n"))], "second");
+        let third = test.commit("main", &[("a.txt", Some("c\n"))], "third");
+        let hashes = |commits: Vec<GitCommit>| commits.into_iter().map(|x| x.hash).collect::<Vec<_>>();
+
+        let range = test.git.commit_range(Some(&first.to_string()), "main").unwrap();
+        assert_eq!(hashes(range), vec![second.to_string(), third.to_string()]);
+        let range = test.git.commit_range(None, "main").unwrap();
+        assert_eq!(hashes(range).len(), 3);
+        let missing = "0123456789012345678901234567890123456789";
+        assert_eq!(test.git.commit_range(Some(missing), "main").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_git_commit_range_excluding() {
+        let test = crate::test_repo::TestRepo::new();
+        let base = test.commit("main", &[("a.txt", Some("a\n"))], "base");
+        test.branch("feature", base);
+        let feature = test.commit("feature", &[("b.txt", Some("b\n"))], "feature");
+        let main = test.commit("main", &[("a.txt", Some("b\n"))], "main");
+        test.branch("topic", feature);
+        let topic = test.commit("topic", &[("c.txt", Some("c\n"))], "topic");
+        let hashes = |commits: Vec<GitCommit>| commits.into_iter().map(|x| x.hash).collect::<Vec<_>>();
+
+        let range = test.git.commit_range_excluding("topic", &["main", "feature"]).unwrap();
+        assert_eq!(hashes(range), vec![topic.to_string()]);
+        let range = test.git.commit_range_excluding("topic", &[main.to_string()]).unwrap();
+        assert_eq!(hashes(range), vec![feature.to_string(), topic.to_string()]);
+        let none: [&str; 0] = [];
+        assert_eq!(test.git.commit_range_excluding("topic", &none).unwrap().len(), 3);
+        let missing = "0123456789012345678901234567890123456789";
+        assert_eq!(test.git.commit_range_excluding("main", &[missing]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_git_commit_range_split() {
+        let test = crate::test_repo::TestRepo::new();
+        let base = test.commit("main", &[("a.txt", Some("a\n"))], "base");
+        test.branch("feature", base);
+        let feature = test.commit("feature", &[("b.txt", Some("b\n"))], "feature");
+        test.commit("main", &[("a.txt", Some("b\n"))], "main");
+        test.branch("topic", feature);
+        let topic = test.commit("topic", &[("c.txt", Some("c\n"))], "topic");
+        let hashes = |commits: &Vec<GitCommit>| commits.iter().map(|x| x.hash.clone()).collect::<Vec<_>>();
+
+        // deleting topic: base is reached by main, the default branch, while
+        // its parent is reached only by feature
+        let ranges = test.git.commit_range_split("topic", &["main", "feature"]).unwrap();
+        let expected = vec![vec![feature.to_string(), topic.to_string()], vec![topic.to_string()]];
+        assert_eq!(ranges.iter().map(hashes).collect::<Vec<_>>(), expected);
+        let ranges = test.git.commit_range_split("feature", &["main", "topic", "feature"]).unwrap();
+        let expected = vec![vec![feature.to_string()], vec![], vec![]];
+        assert_eq!(ranges.iter().map(hashes).collect::<Vec<_>>(), expected);
+        let none: [&str; 0] = [];
+        assert!(test.git.commit_range_split("topic", &none).unwrap().is_empty());
+    }
 }
@@ -1,2 +1,4 @@
+pub mod edit;
 pub mod list;
+pub mod references;
 pub mod tree;
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+/// Words that close the issue they precede, e.g. `fixes #12`.
+const CLOSING_KEYWORDS: &[&str] = &[
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
+
+/// An issue or pull request mentioned in a commit message as `#N` or `owner/repo#N`.
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct GitCommitReference {
+    /// Owner and name of another repository; `None` for the repository itself.
+    pub repository: Option<(String, String)>,
+    pub number: i64,
+    /// The reference follows a closing keyword.
+    pub closes: bool,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+}
+
+/// Parses a single word such as `#12`, `owner/repo#3` or `(#4).`.
+fn parse_reference(word: &str) -> Option<(Option<(String, String)>, i64)> {
+    let word = word
+        .trim_start_matches(['(', '['])
+        .trim_end_matches(['.', ',', ';', ':', ')', ']', '!', '?']);
+    let (repository, number) = word.split_once('#')?;
+    if number.is_empty() || !number.chars().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+    let number = number.parse().ok().filter(|x| *x > 0)?;
+    if repository.is_empty() {
+        return Some((None, number));
+    }
+    let (owner, repo) = repository.split_once('/')?;
+    let valid = |x: &str| !x.is_empty() && x.chars().all(is_name_char);
+    if !valid(owner) || !valid(repo) {
+        return None;
+    }
+    Some((Some((owner.to_string(), repo.to_string())), number))
+}
+
+/// Finds the issue and pull request references in a commit message. Each
+/// target is reported once, closing if any of its mentions was.
+pub fn commit_references(message: &str) -> Vec<GitCommitReference> {
+    let mut references: Vec<GitCommitReference> = vec![];
+    let mut previous = "";
+    for word in message.split_whitespace() {
+        if let Some((repository, number)) = parse_reference(word) {
+            let keyword = previous.trim_end_matches(':').to_lowercase();
+            let closes = CLOSING_KEYWORDS.contains(&keyword.as_str());
+            match references.iter_mut().find(|x| x.repository == repository && x.number == number) {
+                Some(reference) => reference.closes |= closes,
+                None => references.push(GitCommitReference { repository, number, closes }),
+            }
+        }
+        previous = word;
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(repository: Option<(&str, &str)>, number: i64, closes: bool) -> GitCommitReference {
+        GitCommitReference {
+            repository: repository.map(|(owner, repo)| (owner.to_string(), repo.to_string())),
+            number,
+            closes,
+        }
+    }
+
+    #[test]
+    fn test_git_commit_references() {
+        let message = "Fix parser crash\n\nFixes #12, closes owner/repo#3.\nSee #4 and (#12)\nResolved: #5 and issue#6 #x #0";
+        assert_eq!(
+            commit_references(message),
+            vec![
+                reference(None, 12, true),
+                reference(Some(("owner", "repo")), 3, true),
+                reference(None, 4, false),
+                reference(None, 5, true),
+            ]
+        );
+        assert!(commit_references("no references here").is_empty());
+    }
+}
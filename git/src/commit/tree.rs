@@ -1,4 +1,5 @@
 use crate::AppGit;
+use crate::diff::patch::delta_status;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -24,7 +25,7 @@ pub struct GitTreeResult {
 }
 
 impl AppGit {
-    pub fn commit_tree(&self, param: GitTreeParam) -> anyhow::Result<()> {
+    pub fn commit_tree(&self, param: GitTreeParam) -> anyhow::Result<GitTreeResult> {
         let repo = self.git()?;
         let refs = match param.branch {
             Some(branch) => repo
@@ -63,20 +64,7 @@ impl AppGit {
                 Some(path) => path.to_str().unwrap().to_string(),
                 None => "".to_string(),
             };
-            let status = match delta.status() {
-                git2::Delta::Added => "added",
-                git2::Delta::Deleted => "deleted",
-                git2::Delta::Modified => "modified",
-                git2::Delta::Renamed => "renamed",
-                git2::Delta::Copied => "copied",
-                git2::Delta::Ignored => "ignored",
-                git2::Delta::Untracked => "untracked",
-                git2::Delta::Unmodified => "unmodified",
-                git2::Delta::Typechange => "typechange",
-                git2::Delta::Unreadable => "unreadable",
-                git2::Delta::Conflicted => "conflicted",
-            }
-            .to_string();
+            let status = delta_status(delta.status()).to_string();
             let map = GitTreeFileMap {
                 status,
                 old_file: Some(GitTreeFile {
@@ -88,8 +76,7 @@ impl AppGit {
             };
             tree_map.push(map);
         }
-        dbg!(tree_map);
-        Ok(())
+        Ok(GitTreeResult { data: tree_map })
     }
 }
 
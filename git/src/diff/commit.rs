@@ -0,0 +1,52 @@
+use crate::commit::list::GitCommit;
+use crate::diff::patch::{GitDiffResult, diff_trees};
+use crate::{AppGit, rev_commit};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffCommitResult {
+    pub commit: GitCommit,
+    pub parents: Vec<String>,
+    pub diff: GitDiffResult,
+}
+
+impl AppGit {
+    /// Diffs a commit against its first parent, or against the empty tree for a root commit.
+    pub fn diff_commit(&self, sha: &str) -> anyhow::Result<GitDiffCommitResult> {
+        let repo = self.git()?;
+        let commit = rev_commit(&repo, sha)?;
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = diff_trees(&repo, parent_tree.as_ref(), Some(&tree), None)?;
+        Ok(GitDiffCommitResult {
+            commit: GitCommit::from(&commit),
+            parents: commit.parent_ids().map(|x| x.to_string()).collect(),
+            diff,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_diff_commit() {
+        let test = TestRepo::new();
+        let root = test.commit("main", &[("README.md", Some("hello\n"))], "root");
+        let child = test.commit("main", &[("README.md", Some("hello\nworld\n"))], "child");
+
+        let diff = test.git.diff_commit(&root.to_string()).unwrap();
+        assert!(diff.parents.is_empty());
+        assert_eq!(diff.diff.files[0].status, "added");
+
+        let diff = test.git.diff_commit("main").unwrap();
+        assert_eq!(diff.commit.hash, child.to_string());
+        assert_eq!(diff.parents, vec![root.to_string()]);
+        assert_eq!(diff.diff.stats.additions, 1);
+        assert_eq!(diff.diff.stats.deletions, 0);
+    }
+}
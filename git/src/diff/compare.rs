@@ -0,0 +1,98 @@
+use crate::commit::list::GitCommit;
+use crate::diff::patch::{GitDiffResult, diff_trees};
+use crate::{AppGit, rev_commit};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffCompareParam {
+    pub base: String,
+    pub head: String,
+    /// Compare `head` against the merge base of both sides (`base...head`)
+    /// instead of against `base` itself (`base..head`).
+    pub merge_base: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffCompareResult {
+    pub base: String,
+    pub head: String,
+    pub merge_base: Option<String>,
+    pub commits: Vec<GitCommit>,
+    pub diff: GitDiffResult,
+}
+
+impl AppGit {
+    pub fn diff_compare(&self, param: GitDiffCompareParam) -> anyhow::Result<GitDiffCompareResult> {
+        let repo = self.git()?;
+        let base = rev_commit(&repo, &param.base)?;
+        let head = rev_commit(&repo, &param.head)?;
+        let merge_base = repo.merge_base(base.id(), head.id()).ok();
+        let from = match (param.merge_base, merge_base) {
+            (true, Some(oid)) => repo.find_commit(oid)?,
+            (true, None) => {
+                return Err(anyhow::anyhow!(
+                    "No common ancestor between {} and {}",
+                    param.base,
+                    param.head
+                ));
+            }
+            (false, _) => base.clone(),
+        };
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head.id())?;
+        revwalk.hide(base.id())?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        let mut commits = vec![];
+        for oid in revwalk {
+            commits.push(GitCommit::from(&repo.find_commit(oid?)?));
+        }
+
+        let diff = diff_trees(&repo, Some(&from.tree()?), Some(&head.tree()?), None)?;
+        Ok(GitDiffCompareResult {
+            base: base.id().to_string(),
+            head: head.id().to_string(),
+            merge_base: merge_base.map(|x| x.to_string()),
+            commits,
+            diff,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_diff_compare() {
+        let test = TestRepo::new();
+        let root = test.commit("main", &[("a.txt", Some("a\n"))], "root");
+        test.branch("feature", root);
+        test.commit("main", &[("main.txt", Some("main\n"))], "main work");
+        let head = test.commit("feature", &[("feature.txt", Some("feature\n"))], "feature work");
+
+        let three_dot = test
+            .git
+            .diff_compare(GitDiffCompareParam {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                merge_base: true,
+            })
+            .unwrap();
+        assert_eq!(three_dot.merge_base, Some(root.to_string()));
+        assert_eq!(three_dot.commits.len(), 1);
+        assert_eq!(three_dot.commits[0].hash, head.to_string());
+        assert_eq!(three_dot.diff.files.len(), 1);
+
+        let two_dot = test
+            .git
+            .diff_compare(GitDiffCompareParam {
+                base: "main".to_string(),
+                head: "feature".to_string(),
+                merge_base: false,
+            })
+            .unwrap();
+        assert_eq!(two_dot.diff.files.len(), 2);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod commit;
+pub mod compare;
+pub mod patch;
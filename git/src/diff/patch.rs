@@ -0,0 +1,251 @@
+use crate::AppGit;
+use git2::{Delta, Diff, DiffFindOptions, DiffLineType, DiffOptions, Patch, Repository, Tree};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffTreeParam {
+    pub old_tree: Option<String>,
+    pub new_tree: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GitDiffResult {
+    pub files: Vec<GitDiffFile>,
+    pub stats: GitDiffStats,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+pub struct GitDiffStats {
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffFile {
+    pub status: String,
+    pub old_path: Option<String>,
+    pub new_path: Option<String>,
+    pub old_oid: String,
+    pub new_oid: String,
+    pub old_mode: u32,
+    pub new_mode: u32,
+    pub binary: bool,
+    pub additions: usize,
+    pub deletions: usize,
+    pub hunks: Vec<GitDiffHunk>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<GitDiffLine>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitDiffLine {
+    pub origin: String,
+    pub old_lineno: Option<u32>,
+    pub new_lineno: Option<u32>,
+    pub content: String,
+}
+
+pub(crate) fn delta_status(delta: Delta) -> &'static str {
+    match delta {
+        Delta::Added => "added",
+        Delta::Deleted => "deleted",
+        Delta::Modified => "modified",
+        Delta::Renamed => "renamed",
+        Delta::Copied => "copied",
+        Delta::Ignored => "ignored",
+        Delta::Untracked => "untracked",
+        Delta::Unmodified => "unmodified",
+        Delta::Typechange => "typechange",
+        Delta::Unreadable => "unreadable",
+        Delta::Conflicted => "conflicted",
+    }
+}
+
+/// Diffs two trees with rename and copy detection and collects every patch.
+pub(crate) fn diff_trees(
+    repo: &Repository,
+    old_tree: Option<&Tree>,
+    new_tree: Option<&Tree>,
+    options: Option<&mut DiffOptions>,
+) -> anyhow::Result<GitDiffResult> {
+    let mut diff = repo.diff_tree_to_tree(old_tree, new_tree, options)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true).copies(true)))?;
+    diff_result(&diff)
+}
+
+pub(crate) fn diff_result(diff: &Diff) -> anyhow::Result<GitDiffResult> {
+    let mut files = vec![];
+    for idx in 0..diff.deltas().len() {
+        let patch = Patch::from_diff(diff, idx)?;
+        let Some(delta) = diff.get_delta(idx) else {
+            continue;
+        };
+        let old_file = delta.old_file();
+        let new_file = delta.new_file();
+        let mut file = GitDiffFile {
+            status: delta_status(delta.status()).to_string(),
+            old_path: match delta.status() {
+                Delta::Added => None,
+                _ => old_file.path().map(|x| x.to_string_lossy().to_string()),
+            },
+            new_path: match delta.status() {
+                Delta::Deleted => None,
+                _ => new_file.path().map(|x| x.to_string_lossy().to_string()),
+            },
+            old_oid: old_file.id().to_string(),
+            new_oid: new_file.id().to_string(),
+            old_mode: u32::from(old_file.mode()),
+            new_mode: u32::from(new_file.mode()),
+            binary: delta.flags().is_binary(),
+            additions: 0,
+            deletions: 0,
+            hunks: vec![],
+        };
+        if let Some(patch) = patch {
+            file.binary = file.binary || patch.delta().flags().is_binary();
+            let (_, additions, deletions) = patch.line_stats()?;
+            file.additions = additions;
+            file.deletions = deletions;
+            for hunk_idx in 0..patch.num_hunks() {
+                let (hunk, line_count) = patch.hunk(hunk_idx)?;
+                let mut lines = Vec::with_capacity(line_count);
+                for line_idx in 0..line_count {
+                    let line = patch.line_in_hunk(hunk_idx, line_idx)?;
+                    let origin = match line.origin_value() {
+                        DiffLineType::Addition => "add",
+                        DiffLineType::Deletion => "delete",
+                        DiffLineType::Context => "context",
+                        _ => continue,
+                    };
+                    let content = String::from_utf8_lossy(line.content());
+                    lines.push(GitDiffLine {
+                        origin: origin.to_string(),
+                        old_lineno: line.old_lineno(),
+                        new_lineno: line.new_lineno(),
+                        content: content.trim_end_matches(['\r', '\n']).to_string(),
+                    });
+                }
+                file.hunks.push(GitDiffHunk {
+                    header: String::from_utf8_lossy(hunk.header()).trim_end().to_string(),
+                    old_start: hunk.old_start(),
+                    old_lines: hunk.old_lines(),
+                    new_start: hunk.new_start(),
+                    new_lines: hunk.new_lines(),
+                    lines,
+                });
+            }
+        }
+        files.push(file);
+    }
+    let stats = diff.stats()?;
+    Ok(GitDiffResult {
+        files,
+        stats: GitDiffStats {
+            files_changed: stats.files_changed(),
+            additions: stats.insertions(),
+            deletions: stats.deletions(),
+        },
+    })
+}
+
+impl AppGit {
+    pub fn diff_tree(&self, param: GitDiffTreeParam) -> anyhow::Result<GitDiffResult> {
+        let repo = self.git()?;
+        let old_tree = match param.old_tree {
+            Some(old_tree) => Some(repo.revparse_single(&old_tree)?.peel_to_tree()?),
+            None => None,
+        };
+        let new_tree = repo.revparse_single(&param.new_tree)?.peel_to_tree()?;
+        diff_trees(&repo, old_tree.as_ref(), Some(&new_tree), None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_diff_tree() {
+        let test = TestRepo::new();
+        let first = test.commit("main", &[("a.txt", Some("one\ntwo\nthree\n"))], "first");
+        let second = test.commit(
+            "main",
+            &[("a.txt", Some("one\n2\nthree\n")), ("b/c.txt", Some("new\n"))],
+            "second",
+        );
+        let diff = test
+            .git
+            .diff_tree(GitDiffTreeParam {
+                old_tree: Some(first.to_string()),
+                new_tree: second.to_string(),
+            })
+            .unwrap();
+        assert_eq!(diff.stats.files_changed, 2);
+        assert_eq!(diff.stats.additions, 2);
+        assert_eq!(diff.stats.deletions, 1);
+        let modified = diff
+            .files
+            .iter()
+            .find(|x| x.new_path.as_deref() == Some("a.txt"))
+            .unwrap();
+        assert_eq!(modified.status, "modified");
+        assert_eq!(modified.hunks.len(), 1);
+        let removed = modified.hunks[0]
+            .lines
+            .iter()
+            .find(|x| x.origin == "delete")
+            .unwrap();
+        assert_eq!(removed.content, "two");
+        assert_eq!(removed.old_lineno, Some(2));
+        let added = diff
+            .files
+            .iter()
+            .find(|x| x.new_path.as_deref() == Some("b/c.txt"))
+            .unwrap();
+        assert_eq!(added.status, "added");
+        assert!(added.old_path.is_none());
+    }
+
+    #[test]
+    fn test_git_diff_rename_and_binary() {
+        let test = TestRepo::new();
+        let content = "line\n".repeat(20);
+        let first = test.commit("main", &[("old.txt", Some(&content))], "first");
+        let second = test.commit(
+            "main",
+            &[
+                ("old.txt", None),
+                ("new.txt", Some(&content)),
+                ("blob.bin", Some("\0\u{1}\u{2}")),
+            ],
+            "second",
+        );
+        let diff = test
+            .git
+            .diff_tree(GitDiffTreeParam {
+                old_tree: Some(first.to_string()),
+                new_tree: second.to_string(),
+            })
+            .unwrap();
+        let renamed = diff.files.iter().find(|x| x.status == "renamed").unwrap();
+        assert_eq!(renamed.old_path.as_deref(), Some("old.txt"));
+        assert_eq!(renamed.new_path.as_deref(), Some("new.txt"));
+        let binary = diff
+            .files
+            .iter()
+            .find(|x| x.new_path.as_deref() == Some("blob.bin"))
+            .unwrap();
+        assert!(binary.binary);
+        assert!(binary.hunks.is_empty());
+    }
+}
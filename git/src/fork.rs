@@ -0,0 +1,71 @@
+use crate::AppGit;
+use anyhow::Context;
+
+impl AppGit {
+    /// Creates `target` as a fork of this repository. Instead of copying
+    /// objects the fork borrows them through `objects/info/alternates`, so only
+    /// objects pushed to the fork afterwards are stored in it. Branches, tags
+    /// and the default branch are copied over.
+    pub fn fork(&self, target: &AppGit) -> anyhow::Result<()> {
+        let source = self.git()?;
+        if target.path_buf.exists() {
+            return Err(anyhow::anyhow!("Fork target already exists"));
+        }
+        let result = self.fork_into(&source, target);
+        if result.is_err() {
+            std::fs::remove_dir_all(&target.path_buf).ok();
+        }
+        result
+    }
+
+    fn fork_into(&self, source: &git2::Repository, target: &AppGit) -> anyhow::Result<()> {
+        target.init()?;
+        let objects = std::fs::canonicalize(self.path_buf.join("objects"))?;
+        let info = target.path_buf.join("objects").join("info");
+        std::fs::create_dir_all(&info)?;
+        std::fs::write(info.join("alternates"), format!("{}\n", objects.to_string_lossy()))?;
+
+        let repo = target.git()?;
+        for reference in source.references()? {
+            let reference = reference?;
+            let (Some(name), Some(oid)) = (reference.name(), reference.target()) else {
+                continue;
+            };
+            if name.starts_with("refs/heads/") || name.starts_with("refs/tags/") {
+                repo.reference(name, oid, false, "fork")
+                    .with_context(|| format!("Failed to copy {}", name))?;
+            }
+        }
+        if let Some(head) = source.find_reference("HEAD")?.symbolic_target() {
+            repo.set_head(head)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_repo::TestRepo;
+    use crate::AppGit;
+
+    #[test]
+    fn test_git_fork() {
+        let source = TestRepo::new();
+        let root = source.commit("main", &[("a.txt", Some("a\n"))], "root");
+        source.branch("dev", root);
+        let fork = AppGit {
+            path_buf: source.git.path_buf.with_extension("fork"),
+        };
+        source.git.fork(&fork).unwrap();
+        let repo = fork.git().unwrap();
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap(), root);
+        assert_eq!(repo.refname_to_id("refs/heads/dev").unwrap(), root);
+        assert_eq!(repo.head().unwrap().name(), Some("refs/heads/main"));
+        assert!(repo.find_commit(root).is_ok());
+        // objects are shared, not copied
+        let loose = fork.path_buf.join("objects").join(&root.to_string()[..2]);
+        assert!(!loose.exists());
+        drop(repo);
+        std::fs::remove_dir_all(&fork.path_buf).ok();
+    }
+}
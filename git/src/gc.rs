@@ -0,0 +1,38 @@
+use crate::AppGit;
+use anyhow::Context;
+use std::process::Command;
+
+impl AppGit {
+    /// Runs `git gc --auto`, which packs loose objects and prunes unreachable
+    /// ones only once enough have piled up. Forks borrow the objects of their
+    /// source, so a repository with forks must keep its unreachable objects.
+    pub fn gc(&self, keep_unreachable: bool) -> anyhow::Result<()> {
+        let mut command = Command::new("git");
+        command.arg("--git-dir").arg(&self.path_buf).args(["gc", "--auto", "--quiet"]);
+        if keep_unreachable {
+            command.arg("--prune=never");
+        }
+        let output = command.output().with_context(|| "Failed to run git gc")?;
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "git gc failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_gc() {
+        let test = TestRepo::new();
+        let head = test.commit("main", &[("a.txt", Some("a\n"))], "first");
+        test.git.gc(true).unwrap();
+        test.git.gc(false).unwrap();
+        assert_eq!(test.git.git().unwrap().head().unwrap().target(), Some(head));
+    }
+}
@@ -41,10 +41,31 @@ impl AppGit {
     }
 }
 
+/// Resolves a branch, tag, or (abbreviated) commit id to the commit it points at.
+pub(crate) fn rev_commit<'a>(
+    repo: &'a git2::Repository,
+    rev: &str,
+) -> anyhow::Result<git2::Commit<'a>> {
+    repo.revparse_single(rev)
+        .and_then(|x| x.peel_to_commit())
+        .with_context(|| format!("Revision not found: {}", rev))
+}
+
+pub mod archive;
+pub mod blame;
 pub mod blob;
 pub mod branch;
 pub mod commit;
+pub mod diff;
+pub mod fork;
+pub mod gc;
+pub mod merge;
+pub mod pull;
+pub mod receive;
 pub mod remote;
 pub mod tag;
 pub mod tree;
-pub mod cat_file;
\ No newline at end of file
+pub mod cat_file;
+
+#[cfg(test)]
+mod test_repo;
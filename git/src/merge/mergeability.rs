@@ -0,0 +1,241 @@
+use crate::{rev_commit, AppGit};
+use git2::{IndexEntry, MergeFileOptions, Repository};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitMergeability {
+    pub base: String,
+    pub head: String,
+    pub merge_base: Option<String>,
+    /// Commits on the head missing from the base.
+    pub ahead: usize,
+    /// Commits on the base missing from the head.
+    pub behind: usize,
+    /// The base can be moved to the head without a merge commit.
+    pub fast_forward: bool,
+    pub mergeable: bool,
+    pub conflicts: Vec<GitConflictFile>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GitConflictKind {
+    /// Both sides changed the same lines; see the hunks.
+    Content,
+    /// One side modified the file, the other deleted it.
+    ModifyDelete,
+    Binary,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitConflictFile {
+    pub path: String,
+    pub kind: GitConflictKind,
+    pub hunks: Vec<GitConflictHunk>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GitConflictHunk {
+    /// First line of the conflict in the merged file, 1-based.
+    pub line: usize,
+    pub ours: Vec<String>,
+    pub ancestor: Vec<String>,
+    pub theirs: Vec<String>,
+}
+
+/// Splits a file merged with diff3-style markers into its conflicting regions.
+/// Markers are matched exactly as git writes them, labels included, so lines
+/// such as setext underlines are kept as content.
+fn conflict_hunks(content: &str) -> Vec<GitConflictHunk> {
+    enum Side {
+        Outside,
+        Ours,
+        Ancestor,
+        Theirs,
+    }
+    let mut hunks = vec![];
+    let mut hunk = GitConflictHunk::default();
+    let mut side = Side::Outside;
+    for (index, line) in content.lines().enumerate() {
+        match side {
+            Side::Outside if line.starts_with("<<<<<<< ") => {
+                hunk.line = index + 1;
+                side = Side::Ours;
+            }
+            Side::Outside => {}
+            Side::Ours | Side::Ancestor if line == "=======" => side = Side::Theirs,
+            Side::Ours if line.starts_with("||||||| ") => side = Side::Ancestor,
+            Side::Ours => hunk.ours.push(line.to_string()),
+            Side::Ancestor => hunk.ancestor.push(line.to_string()),
+            Side::Theirs if line.starts_with(">>>>>>> ") => {
+                hunks.push(std::mem::take(&mut hunk));
+                side = Side::Outside;
+            }
+            Side::Theirs => hunk.theirs.push(line.to_string()),
+        }
+    }
+    hunks
+}
+
+fn is_binary(repo: &Repository, entry: &IndexEntry) -> anyhow::Result<bool> {
+    Ok(repo.find_blob(entry.id)?.is_binary())
+}
+
+fn conflict_file(
+    repo: &Repository,
+    ancestor: Option<IndexEntry>,
+    ours: Option<IndexEntry>,
+    theirs: Option<IndexEntry>,
+    labels: (&str, &str),
+) -> anyhow::Result<Option<GitConflictFile>> {
+    let Some(entry) = ours.as_ref().or(theirs.as_ref()).or(ancestor.as_ref()) else {
+        return Ok(None);
+    };
+    let path = String::from_utf8_lossy(&entry.path).to_string();
+    let (Some(ours), Some(theirs)) = (ours, theirs) else {
+        return Ok(Some(GitConflictFile {
+            path,
+            kind: GitConflictKind::ModifyDelete,
+            hunks: vec![],
+        }));
+    };
+    if is_binary(repo, &ours)? || is_binary(repo, &theirs)? {
+        return Ok(Some(GitConflictFile {
+            path,
+            kind: GitConflictKind::Binary,
+            hunks: vec![],
+        }));
+    }
+    // files added on both sides merge against an empty ancestor
+    let ancestor = match ancestor {
+        Some(ancestor) => ancestor,
+        None => IndexEntry {
+            ctime: ours.ctime,
+            mtime: ours.mtime,
+            dev: 0,
+            ino: 0,
+            mode: ours.mode,
+            uid: 0,
+            gid: 0,
+            file_size: 0,
+            id: repo.blob(b"")?,
+            flags: ours.flags,
+            flags_extended: 0,
+            path: ours.path.clone(),
+        },
+    };
+    let mut options = MergeFileOptions::new();
+    options.style_diff3(true).our_label(labels.0).their_label(labels.1).ancestor_label("base");
+    let merged = repo.merge_file_from_index(&ancestor, &ours, &theirs, Some(&mut options))?;
+    Ok(Some(GitConflictFile {
+        path,
+        kind: GitConflictKind::Content,
+        hunks: conflict_hunks(&String::from_utf8_lossy(merged.content())),
+    }))
+}
+
+impl AppGit {
+    /// Previews merging `head` into `base` without writing anything.
+    pub fn mergeability(&self, base: &str, head: &str) -> anyhow::Result<GitMergeability> {
+        let repo = self.git()?;
+        // objects the preview needs, such as the empty ancestor of files added
+        // on both sides, are kept in memory and dropped with this handle
+        let odb = repo.odb()?;
+        let _mempack = odb.add_new_mempack_backend(1000)?;
+        let base_commit = rev_commit(&repo, base)?;
+        let head_commit = rev_commit(&repo, head)?;
+        let merge_base = repo.merge_base(base_commit.id(), head_commit.id()).ok();
+        let (ahead, behind) = repo.graph_ahead_behind(head_commit.id(), base_commit.id())?;
+        let mut conflicts = vec![];
+        if ahead > 0 && behind > 0 {
+            let index = repo.merge_commits(&base_commit, &head_commit, None)?;
+            if index.has_conflicts() {
+                for conflict in index.conflicts()? {
+                    let conflict = conflict?;
+                    if let Some(file) = conflict_file(&repo, conflict.ancestor, conflict.our, conflict.their, (base, head))? {
+                        conflicts.push(file);
+                    }
+                }
+            }
+        }
+        Ok(GitMergeability {
+            base: base_commit.id().to_string(),
+            head: head_commit.id().to_string(),
+            merge_base: merge_base.map(|x| x.to_string()),
+            ahead,
+            behind,
+            fast_forward: ahead > 0 && behind == 0,
+            mergeable: ahead > 0 && conflicts.is_empty(),
+            conflicts,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+    use git2::{ObjectType, Oid};
+
+    #[test]
+    fn test_git_mergeability() {
+        let test = TestRepo::new();
+        let root = test.commit("main", &[("a.txt", Some("one\ntwo\nthree\n")), ("d.txt", Some("d\n"))], "root");
+        test.branch("feature", root);
+        test.branch("clean", root);
+        test.commit("clean", &[("c.txt", Some("c\n"))], "clean");
+        let result = test.git.mergeability("main", "clean").unwrap();
+        assert_eq!((result.ahead, result.behind), (1, 0));
+        assert!(result.fast_forward && result.mergeable);
+
+        test.commit("feature", &[("a.txt", Some("one\nfeature\nthree\n")), ("d.txt", None)], "feature");
+        test.commit("main", &[("a.txt", Some("one\nmain\nthree\n")), ("d.txt", Some("changed\n"))], "main");
+        let result = test.git.mergeability("main", "feature").unwrap();
+        assert_eq!((result.ahead, result.behind), (1, 1));
+        assert_eq!(result.merge_base, Some(root.to_string()));
+        assert!(!result.fast_forward && !result.mergeable);
+        assert_eq!(result.conflicts.len(), 2);
+        let content = result.conflicts.iter().find(|x| x.path == "a.txt").unwrap();
+        assert_eq!(content.kind, GitConflictKind::Content);
+        assert_eq!(
+            content.hunks,
+            vec![GitConflictHunk {
+                line: 2,
+                ours: vec!["main".to_string()],
+                ancestor: vec!["two".to_string()],
+                theirs: vec!["feature".to_string()],
+            }]
+        );
+        let deleted = result.conflicts.iter().find(|x| x.path == "d.txt").unwrap();
+        assert_eq!(deleted.kind, GitConflictKind::ModifyDelete);
+    }
+
+    #[test]
+    fn test_git_mergeability_add_add() {
+        let test = TestRepo::new();
+        let root = test.commit("main", &[("a.txt", Some("a\n"))], "root");
+        test.branch("feature", root);
+        test.commit("feature", &[("new.txt", Some("feature\n"))], "feature");
+        test.commit("main", &[("new.txt", Some("main\n"))], "main");
+        let result = test.git.mergeability("main", "feature").unwrap();
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].kind, GitConflictKind::Content);
+        assert_eq!(result.conflicts[0].hunks[0].theirs, vec!["feature".to_string()]);
+        let empty = Oid::hash_object(ObjectType::Blob, b"").unwrap();
+        assert!(test.git.git().unwrap().find_blob(empty).is_err());
+    }
+
+    #[test]
+    fn test_git_conflict_hunks_markers() {
+        let content = "Title\n=======\n<<<<<<< main\nours\n========\n||||||| base\nbase\n=======\ntheirs\n>>>>>>>> x\n>>>>>>> feature\n";
+        assert_eq!(
+            conflict_hunks(content),
+            vec![GitConflictHunk {
+                line: 3,
+                ours: vec!["ours".to_string(), "========".to_string()],
+                ancestor: vec!["base".to_string()],
+                theirs: vec!["theirs".to_string(), ">>>>>>>> x".to_string()],
+            }]
+        );
+    }
+}
@@ -0,0 +1,271 @@
+pub mod mergeability;
+
+use crate::receive::policy::GitCommitPolicy;
+use crate::tree::msg_tree::GitTreeAuthors;
+use crate::AppGit;
+use anyhow::Context;
+use git2::{Commit, Index, Oid, Repository, Signature, Time};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GitMergeStrategy {
+    /// A merge commit with the target and the head as parents.
+    Merge,
+    /// A single commit on the target holding all changes of the head.
+    Squash,
+    /// The head's commits replayed onto the target, which is fast-forwarded
+    /// to them; nothing is rewritten when the head already contains the target.
+    Rebase,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitMergeParam {
+    /// Full name of the ref to update, e.g. `refs/heads/main`.
+    pub target: String,
+    /// The oid `target` is expected to point at; the update fails if it moved.
+    pub expected: String,
+    pub head: String,
+    pub strategy: GitMergeStrategy,
+    /// Message of the merge or squash commit; unused when rebasing.
+    pub message: String,
+    pub author: GitTreeAuthors,
+    pub committer: GitTreeAuthors,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum GitMergeResult {
+    Merged { sha: String },
+    /// Nothing was written; `paths` lists every file that conflicted.
+    Conflict { paths: Vec<String> },
+}
+
+fn signature(authors: &GitTreeAuthors) -> anyhow::Result<Signature<'static>> {
+    Ok(Signature::new(&authors.name, &authors.email, &Time::new(authors.time, 0))?)
+}
+
+fn conflicts(index: &Index) -> anyhow::Result<Vec<String>> {
+    let mut paths = BTreeSet::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        for entry in [conflict.ancestor, conflict.our, conflict.their].into_iter().flatten() {
+            paths.insert(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    Ok(paths.into_iter().collect())
+}
+
+impl AppGit {
+    /// Merges `head` into `target` without a working tree and moves the ref
+    /// with a compare-and-swap on `expected`. Every commit the merge creates
+    /// has to pass `policy`, or the ref is left alone.
+    pub fn merge(&self, param: GitMergeParam, policy: &GitCommitPolicy) -> anyhow::Result<GitMergeResult> {
+        let repo = self.git()?;
+        let expected = Oid::from_str(&param.expected)?;
+        let current = repo
+            .refname_to_id(&param.target)
+            .with_context(|| format!("Reference not found: {}", param.target))?;
+        if current != expected {
+            return Err(anyhow::anyhow!("Target branch has moved, refresh and try again"));
+        }
+        let base = repo.find_commit(expected)?;
+        let head = repo.find_commit(Oid::from_str(&param.head)?)?;
+        if repo.graph_descendant_of(base.id(), head.id())? || base.id() == head.id() {
+            return Err(anyhow::anyhow!("Nothing to merge"));
+        }
+        let author = signature(&param.author)?;
+        let committer = signature(&param.committer)?;
+        let merged = match param.strategy {
+            GitMergeStrategy::Merge | GitMergeStrategy::Squash => {
+                let mut index = repo.merge_commits(&base, &head, None)?;
+                if index.has_conflicts() {
+                    return Ok(GitMergeResult::Conflict { paths: conflicts(&index)? });
+                }
+                let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+                let parents = match param.strategy {
+                    GitMergeStrategy::Merge => vec![&base, &head],
+                    _ => vec![&base],
+                };
+                let oid = repo.commit(None, &author, &committer, &param.message, &tree, &parents)?;
+                if let Some(violation) = policy.violation(&repo, &repo.find_commit(oid)?)? {
+                    return Err(anyhow::anyhow!(violation));
+                }
+                oid
+            }
+            GitMergeStrategy::Rebase if repo.graph_descendant_of(head.id(), base.id())? => head.id(),
+            GitMergeStrategy::Rebase => match Self::rebase(&repo, &base, &head, &committer, policy)? {
+                Ok(oid) => oid,
+                Err(paths) => return Ok(GitMergeResult::Conflict { paths }),
+            },
+        };
+        repo.reference_matching(
+            &param.target,
+            merged,
+            true,
+            expected,
+            &format!("merge {}: {:?}", head.id(), param.strategy),
+        )
+        .context("Target branch has moved, refresh and try again")?;
+        Ok(GitMergeResult::Merged { sha: merged.to_string() })
+    }
+
+    /// Replays the commits of `head` missing from `base` on top of it, keeping
+    /// their authors and messages. Returns the conflicting paths of the first
+    /// commit that does not apply, and fails on the first one breaking
+    /// `policy`.
+    fn rebase(
+        repo: &Repository,
+        base: &Commit,
+        head: &Commit,
+        committer: &Signature,
+        policy: &GitCommitPolicy,
+    ) -> anyhow::Result<Result<Oid, Vec<String>>> {
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head.id())?;
+        revwalk.hide(base.id())?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        let mut onto = base.clone();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                return Err(anyhow::anyhow!("Cannot rebase merge commit {}", commit.id()));
+            }
+            let mut index = repo.cherrypick_commit(&commit, &onto, 0, None)?;
+            if index.has_conflicts() {
+                return Ok(Err(conflicts(&index)?));
+            }
+            let tree = repo.find_tree(index.write_tree_to(repo)?)?;
+            let oid = repo.commit(
+                None,
+                &commit.author(),
+                committer,
+                commit.message().unwrap_or_default(),
+                &tree,
+                &[&onto],
+            )?;
+            onto = repo.find_commit(oid)?;
+            if let Some(violation) = policy.violation(repo, &onto)? {
+                return Err(anyhow::anyhow!(violation));
+            }
+        }
+        Ok(Ok(onto.id()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    fn merger() -> GitTreeAuthors {
+        GitTreeAuthors {
+            name: "merger".to_string(),
+            email: "merger@example.com".to_string(),
+            time: 1_700_000_200,
+        }
+    }
+
+    fn param(target: Oid, head: Oid, strategy: GitMergeStrategy) -> GitMergeParam {
+        GitMergeParam {
+            target: "refs/heads/main".to_string(),
+            expected: target.to_string(),
+            head: head.to_string(),
+            strategy,
+            message: "merge feature".to_string(),
+            author: merger(),
+            committer: merger(),
+        }
+    }
+
+    #[test]
+    fn test_git_merge_strategies() {
+        for strategy in [GitMergeStrategy::Merge, GitMergeStrategy::Squash, GitMergeStrategy::Rebase] {
+            let test = TestRepo::new();
+            let root = test.commit("main", &[("a.txt", Some("a\n"))], "root");
+            test.branch("feature", root);
+            test.commit("feature", &[("b.txt", Some("b\n"))], "first");
+            let head = test.commit("feature", &[("c.txt", Some("c\n"))], "second");
+            let main = test.commit("main", &[("a.txt", Some("b\n"))], "main");
+
+            let result = test.git.merge(param(main, head, strategy), &GitCommitPolicy::default()).unwrap();
+            let GitMergeResult::Merged { sha } = result else {
+                panic!("unexpected conflict");
+            };
+            let repo = test.git.git().unwrap();
+            assert_eq!(repo.refname_to_id("refs/heads/main").unwrap().to_string(), sha);
+            let merged = repo.find_commit(Oid::from_str(&sha).unwrap()).unwrap();
+            let tree = merged.tree().unwrap();
+            for path in ["a.txt", "b.txt", "c.txt"] {
+                assert!(tree.get_name(path).is_some(), "{:?} lost {}", strategy, path);
+            }
+            match strategy {
+                GitMergeStrategy::Merge => {
+                    assert_eq!(merged.parent_ids().collect::<Vec<_>>(), vec![main, head]);
+                }
+                GitMergeStrategy::Squash => {
+                    assert_eq!(merged.parent_ids().collect::<Vec<_>>(), vec![main]);
+                }
+                GitMergeStrategy::Rebase => {
+                    assert_eq!(merged.message(), Some("second"));
+                    assert_eq!(merged.parent(0).unwrap().parent_id(0).unwrap(), main);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_git_merge_conflict_and_stale_target() {
+        let test = TestRepo::new();
+        let root = test.commit("main", &[("a.txt", Some("a\n"))], "root");
+        test.branch("feature", root);
+        let head = test.commit("feature", &[("a.txt", Some("feature\n"))], "feature");
+        let main = test.commit("main", &[("a.txt", Some("main\n"))], "main");
+
+        for strategy in [GitMergeStrategy::Merge, GitMergeStrategy::Rebase] {
+            let result = test.git.merge(param(main, head, strategy), &GitCommitPolicy::default()).unwrap();
+            assert_eq!(result, GitMergeResult::Conflict { paths: vec!["a.txt".to_string()] });
+        }
+        let repo = test.git.git().unwrap();
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap(), main);
+
+        assert!(test.git.merge(param(root, head, GitMergeStrategy::Merge), &GitCommitPolicy::default()).is_err());
+
+        // fast-forward when the head already contains the target
+        test.branch("next", main);
+        let next = test.commit("next", &[("c.txt", Some("c\n"))], "next");
+        let result = test.git.merge(param(main, next, GitMergeStrategy::Rebase), &GitCommitPolicy::default()).unwrap();
+        assert_eq!(result, GitMergeResult::Merged { sha: next.to_string() });
+    }
+
+    #[test]
+    fn test_git_merge_policy() {
+        let test = TestRepo::new();
+        let root = test.commit("main", &[("a.txt", Some("a\n"))], "root");
+        test.branch("feature", root);
+        let head = test.commit("feature", &[("big.bin", Some(&"x".repeat(100)))], "big");
+        let main = test.commit("main", &[("b.txt", Some("b\n"))], "main");
+
+        let size = GitCommitPolicy {
+            max_file_size: 50,
+            ..GitCommitPolicy::default()
+        };
+        let subject = GitCommitPolicy {
+            max_subject: Some(5),
+            ..GitCommitPolicy::default()
+        };
+        for strategy in [GitMergeStrategy::Merge, GitMergeStrategy::Squash, GitMergeStrategy::Rebase] {
+            let refused = test.git.merge(param(main, head, strategy), &size);
+            assert!(refused.is_err_and(|e| e.to_string().contains("big.bin")), "{:?}", strategy);
+        }
+        // "merge feature" is over the limit, "big" as replayed is not
+        let refused = test.git.merge(param(main, head, GitMergeStrategy::Squash), &subject);
+        assert!(refused.is_err_and(|e| e.to_string().contains("subject longer")));
+        let repo = test.git.git().unwrap();
+        assert_eq!(repo.refname_to_id("refs/heads/main").unwrap(), main);
+
+        let result = test.git.merge(param(main, head, GitMergeStrategy::Rebase), &subject).unwrap();
+        assert!(matches!(result, GitMergeResult::Merged { .. }));
+    }
+}
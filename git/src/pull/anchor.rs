@@ -0,0 +1,87 @@
+use crate::{rev_commit, AppGit};
+use git2::{Blob, DiffOptions, Patch, Repository};
+use std::path::Path;
+
+fn file_blob<'a>(repo: &'a Repository, rev: &str, path: &str) -> anyhow::Result<Option<Blob<'a>>> {
+    let tree = rev_commit(repo, rev)?.tree()?;
+    let Ok(entry) = tree.get_path(Path::new(path)) else {
+        return Ok(None);
+    };
+    Ok(entry.to_object(repo)?.into_blob().ok())
+}
+
+impl AppGit {
+    pub fn merge_base(&self, one: &str, two: &str) -> anyhow::Result<Option<String>> {
+        let repo = self.git()?;
+        let one = rev_commit(&repo, one)?;
+        let two = rev_commit(&repo, two)?;
+        Ok(repo.merge_base(one.id(), two.id()).ok().map(|x| x.to_string()))
+    }
+
+    /// Number of lines of `path` at `rev`, or `None` when it is not a file there.
+    pub fn file_line_count(&self, rev: &str, path: &str) -> anyhow::Result<Option<usize>> {
+        let repo = self.git()?;
+        let Some(blob) = file_blob(&repo, rev, path)? else {
+            return Ok(None);
+        };
+        if blob.is_binary() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(blob.content()).lines().count()))
+    }
+
+    /// Follows a 1-based `line` of `path` from `from` to `to`. Returns `None`
+    /// when the line was changed or removed, or the file is gone or binary.
+    pub fn line_remap(&self, from: &str, to: &str, path: &str, line: u32) -> anyhow::Result<Option<u32>> {
+        let repo = self.git()?;
+        let (Some(old), Some(new)) = (file_blob(&repo, from, path)?, file_blob(&repo, to, path)?) else {
+            return Ok(None);
+        };
+        if old.id() == new.id() {
+            return Ok(Some(line));
+        }
+        if old.is_binary() || new.is_binary() {
+            return Ok(None);
+        }
+        let mut options = DiffOptions::new();
+        options.context_lines(0);
+        let patch = Patch::from_blobs(&old, Some(Path::new(path)), &new, Some(Path::new(path)), Some(&mut options))?;
+        let mut shift = 0i64;
+        for index in 0..patch.num_hunks() {
+            let (hunk, _) = patch.hunk(index)?;
+            let (start, count) = (hunk.old_start(), hunk.old_lines());
+            // a pure insertion is reported after line `start`
+            let before = if count == 0 { start < line } else { start + count <= line };
+            if before {
+                shift += hunk.new_lines() as i64 - count as i64;
+            } else if count > 0 && start <= line {
+                return Ok(None);
+            } else {
+                break;
+            }
+        }
+        Ok(Some((line as i64 + shift) as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_line_remap() {
+        let test = TestRepo::new();
+        let old = test.commit("main", &[("a.txt", Some("one\ntwo\nthree\nfour\nfive\n"))], "old");
+        let new = test.commit("main", &[("a.txt", Some("zero\none\nTWO\nthree\nfive\n"))], "new");
+        let (old, new) = (old.to_string(), new.to_string());
+
+        assert_eq!(test.git.file_line_count(&old, "a.txt").unwrap(), Some(5));
+        assert_eq!(test.git.file_line_count(&old, "b.txt").unwrap(), None);
+        assert_eq!(test.git.line_remap(&old, &new, "a.txt", 1).unwrap(), Some(2));
+        assert_eq!(test.git.line_remap(&old, &new, "a.txt", 2).unwrap(), None);
+        assert_eq!(test.git.line_remap(&old, &new, "a.txt", 3).unwrap(), Some(4));
+        assert_eq!(test.git.line_remap(&old, &new, "a.txt", 4).unwrap(), None);
+        assert_eq!(test.git.line_remap(&old, &new, "a.txt", 5).unwrap(), Some(5));
+        assert_eq!(test.git.line_remap(&old, &new, "b.txt", 1).unwrap(), None);
+    }
+}
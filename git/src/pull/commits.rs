@@ -0,0 +1,63 @@
+use crate::commit::list::GitCommit;
+use crate::{rev_commit, AppGit};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitPullCommits {
+    /// The commit the base branch pointed at.
+    pub base: String,
+    pub merge_base: Option<String>,
+    /// Commits on the head but not on the base branch, oldest first.
+    pub commits: Vec<GitCommit>,
+}
+
+impl AppGit {
+    pub fn pull_commits(&self, base: &str, head: &str) -> anyhow::Result<GitPullCommits> {
+        let repo = self.git()?;
+        let base = rev_commit(&repo, base)?;
+        let head = rev_commit(&repo, head)?;
+        let merge_base = repo.merge_base(base.id(), head.id()).ok();
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(head.id())?;
+        revwalk.hide(base.id())?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+        let mut commits = vec![];
+        for oid in revwalk {
+            commits.push(GitCommit::from(&repo.find_commit(oid?)?));
+        }
+        Ok(GitPullCommits {
+            base: base.id().to_string(),
+            merge_base: merge_base.map(|x| x.to_string()),
+            commits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_pull_commits() {
+        let target = TestRepo::new();
+        let root = target.commit("main", &[("a.txt", Some("a\n"))], "root");
+        target.branch("feature", root);
+        let first = target.commit("feature", &[("b.txt", Some("b\n"))], "first");
+        let second = target.commit("feature", &[("b.txt", Some("c\n"))], "second");
+        let main = target.commit("main", &[("a.txt", Some("b\n"))], "main");
+
+        let head = target.git.pull_head_update(&target.git, "feature", 1).unwrap();
+        assert_eq!(head, second.to_string());
+        let result = target.git.pull_commits("main", &head).unwrap();
+        assert_eq!(result.base, main.to_string());
+        assert_eq!(result.merge_base, Some(root.to_string()));
+        let hashes = result.commits.iter().map(|x| x.hash.clone()).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![first.to_string(), second.to_string()]);
+
+        let fork = TestRepo::new();
+        let remote = fork.commit("main", &[("c.txt", Some("c\n"))], "remote");
+        let head = target.git.pull_head_update(&fork.git, "main", 2).unwrap();
+        assert_eq!(head, remote.to_string());
+        assert!(target.git.git().unwrap().find_commit(remote).is_ok());
+    }
+}
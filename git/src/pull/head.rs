@@ -0,0 +1,30 @@
+use crate::AppGit;
+use anyhow::Context;
+
+/// The ref a pull request's head is kept under in the target repository, so
+/// its commits stay reachable there even when they come from a fork.
+pub fn pull_head_ref(number: i64) -> String {
+    format!("refs/pull/{}/head", number)
+}
+
+impl AppGit {
+    /// Points `refs/pull/{number}/head` at `branch` of `source`, fetching the
+    /// objects first when `source` is another repository. Returns the new head.
+    pub fn pull_head_update(&self, source: &AppGit, branch: &str, number: i64) -> anyhow::Result<String> {
+        let repo = self.git()?;
+        let refname = pull_head_ref(number);
+        if source.path_buf == self.path_buf {
+            let oid = repo
+                .refname_to_id(&format!("refs/heads/{}", branch))
+                .with_context(|| format!("Branch not found: {}", branch))?;
+            repo.reference(&refname, oid, true, "pull request head")?;
+        } else {
+            let url = source.path_buf.to_string_lossy().to_string();
+            let mut remote = repo.remote_anonymous(&url)?;
+            remote
+                .fetch(&[format!("+refs/heads/{}:{}", branch, refname)], None, None)
+                .with_context(|| format!("Failed to fetch branch {}", branch))?;
+        }
+        Ok(repo.refname_to_id(&refname)?.to_string())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod anchor;
+pub mod commits;
+pub mod head;
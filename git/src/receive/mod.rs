@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+pub mod policy;
+pub mod quarantine;
+
+/// A single `<old> <new> <ref>` command from a receive-pack request.
+#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+pub struct GitRefUpdate {
+    pub old: String,
+    pub new: String,
+    pub name: String,
+}
+
+impl GitRefUpdate {
+    pub const ZERO: &'static str = "0000000000000000000000000000000000000000";
+
+    /// An update of the full ref `name`; a missing `old` creates it and a
+    /// missing `new` deletes it.
+    pub fn new(name: impl Into<String>, old: Option<&str>, new: Option<&str>) -> Self {
+        GitRefUpdate {
+            old: old.unwrap_or(Self::ZERO).to_string(),
+            new: new.unwrap_or(Self::ZERO).to_string(),
+            name: name.into(),
+        }
+    }
+
+    pub fn is_create(&self) -> bool {
+        self.old == Self::ZERO
+    }
+    pub fn is_delete(&self) -> bool {
+        self.new == Self::ZERO
+    }
+    pub fn branch(&self) -> Option<&str> {
+        self.name.strip_prefix("refs/heads/")
+    }
+    pub fn tag(&self) -> Option<&str> {
+        self.name.strip_prefix("refs/tags/")
+    }
+}
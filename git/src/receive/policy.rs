@@ -0,0 +1,128 @@
+use git2::{Commit, ObjectType, Repository};
+
+/// Limits every new commit has to respect, whether it is pushed or made by
+/// the server, as with edits from the web and pull request merges.
+#[derive(Clone, Debug)]
+pub struct GitCommitPolicy {
+    /// Largest file a commit may add or change, in bytes.
+    pub max_file_size: usize,
+    /// Refuses commits with an empty subject line.
+    pub require_subject: bool,
+    /// Longest subject line allowed, in characters.
+    pub max_subject: Option<usize>,
+}
+
+impl Default for GitCommitPolicy {
+    fn default() -> Self {
+        GitCommitPolicy {
+            max_file_size: Self::DEFAULT_MAX_FILE_SIZE,
+            require_subject: false,
+            max_subject: None,
+        }
+    }
+}
+
+impl GitCommitPolicy {
+    pub const DEFAULT_MAX_FILE_SIZE: usize = 100 * 1024 * 1024;
+
+    /// Reads the policy from `JZFS_MAX_FILE_SIZE`, in bytes,
+    /// `JZFS_COMMIT_SUBJECT_REQUIRED` (`true` or `1`) and
+    /// `JZFS_COMMIT_SUBJECT_MAX`.
+    pub fn from_env() -> Self {
+        let max_file_size = std::env::var("JZFS_MAX_FILE_SIZE")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(Self::DEFAULT_MAX_FILE_SIZE);
+        let require_subject = std::env::var("JZFS_COMMIT_SUBJECT_REQUIRED")
+            .is_ok_and(|x| x == "1" || x.eq_ignore_ascii_case("true"));
+        let max_subject = std::env::var("JZFS_COMMIT_SUBJECT_MAX")
+            .ok()
+            .and_then(|x| x.parse().ok());
+        GitCommitPolicy {
+            max_file_size,
+            require_subject,
+            max_subject,
+        }
+    }
+
+    /// Whether any rule applies to commit messages.
+    pub fn checks_messages(&self) -> bool {
+        self.require_subject || self.max_subject.is_some()
+    }
+
+    /// Why `message` is refused, if it is.
+    pub fn message_violation(&self, message: &str) -> Option<String> {
+        let subject = message.lines().next().unwrap_or_default().trim();
+        if self.require_subject && subject.is_empty() {
+            return Some("empty commit message".to_string());
+        }
+        match self.max_subject {
+            Some(max) if subject.chars().count() > max => {
+                Some(format!("subject longer than {} characters", max))
+            }
+            _ => None,
+        }
+    }
+
+    /// Why the files `commit` adds or changes are refused, if they are.
+    pub fn file_violation(
+        &self,
+        repo: &Repository,
+        commit: &Commit,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(large_files(repo, commit, self.max_file_size)?
+            .into_iter()
+            .next()
+            .map(|(path, size)| {
+                format!(
+                    "file {} is {} bytes, over the {} byte limit (commit {})",
+                    path,
+                    size,
+                    self.max_file_size,
+                    &commit.id().to_string()[..7]
+                )
+            }))
+    }
+
+    /// Why `commit` is refused, if it is, going by both its files and its
+    /// message.
+    pub fn violation(&self, repo: &Repository, commit: &Commit) -> anyhow::Result<Option<String>> {
+        if let Some(violation) = self.file_violation(repo, commit)? {
+            return Ok(Some(violation));
+        }
+        Ok(self
+            .message_violation(commit.message().unwrap_or_default())
+            .map(|x| format!("commit {}: {}", &commit.id().to_string()[..7], x)))
+    }
+}
+
+/// Files `commit` adds or changes, compared to its first parent, whose
+/// content exceeds `limit` bytes, with their size.
+pub(crate) fn large_files(
+    repo: &Repository,
+    commit: &Commit,
+    limit: usize,
+) -> anyhow::Result<Vec<(String, usize)>> {
+    let parent = match commit.parents().next() {
+        Some(parent) => Some(parent.tree()?),
+        None => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent.as_ref(), Some(&commit.tree()?), None)?;
+    let odb = repo.odb()?;
+    let mut files = vec![];
+    for delta in diff.deltas() {
+        let file = delta.new_file();
+        if file.id().is_zero() {
+            continue;
+        }
+        let (size, kind) = odb.read_header(file.id())?;
+        if kind == ObjectType::Blob && size > limit {
+            let path = file
+                .path()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default();
+            files.push((path, size));
+        }
+    }
+    Ok(files)
+}
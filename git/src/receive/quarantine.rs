@@ -0,0 +1,163 @@
+use crate::commit::list::GitCommit;
+use crate::receive::policy::{large_files, GitCommitPolicy};
+use crate::{rev_commit, AppGit};
+use git2::{Indexer, Oid, Repository};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Objects from an incoming pack, indexed into a temporary object directory
+/// so they can be inspected before any ref is allowed to point at them.
+pub struct GitQuarantine {
+    repo_path: PathBuf,
+    objects: Option<PathBuf>,
+}
+
+impl AppGit {
+    pub fn quarantine(&self, pack: &[u8]) -> anyhow::Result<GitQuarantine> {
+        let mut quarantine = GitQuarantine {
+            repo_path: self.path_buf.clone(),
+            objects: None,
+        };
+        if pack.is_empty() {
+            return Ok(quarantine);
+        }
+        let objects = self.path_buf.join("objects").join(format!(
+            "incoming-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_nanos()
+        ));
+        std::fs::create_dir_all(objects.join("pack"))?;
+        quarantine.objects = Some(objects.clone());
+        let repo = self.git()?;
+        let odb = repo.odb()?;
+        let mut indexer = Indexer::new(Some(&odb), &objects.join("pack"), 0, true)?;
+        indexer.write_all(pack)?;
+        indexer.commit()?;
+        Ok(quarantine)
+    }
+}
+
+impl GitQuarantine {
+    /// Opens the repository with the quarantined objects visible as an alternate.
+    pub fn git(&self) -> anyhow::Result<Repository> {
+        let repo = Repository::open_bare(&self.repo_path)?;
+        if let Some(objects) = &self.objects {
+            repo.odb()?
+                .add_disk_alternate(&objects.to_string_lossy())?;
+        }
+        Ok(repo)
+    }
+
+    /// Whether moving a ref from `old` to `new` keeps `old` in its history.
+    pub fn is_fast_forward(&self, old: &str, new: &str) -> anyhow::Result<bool> {
+        let repo = self.git()?;
+        let old = Oid::from_str(old)?;
+        let new = Oid::from_str(new)?;
+        Ok(old == new || repo.graph_descendant_of(new, old)?)
+    }
+
+    /// Commits reachable from `new` that no existing ref reaches yet, i.e. the
+    /// ones a push introduces. Empty when `new` is not a commit.
+    pub fn new_commits(&self, new: &str) -> anyhow::Result<Vec<GitCommit>> {
+        let repo = self.git()?;
+        let Ok(new) = rev_commit(&repo, new) else {
+            return Ok(vec![]);
+        };
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push(new.id())?;
+        for reference in repo.references()? {
+            if let Ok(commit) = reference?.peel_to_commit() {
+                revwalk.hide(commit.id())?;
+            }
+        }
+        let mut commits = vec![];
+        for oid in revwalk {
+            commits.push(GitCommit::from(&repo.find_commit(oid?)?));
+        }
+        Ok(commits)
+    }
+
+    /// Files `commit` adds or changes whose content exceeds `limit` bytes,
+    /// with their size.
+    pub fn large_files(&self, commit: &str, limit: usize) -> anyhow::Result<Vec<(String, usize)>> {
+        let repo = self.git()?;
+        let commit = rev_commit(&repo, commit)?;
+        large_files(&repo, &commit, limit)
+    }
+
+    /// Why the files `commit` adds or changes break `policy`, if they do.
+    pub fn file_violation(&self, commit: &str, policy: &GitCommitPolicy) -> anyhow::Result<Option<String>> {
+        let repo = self.git()?;
+        let commit = rev_commit(&repo, commit)?;
+        policy.file_violation(&repo, &commit)
+    }
+}
+
+impl Drop for GitQuarantine {
+    fn drop(&mut self) {
+        if let Some(objects) = &self.objects {
+            std::fs::remove_dir_all(objects).ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_repo::TestRepo;
+
+    #[test]
+    fn test_git_quarantine_fast_forward() {
+        let source = TestRepo::new();
+        let target = TestRepo::new();
+        let root = source.commit("main", &[("a.txt", Some("a\n"))], "root");
+        let ahead = source.commit("main", &[("a.txt", Some("b\n"))], "ahead");
+        source.branch("other", root);
+        let rewritten = source.commit("other", &[("a.txt", Some("c\n"))], "rewritten");
+
+        let repo = source.git.git().unwrap();
+        let mut builder = repo.packbuilder().unwrap();
+        let mut walk = repo.revwalk().unwrap();
+        walk.push(ahead).unwrap();
+        walk.push(rewritten).unwrap();
+        builder.insert_walk(&mut walk).unwrap();
+        let mut pack = git2::Buf::new();
+        builder.write_buf(&mut pack).unwrap();
+
+        let quarantine = target.git.quarantine(&pack).unwrap();
+        assert!(target.git.git().unwrap().find_commit(ahead).is_err());
+        assert!(quarantine.git().unwrap().find_commit(ahead).is_ok());
+        assert!(quarantine.is_fast_forward(&root.to_string(), &ahead.to_string()).unwrap());
+        assert!(!quarantine.is_fast_forward(&ahead.to_string(), &rewritten.to_string()).unwrap());
+        let objects = quarantine.objects.clone().unwrap();
+        drop(quarantine);
+        assert!(!objects.exists());
+    }
+
+    #[test]
+    fn test_git_quarantine_new_commits() {
+        let source = TestRepo::new();
+        let target = TestRepo::new();
+        let root = source.commit("main", &[("a.txt", Some("a\n"))], "root");
+        let big = source.commit("main", &[("big.bin", Some(&"x".repeat(100))), ("a.txt", Some("b\n"))], "big");
+        // identical commits, so the target already has `root`
+        assert_eq!(target.commit("main", &[("a.txt", Some("a\n"))], "root"), root);
+
+        let repo = source.git.git().unwrap();
+        let mut builder = repo.packbuilder().unwrap();
+        let mut walk = repo.revwalk().unwrap();
+        walk.push(big).unwrap();
+        walk.hide(root).unwrap();
+        builder.insert_walk(&mut walk).unwrap();
+        let mut pack = git2::Buf::new();
+        builder.write_buf(&mut pack).unwrap();
+
+        let quarantine = target.git.quarantine(&pack).unwrap();
+        let commits = quarantine.new_commits(&big.to_string()).unwrap();
+        assert_eq!(commits.iter().map(|x| x.hash.clone()).collect::<Vec<_>>(), vec![big.to_string()]);
+        assert!(quarantine.new_commits(&root.to_string()).unwrap().is_empty());
+        assert_eq!(quarantine.large_files(&big.to_string(), 50).unwrap(), vec![("big.bin".to_string(), 100)]);
+        assert!(quarantine.large_files(&big.to_string(), 100).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::tag::list::{GitTagListResult, tag_info};
+use crate::tree::msg_tree::GitTreeAuthors;
+use crate::{AppGit, rev_commit};
+use git2::{Reference, Signature, Time};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitTagCreateParam {
+    pub name: String,
+    pub target: String,
+    /// Creates an annotated tag when set, a lightweight tag otherwise.
+    pub message: Option<String>,
+    pub tagger: GitTreeAuthors,
+}
+
+impl AppGit {
+    pub fn tag_create(&self, param: GitTagCreateParam) -> anyhow::Result<GitTagListResult> {
+        let repo = self.git()?;
+        if !Reference::is_valid_name(&format!("refs/tags/{}", param.name)) {
+            return Err(anyhow::anyhow!("Invalid tag name"));
+        }
+        if repo.find_reference(&format!("refs/tags/{}", param.name)).is_ok() {
+            return Err(anyhow::anyhow!("Tag already exists"));
+        }
+        let target = rev_commit(&repo, &param.target)?;
+        match param.message.filter(|x| !x.trim().is_empty()) {
+            Some(message) => {
+                let tagger = Signature::new(
+                    &param.tagger.name,
+                    &param.tagger.email,
+                    &Time::new(param.tagger.time, 0),
+                )?;
+                repo.tag(&param.name, target.as_object(), &tagger, &message, false)?;
+            }
+            None => {
+                repo.tag_lightweight(&param.name, target.as_object(), false)?;
+            }
+        }
+        tag_info(&repo, &param.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_repo::TestRepo;
+
+    fn tagger() -> GitTreeAuthors {
+        GitTreeAuthors {
+            name: "tester".to_string(),
+            email: "tester@example.com".to_string(),
+            time: 1_700_000_100,
+        }
+    }
+
+    #[test]
+    fn test_git_tag_create_list_delete() {
+        let test = TestRepo::new();
+        let head = test.commit("main", &[("a.txt", Some("a\n"))], "first");
+        let light = test
+            .git
+            .tag_create(GitTagCreateParam {
+                name: "v0.1".to_string(),
+                target: "main".to_string(),
+                message: None,
+                tagger: tagger(),
+            })
+            .unwrap();
+        assert!(!light.annotated);
+        assert_eq!(light.oid, head.to_string());
+        assert_eq!(light.target, head.to_string());
+
+        let annotated = test
+            .git
+            .tag_create(GitTagCreateParam {
+                name: "release/v1.0".to_string(),
+                target: head.to_string(),
+                message: Some("First release".to_string()),
+                tagger: tagger(),
+            })
+            .unwrap();
+        assert!(annotated.annotated);
+        assert_ne!(annotated.oid, head.to_string());
+        assert_eq!(annotated.target, head.to_string());
+        assert_eq!(annotated.tagger.unwrap().name, "tester");
+
+        assert!(
+            test.git
+                .tag_create(GitTagCreateParam {
+                    name: "v0.1".to_string(),
+                    target: "main".to_string(),
+                    message: None,
+                    tagger: tagger(),
+                })
+                .is_err()
+        );
+
+        let tags = test.git.tag_list().unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags[0].name, "release/v1.0");
+
+        test.git.tag_delete("v0.1").unwrap();
+        assert!(test.git.tag_delete("v0.1").is_err());
+        assert_eq!(test.git.tag_list().unwrap().len(), 1);
+    }
+}
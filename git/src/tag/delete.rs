@@ -0,0 +1,12 @@
+use crate::AppGit;
+
+impl AppGit {
+    pub fn tag_delete(&self, name: &str) -> anyhow::Result<()> {
+        let repo = self.git()?;
+        if repo.find_reference(&format!("refs/tags/{}", name)).is_err() {
+            return Err(anyhow::anyhow!("Tag not found"));
+        }
+        repo.tag_delete(name)?;
+        Ok(())
+    }
+}
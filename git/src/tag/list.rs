@@ -0,0 +1,63 @@
+use crate::AppGit;
+use crate::tree::msg_tree::GitTreeAuthors;
+use git2::{ObjectType, Repository};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct GitTagListResult {
+    pub name: String,
+    pub oid: String,
+    pub target: String,
+    pub annotated: bool,
+    pub tagger: Option<GitTreeAuthors>,
+    pub message: Option<String>,
+    pub time: i64,
+}
+
+pub(crate) fn tag_info(repo: &Repository, name: &str) -> anyhow::Result<GitTagListResult> {
+    let reference = repo.find_reference(&format!("refs/tags/{}", name))?;
+    let oid = reference
+        .target()
+        .ok_or(anyhow::anyhow!("Failed to get target from reference"))?;
+    let target = reference.peel(ObjectType::Any)?;
+    let mut result = GitTagListResult {
+        name: name.to_string(),
+        oid: oid.to_string(),
+        target: target.id().to_string(),
+        annotated: false,
+        tagger: None,
+        message: None,
+        time: 0,
+    };
+    if let Ok(tag) = repo.find_tag(oid) {
+        result.annotated = true;
+        result.message = tag.message().map(|x| x.to_string());
+        result.tagger = tag.tagger().map(|x| GitTreeAuthors {
+            name: x.name().unwrap_or("?").to_string(),
+            email: x.email().unwrap_or("?").to_string(),
+            time: x.when().seconds(),
+        });
+    }
+    result.time = match (&result.tagger, target.as_commit()) {
+        (Some(tagger), _) => tagger.time,
+        (None, Some(commit)) => commit.time().seconds(),
+        (None, None) => 0,
+    };
+    Ok(result)
+}
+
+impl AppGit {
+    pub fn tag_get(&self, name: &str) -> anyhow::Result<GitTagListResult> {
+        tag_info(&self.git()?, name)
+    }
+
+    pub fn tag_list(&self) -> anyhow::Result<Vec<GitTagListResult>> {
+        let repo = self.git()?;
+        let mut tag_list = vec![];
+        for name in repo.tag_names(None)?.iter().flatten() {
+            tag_list.push(tag_info(&repo, name)?);
+        }
+        tag_list.sort_by_key(|x| std::cmp::Reverse(x.time));
+        Ok(tag_list)
+    }
+}
@@ -0,0 +1,96 @@
+use crate::AppGit;
+use git2::build::TreeUpdateBuilder;
+use git2::{FileMode, Oid, Signature, Time};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A throwaway bare repository under the system temp directory, removed on drop.
+pub struct TestRepo {
+    pub git: AppGit,
+}
+
+impl TestRepo {
+    pub fn new() -> Self {
+        let path_buf = std::env::temp_dir().join(format!(
+            "jzfs-git-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        if path_buf.exists() {
+            std::fs::remove_dir_all(&path_buf).ok();
+        }
+        let git = AppGit { path_buf };
+        git.init().unwrap();
+        git.git()
+            .unwrap()
+            .set_head("refs/heads/main")
+            .unwrap();
+        TestRepo { git }
+    }
+
+    pub fn branch(&self, name: &str, target: Oid) {
+        let repo = self.git.git().unwrap();
+        let commit = repo.find_commit(target).unwrap();
+        repo.branch(name, &commit, false).unwrap();
+    }
+
+    /// Commits `files` on top of `branch`; a `None` content removes the path.
+    pub fn commit(&self, branch: &str, files: &[(&str, Option<&str>)], message: &str) -> Oid {
+        self.commit_at(branch, files, message, 1_700_000_000)
+    }
+
+    pub fn commit_at(
+        &self,
+        branch: &str,
+        files: &[(&str, Option<&str>)],
+        message: &str,
+        time: i64,
+    ) -> Oid {
+        let repo = self.git.git().unwrap();
+        let refname = format!("refs/heads/{}", branch);
+        let parent = repo
+            .find_reference(&refname)
+            .ok()
+            .and_then(|x| x.peel_to_commit().ok());
+        let base = match &parent {
+            Some(parent) => parent.tree().unwrap(),
+            None => {
+                let oid = repo.treebuilder(None).unwrap().write().unwrap();
+                repo.find_tree(oid).unwrap()
+            }
+        };
+        let mut builder = TreeUpdateBuilder::new();
+        for (path, content) in files {
+            match content {
+                Some(content) => {
+                    let oid = repo.blob(content.as_bytes()).unwrap();
+                    builder.upsert(*path, oid, FileMode::Blob);
+                }
+                None => {
+                    builder.remove(*path);
+                }
+            }
+        }
+        let tree = repo
+            .find_tree(builder.create_updated(&repo, &base).unwrap())
+            .unwrap();
+        let signature = Signature::new("tester", "tester@example.com", &Time::new(time, 0)).unwrap();
+        let parents = parent.iter().collect::<Vec<_>>();
+        repo.commit(
+            Some(&refname),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap()
+    }
+}
+
+impl Drop for TestRepo {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.git.path_buf).ok();
+    }
+}
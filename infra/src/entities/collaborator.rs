@@ -0,0 +1,243 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct CollaboratorModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub user_uid: Uuid,
+    pub role: String,
+    pub invited_by: Uuid,
+    pub accepted: bool,
+    pub created_at: NaiveDateTime,
+    pub accepted_at: Option<NaiveDateTime>,
+}
+
+impl CollaboratorModel {
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        user_uid: Uuid,
+        role: &str,
+        invited_by: Uuid,
+    ) -> Result<CollaboratorModel, Error> {
+        let uid = Uuid::new_v4();
+        let created_at = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO repository_collaborator (uid, repo_uid, user_uid, role, invited_by, accepted, created_at, accepted_at)
+        VALUES ($1, $2, $3, $4, $5, FALSE, $6, NULL)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(user_uid)
+        .bind(role)
+        .bind(invited_by)
+        .bind(created_at)
+        .fetch_one(pool)
+        .await?;
+        Ok(CollaboratorModel {
+            uid: row.get("uid"),
+            repo_uid: row.get("repo_uid"),
+            user_uid: row.get("user_uid"),
+            role: row.get("role"),
+            invited_by: row.get("invited_by"),
+            accepted: row.get("accepted"),
+            created_at: row.get("created_at"),
+            accepted_at: row.get("accepted_at"),
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<CollaboratorModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM repository_collaborator
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| CollaboratorModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            invited_by: r.get("invited_by"),
+            accepted: r.get("accepted"),
+            created_at: r.get("created_at"),
+            accepted_at: r.get("accepted_at"),
+        }))
+    }
+
+    pub async fn get_by_repo_uid_and_user_uid(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        user_uid: Uuid,
+    ) -> Result<Option<CollaboratorModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM repository_collaborator
+        WHERE repo_uid = $1 AND user_uid = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(user_uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| CollaboratorModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            invited_by: r.get("invited_by"),
+            accepted: r.get("accepted"),
+            created_at: r.get("created_at"),
+            accepted_at: r.get("accepted_at"),
+        }))
+    }
+
+    pub async fn get_by_repo_uid(
+        pool: &PgPool,
+        repo_uid: Uuid,
+    ) -> Result<Vec<CollaboratorModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM repository_collaborator
+        WHERE repo_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(repo_uid)
+        .fetch_all(pool)
+        .await?;
+        let collaborators = rows
+            .into_iter()
+            .map(|r| CollaboratorModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                user_uid: r.get("user_uid"),
+                role: r.get("role"),
+                invited_by: r.get("invited_by"),
+                accepted: r.get("accepted"),
+                created_at: r.get("created_at"),
+                accepted_at: r.get("accepted_at"),
+            })
+            .collect();
+        Ok(collaborators)
+    }
+
+    pub async fn get_pending_by_user_uid(
+        pool: &PgPool,
+        user_uid: Uuid,
+    ) -> Result<Vec<CollaboratorModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM repository_collaborator
+        WHERE user_uid = $1 AND accepted = FALSE
+        ORDER BY created_at DESC
+        "#,
+        )
+        .bind(user_uid)
+        .fetch_all(pool)
+        .await?;
+        let invitations = rows
+            .into_iter()
+            .map(|r| CollaboratorModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                user_uid: r.get("user_uid"),
+                role: r.get("role"),
+                invited_by: r.get("invited_by"),
+                accepted: r.get("accepted"),
+                created_at: r.get("created_at"),
+                accepted_at: r.get("accepted_at"),
+            })
+            .collect();
+        Ok(invitations)
+    }
+
+    pub async fn accept(pool: &PgPool, uid: Uuid) -> Result<Option<CollaboratorModel>, Error> {
+        let accepted_at = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        UPDATE repository_collaborator
+        SET accepted = TRUE,
+            accepted_at = $1
+        WHERE uid = $2
+        RETURNING *
+        "#,
+        )
+        .bind(accepted_at)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| CollaboratorModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            invited_by: r.get("invited_by"),
+            accepted: r.get("accepted"),
+            created_at: r.get("created_at"),
+            accepted_at: r.get("accepted_at"),
+        }))
+    }
+
+    pub async fn update_role(
+        pool: &PgPool,
+        uid: Uuid,
+        role: &str,
+    ) -> Result<Option<CollaboratorModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        UPDATE repository_collaborator
+        SET role = $1
+        WHERE uid = $2
+        RETURNING *
+        "#,
+        )
+        .bind(role)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| CollaboratorModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            invited_by: r.get("invited_by"),
+            accepted: r.get("accepted"),
+            created_at: r.get("created_at"),
+            accepted_at: r.get("accepted_at"),
+        }))
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<CollaboratorModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM repository_collaborator
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| CollaboratorModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            invited_by: r.get("invited_by"),
+            accepted: r.get("accepted"),
+            created_at: r.get("created_at"),
+            accepted_at: r.get("accepted_at"),
+        }))
+    }
+}
@@ -1,6 +1,6 @@
 use chrono::Local;
 use serde::{Deserialize, Serialize};
-use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use sqlx::{Decode, Encode, Error, FromRow, PgConnection, PgPool, Row};
 use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Encode, Decode, FromRow)]
@@ -91,6 +91,30 @@ impl GitBranchModel {
         Ok(branches)
     }
 
+    pub async fn get_by_repo_uid_and_name(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        name: &str,
+    ) -> Result<Option<GitBranchModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM git_branch
+        WHERE repo_uid = $1 AND name = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| GitBranchModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            name: r.get("name"),
+            head: r.get("head"),
+            timestamp: r.get("timestamp"),
+        }))
+    }
+
     pub async fn update(
         pool: &PgPool,
         uid: Uuid,
@@ -142,4 +166,63 @@ impl GitBranchModel {
             timestamp: r.get("timestamp"),
         }))
     }
+
+    /// Creates the branch `name` or moves it to `head`.
+    pub async fn upsert(
+        conn: &mut PgConnection,
+        repo_uid: Uuid,
+        name: &str,
+        head: &str,
+    ) -> Result<GitBranchModel, Error> {
+        let timestamp = Local::now().timestamp();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO git_branch (uid, repo_uid, name, head, timestamp)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (repo_uid, name) DO UPDATE
+        SET head = EXCLUDED.head,
+            timestamp = EXCLUDED.timestamp
+        RETURNING *
+        "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(repo_uid)
+        .bind(name)
+        .bind(head)
+        .bind(timestamp)
+        .fetch_one(conn)
+        .await?;
+        Ok(GitBranchModel {
+            uid: row.get("uid"),
+            repo_uid: row.get("repo_uid"),
+            name: row.get("name"),
+            head: row.get("head"),
+            timestamp: row.get("timestamp"),
+        })
+    }
+
+    pub async fn delete_by_name(
+        conn: &mut PgConnection,
+        repo_uid: Uuid,
+        name: &str,
+    ) -> Result<Option<GitBranchModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM git_branch
+        WHERE repo_uid = $1 AND name = $2
+        RETURNING *
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(name)
+        .fetch_optional(conn)
+        .await?;
+        Ok(row.map(|r| GitBranchModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            name: r.get("name"),
+            head: r.get("head"),
+            timestamp: r.get("timestamp"),
+        }))
+    }
 }
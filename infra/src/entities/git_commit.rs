@@ -1,8 +1,9 @@
 use chrono::Local;
 use serde::{Deserialize, Serialize};
-use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use sqlx::{Decode, Encode, Error, FromRow, PgConnection, PgPool, Row};
 use uuid::Uuid;
 use crate::error::AppResult;
+use git::commit::list::GitCommit;
 
 #[derive(Deserialize, Serialize, Encode, Decode, FromRow)]
 pub struct GitCommitModel {
@@ -222,6 +223,25 @@ impl GitCommitModel {
         }))
     }
 
+    pub async fn update_branch_name(
+        pool: &PgPool,
+        branch_uid: Uuid,
+        branch_name: &str,
+    ) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+        UPDATE git_commit
+        SET branch_name = $1
+        WHERE branch_uid = $2
+        "#,
+        )
+        .bind(branch_name)
+        .bind(branch_uid)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<GitCommitModel>, Error> {
         let row = sqlx::query(
             r#"
@@ -248,4 +268,89 @@ impl GitCommitModel {
             created_at: r.get("created_at"),
         }))
     }
+
+    /// Records `commits` as first seen on a branch, skipping those the
+    /// repository already has. Returns how many were inserted.
+    pub async fn create_batch(
+        conn: &mut PgConnection,
+        repo_uid: Uuid,
+        branch_uid: Uuid,
+        branch_name: &str,
+        commits: &[GitCommit],
+    ) -> Result<u64, Error> {
+        let timestamp = Local::now().timestamp();
+        let created_at = Local::now().naive_local();
+        let mut inserted = 0;
+        for chunk in commits.chunks(1000) {
+            let result = sqlx::query(
+                r#"
+            INSERT INTO git_commit (
+                uid, sha, branch_uid, repo_uid, branch_name, message,
+                author_name, author_email, commiter_name, commiter_email,
+                timestamp, created_at
+            )
+            SELECT x.uid, x.sha, $3, $4, $5, x.message, x.author_name, x.author_email,
+                   x.commiter_name, x.commiter_email, $6, $7
+            FROM UNNEST($1::UUID[], $2::VARCHAR[], $8::TEXT[], $9::VARCHAR[], $10::VARCHAR[], $11::VARCHAR[], $12::VARCHAR[])
+                AS x(uid, sha, message, author_name, author_email, commiter_name, commiter_email)
+            ON CONFLICT (sha, repo_uid) DO NOTHING
+            "#,
+            )
+            .bind(chunk.iter().map(|_| Uuid::new_v4()).collect::<Vec<_>>())
+            .bind(chunk.iter().map(|x| x.hash.clone()).collect::<Vec<_>>())
+            .bind(branch_uid)
+            .bind(repo_uid)
+            .bind(branch_name)
+            .bind(timestamp)
+            .bind(created_at)
+            .bind(chunk.iter().map(|x| x.message.clone()).collect::<Vec<_>>())
+            .bind(chunk.iter().map(|x| x.author.clone()).collect::<Vec<_>>())
+            .bind(chunk.iter().map(|x| x.email.clone()).collect::<Vec<_>>())
+            .bind(chunk.iter().map(|x| x.committer.clone()).collect::<Vec<_>>())
+            .bind(chunk.iter().map(|x| x.committer_email.clone()).collect::<Vec<_>>())
+            .execute(&mut *conn)
+            .await?;
+            inserted += result.rows_affected();
+        }
+        Ok(inserted)
+    }
+
+    /// Moves the commits recorded on one branch over to another.
+    /// Moves the commits of a branch, except those in `keep`, to another one.
+    pub async fn move_branch(
+        conn: &mut PgConnection,
+        branch_uid: Uuid,
+        to_branch_uid: Uuid,
+        to_branch_name: &str,
+        keep: &[String],
+    ) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+        UPDATE git_commit
+        SET branch_uid = $1, branch_name = $2
+        WHERE branch_uid = $3 AND sha <> ALL($4)
+        "#,
+        )
+        .bind(to_branch_uid)
+        .bind(to_branch_name)
+        .bind(branch_uid)
+        .bind(keep)
+        .execute(conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete_by_shas(conn: &mut PgConnection, repo_uid: Uuid, shas: &[String]) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+        DELETE FROM git_commit
+        WHERE repo_uid = $1 AND sha = ANY($2)
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(shas)
+        .execute(conn)
+        .await?;
+        Ok(result.rows_affected())
+    }
 }
@@ -1,6 +1,6 @@
 use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use sqlx::{Decode, Encode, Error, FromRow, PgConnection, PgPool, Row};
 use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Encode, Decode, FromRow)]
@@ -136,4 +136,58 @@ impl GitTags {
             created_at: r.get("created_at"),
         }))
     }
+
+    /// Creates the tag `name` or points it at `sha`.
+    pub async fn upsert(
+        conn: &mut PgConnection,
+        repo_uid: Uuid,
+        name: &str,
+        sha: &str,
+    ) -> Result<GitTags, Error> {
+        let created_at = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO git_tags (uid, repo_uid, name, sha, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (repo_uid, name) DO UPDATE
+        SET sha = EXCLUDED.sha
+        RETURNING *
+        "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(repo_uid)
+        .bind(name)
+        .bind(sha)
+        .bind(created_at)
+        .fetch_one(conn)
+        .await?;
+        Ok(GitTags {
+            uid: row.get("uid"),
+            repo_uid: row.get("repo_uid"),
+            name: row.get("name"),
+            sha: row.get("sha"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    pub async fn delete_by_name(conn: &mut PgConnection, repo_uid: Uuid, name: &str) -> Result<Option<GitTags>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM git_tags
+        WHERE repo_uid = $1 AND name = $2
+        RETURNING *
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(name)
+        .fetch_optional(conn)
+        .await?;
+        Ok(row.map(|r| GitTags {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            name: r.get("name"),
+            sha: r.get("sha"),
+            created_at: r.get("created_at"),
+        }))
+    }
 }
@@ -18,13 +18,19 @@ CREATE TABLE IF NOT EXISTS users (
 CREATE TABLE IF NOT EXISTS repository (
     uid UUID PRIMARY KEY,
     name VARCHAR(100) NOT NULL,
-    owner UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    owner UUID NOT NULL,
     description TEXT NOT NULL,
+    is_public BOOLEAN NOT NULL DEFAULT TRUE,
+    parent_uid UUID REFERENCES repository(uid) ON DELETE SET NULL,
     created_at TIMESTAMP NOT NULL,
     updated_at TIMESTAMP NOT NULL,
     deleted_at TIMESTAMP,
     UNIQUE(name, owner)
 );
+ALTER TABLE repository ADD COLUMN IF NOT EXISTS is_public BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE repository ADD COLUMN IF NOT EXISTS parent_uid UUID REFERENCES repository(uid) ON DELETE SET NULL;
+-- The owner is either a user or an organization
+ALTER TABLE repository DROP CONSTRAINT IF EXISTS repository_owner_fkey;
 
 -- Create git_branch table
 CREATE TABLE IF NOT EXISTS git_branch (
@@ -65,8 +71,333 @@ CREATE TABLE IF NOT EXISTS git_commit (
 
 -- Create indexes for performance optimization
 CREATE INDEX IF NOT EXISTS idx_repository_owner ON repository(owner);
+CREATE INDEX IF NOT EXISTS idx_repository_parent_uid ON repository(parent_uid);
 CREATE INDEX IF NOT EXISTS idx_git_branch_repo_uid ON git_branch(repo_uid);
 CREATE INDEX IF NOT EXISTS idx_git_tags_repo_uid ON git_tags(repo_uid);
 CREATE INDEX IF NOT EXISTS idx_git_commit_branch_uid ON git_commit(branch_uid);
 CREATE INDEX IF NOT EXISTS idx_git_commit_repo_uid ON git_commit(repo_uid);
-CREATE INDEX IF NOT EXISTS idx_git_commit_timestamp ON git_commit(timestamp);
\ No newline at end of file
+CREATE INDEX IF NOT EXISTS idx_git_commit_timestamp ON git_commit(timestamp);
+-- Create protected_branch table
+CREATE TABLE IF NOT EXISTS protected_branch (
+    uid UUID PRIMARY KEY,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    pattern VARCHAR(255) NOT NULL,
+    allow_force_push BOOLEAN NOT NULL DEFAULT FALSE,
+    allow_deletion BOOLEAN NOT NULL DEFAULT FALSE,
+    push_users UUID[] NOT NULL DEFAULT '{}',
+    push_roles TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE(repo_uid, pattern)
+);
+CREATE INDEX IF NOT EXISTS idx_protected_branch_repo_uid ON protected_branch(repo_uid);
+
+-- Create ssh_keys table
+CREATE TABLE IF NOT EXISTS ssh_keys (
+    uid UUID PRIMARY KEY,
+    owner UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    title VARCHAR(100) NOT NULL,
+    fingerprint VARCHAR(100) NOT NULL,
+    content TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    last_used_at TIMESTAMP,
+    UNIQUE(fingerprint)
+);
+CREATE INDEX IF NOT EXISTS idx_ssh_keys_owner ON ssh_keys(owner);
+
+-- Create personal_access_tokens table
+CREATE TABLE IF NOT EXISTS personal_access_tokens (
+    uid UUID PRIMARY KEY,
+    owner UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL,
+    scopes TEXT[] NOT NULL DEFAULT '{}',
+    expires_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE(token_hash)
+);
+CREATE INDEX IF NOT EXISTS idx_personal_access_tokens_owner ON personal_access_tokens(owner);
+
+-- Create repository_collaborator table
+CREATE TABLE IF NOT EXISTS repository_collaborator (
+    uid UUID PRIMARY KEY,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    user_uid UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL,
+    invited_by UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    accepted BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL,
+    accepted_at TIMESTAMP,
+    UNIQUE(repo_uid, user_uid)
+);
+CREATE INDEX IF NOT EXISTS idx_repository_collaborator_repo_uid ON repository_collaborator(repo_uid);
+CREATE INDEX IF NOT EXISTS idx_repository_collaborator_user_uid ON repository_collaborator(user_uid);
+
+-- Create organization table
+CREATE TABLE IF NOT EXISTS organization (
+    uid UUID PRIMARY KEY,
+    name VARCHAR(50) NOT NULL,
+    description TEXT NOT NULL,
+    created_by UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    deleted_at TIMESTAMP,
+    UNIQUE(name)
+);
+
+-- Create organization_member table
+CREATE TABLE IF NOT EXISTS organization_member (
+    uid UUID PRIMARY KEY,
+    org_uid UUID NOT NULL REFERENCES organization(uid) ON DELETE CASCADE,
+    user_uid UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE(org_uid, user_uid)
+);
+CREATE INDEX IF NOT EXISTS idx_organization_member_user_uid ON organization_member(user_uid);
+
+-- Create team table
+CREATE TABLE IF NOT EXISTS team (
+    uid UUID PRIMARY KEY,
+    org_uid UUID NOT NULL REFERENCES organization(uid) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE(org_uid, name)
+);
+
+-- Create team_member table
+CREATE TABLE IF NOT EXISTS team_member (
+    uid UUID PRIMARY KEY,
+    team_uid UUID NOT NULL REFERENCES team(uid) ON DELETE CASCADE,
+    user_uid UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE(team_uid, user_uid)
+);
+CREATE INDEX IF NOT EXISTS idx_team_member_user_uid ON team_member(user_uid);
+
+-- Create team_repository table
+CREATE TABLE IF NOT EXISTS team_repository (
+    uid UUID PRIMARY KEY,
+    team_uid UUID NOT NULL REFERENCES team(uid) ON DELETE CASCADE,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    role VARCHAR(20) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE(team_uid, repo_uid)
+);
+CREATE INDEX IF NOT EXISTS idx_team_repository_repo_uid ON team_repository(repo_uid);
+
+-- Create pull_request table
+CREATE TABLE IF NOT EXISTS pull_request (
+    uid UUID PRIMARY KEY,
+    number BIGINT NOT NULL,
+    target_repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    target_branch VARCHAR(255) NOT NULL,
+    source_repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    source_branch VARCHAR(255) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    state VARCHAR(20) NOT NULL,
+    author UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    head_sha VARCHAR(40) NOT NULL,
+    base_sha VARCHAR(40) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    closed_at TIMESTAMP,
+    merged_by UUID REFERENCES users(uid) ON DELETE SET NULL,
+    merge_sha VARCHAR(40),
+    UNIQUE(target_repo_uid, number)
+);
+ALTER TABLE pull_request ADD COLUMN IF NOT EXISTS merged_by UUID REFERENCES users(uid) ON DELETE SET NULL;
+ALTER TABLE pull_request ADD COLUMN IF NOT EXISTS merge_sha VARCHAR(40);
+CREATE INDEX IF NOT EXISTS idx_pull_request_source_repo_uid ON pull_request(source_repo_uid);
+
+-- Create pull_request_commit table
+CREATE TABLE IF NOT EXISTS pull_request_commit (
+    uid UUID PRIMARY KEY,
+    pull_uid UUID NOT NULL REFERENCES pull_request(uid) ON DELETE CASCADE,
+    position INT NOT NULL,
+    sha VARCHAR(40) NOT NULL,
+    message TEXT NOT NULL,
+    author_name VARCHAR(100) NOT NULL,
+    author_email VARCHAR(100) NOT NULL,
+    timestamp BIGINT NOT NULL,
+    UNIQUE(pull_uid, position)
+);
+
+-- Create pull_request_review table
+CREATE TABLE IF NOT EXISTS pull_request_review (
+    uid UUID PRIMARY KEY,
+    pull_uid UUID NOT NULL REFERENCES pull_request(uid) ON DELETE CASCADE,
+    author UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    state VARCHAR(20) NOT NULL,
+    body TEXT NOT NULL,
+    commit_sha VARCHAR(40) NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_pull_request_review_pull_uid ON pull_request_review(pull_uid);
+
+-- Create pull_request_comment table
+CREATE TABLE IF NOT EXISTS pull_request_comment (
+    uid UUID PRIMARY KEY,
+    pull_uid UUID NOT NULL REFERENCES pull_request(uid) ON DELETE CASCADE,
+    review_uid UUID REFERENCES pull_request_review(uid) ON DELETE SET NULL,
+    in_reply_to UUID REFERENCES pull_request_comment(uid) ON DELETE CASCADE,
+    author UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    path TEXT NOT NULL,
+    side VARCHAR(10) NOT NULL,
+    commit_sha VARCHAR(40) NOT NULL,
+    line INT NOT NULL,
+    original_commit_sha VARCHAR(40) NOT NULL,
+    original_line INT NOT NULL,
+    outdated BOOLEAN NOT NULL DEFAULT FALSE,
+    resolved_by UUID REFERENCES users(uid) ON DELETE SET NULL,
+    resolved_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_pull_request_comment_pull_uid ON pull_request_comment(pull_uid);
+
+-- Create milestone table
+CREATE TABLE IF NOT EXISTS milestone (
+    uid UUID PRIMARY KEY,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    description TEXT NOT NULL,
+    state VARCHAR(20) NOT NULL,
+    due_on DATE,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    UNIQUE(repo_uid, title)
+);
+
+-- Create issue table
+CREATE TABLE IF NOT EXISTS issue (
+    uid UUID PRIMARY KEY,
+    number BIGINT NOT NULL,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    title VARCHAR(255) NOT NULL,
+    body TEXT NOT NULL,
+    state VARCHAR(20) NOT NULL,
+    author UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    milestone_uid UUID REFERENCES milestone(uid) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    closed_at TIMESTAMP,
+    UNIQUE(repo_uid, number)
+);
+
+-- Create issue_label table
+CREATE TABLE IF NOT EXISTS issue_label (
+    uid UUID PRIMARY KEY,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    color VARCHAR(7) NOT NULL,
+    description TEXT NOT NULL,
+    UNIQUE(repo_uid, name)
+);
+
+-- Create issue_label_link table
+CREATE TABLE IF NOT EXISTS issue_label_link (
+    issue_uid UUID NOT NULL REFERENCES issue(uid) ON DELETE CASCADE,
+    label_uid UUID NOT NULL REFERENCES issue_label(uid) ON DELETE CASCADE,
+    PRIMARY KEY(issue_uid, label_uid)
+);
+
+-- Create issue_assignee table
+CREATE TABLE IF NOT EXISTS issue_assignee (
+    issue_uid UUID NOT NULL REFERENCES issue(uid) ON DELETE CASCADE,
+    user_uid UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    PRIMARY KEY(issue_uid, user_uid)
+);
+
+-- Create issue_comment table
+CREATE TABLE IF NOT EXISTS issue_comment (
+    uid UUID PRIMARY KEY,
+    issue_uid UUID NOT NULL REFERENCES issue(uid) ON DELETE CASCADE,
+    author UUID NOT NULL REFERENCES users(uid) ON DELETE CASCADE,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_issue_comment_issue_uid ON issue_comment(issue_uid);
+
+-- Create reference_event table
+CREATE TABLE IF NOT EXISTS reference_event (
+    uid UUID PRIMARY KEY,
+    issue_uid UUID REFERENCES issue(uid) ON DELETE CASCADE,
+    pull_uid UUID REFERENCES pull_request(uid) ON DELETE CASCADE,
+    source_repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    commit_sha VARCHAR(40) NOT NULL,
+    author_name VARCHAR(100) NOT NULL,
+    summary TEXT NOT NULL,
+    closed BOOLEAN NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE(issue_uid, commit_sha),
+    UNIQUE(pull_uid, commit_sha)
+);
+
+-- Create webhook table
+CREATE TABLE IF NOT EXISTS webhook (
+    uid UUID PRIMARY KEY,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    content_type VARCHAR(10) NOT NULL,
+    events TEXT[] NOT NULL,
+    active BOOLEAN NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_repo_uid ON webhook(repo_uid);
+
+-- Create webhook_delivery table
+CREATE TABLE IF NOT EXISTS webhook_delivery (
+    uid UUID PRIMARY KEY,
+    webhook_uid UUID NOT NULL REFERENCES webhook(uid) ON DELETE CASCADE,
+    event VARCHAR(20) NOT NULL,
+    payload TEXT NOT NULL,
+    redelivery BOOLEAN NOT NULL,
+    status_code INT,
+    response_body TEXT,
+    error TEXT,
+    duration_ms BIGINT NOT NULL,
+    delivered_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_webhook_delivery_webhook_uid ON webhook_delivery(webhook_uid);
+
+-- Create job table
+CREATE TABLE IF NOT EXISTS job (
+    uid UUID PRIMARY KEY,
+    repo_uid UUID NOT NULL REFERENCES repository(uid) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    payload JSONB NOT NULL,
+    state VARCHAR(10) NOT NULL,
+    attempts INT NOT NULL,
+    max_attempts INT NOT NULL,
+    run_at TIMESTAMP NOT NULL,
+    locked_until TIMESTAMP,
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_job_state_run_at ON job(state, run_at);
+CREATE INDEX IF NOT EXISTS idx_job_repo_uid ON job(repo_uid);
+ALTER TABLE job ADD COLUMN IF NOT EXISTS ordering_key VARCHAR(64);
+UPDATE job SET ordering_key = CASE kind
+    WHEN 'sync_refs' THEN repo_uid::TEXT
+    WHEN 'webhook_delivery' THEN payload->>'webhook_uid'
+END
+WHERE ordering_key IS NULL AND state IN ('queued', 'running');
+CREATE INDEX IF NOT EXISTS idx_job_repo_uid_ordering_key ON job(repo_uid, ordering_key);
+
+-- Issues and pull requests share one number sequence per repository
+ALTER TABLE repository ADD COLUMN IF NOT EXISTS next_number BIGINT NOT NULL DEFAULT 1;
+UPDATE repository r SET next_number = GREATEST(
+    r.next_number,
+    (SELECT COALESCE(MAX(number), 0) + 1 FROM issue WHERE repo_uid = r.uid),
+    (SELECT COALESCE(MAX(number), 0) + 1 FROM pull_request WHERE target_repo_uid = r.uid)
+);
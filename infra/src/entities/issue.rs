@@ -0,0 +1,272 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const ISSUE_OPEN: &str = "open";
+pub const ISSUE_CLOSED: &str = "closed";
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct IssueModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    /// Sequential per repository, shared with pull requests.
+    pub number: i64,
+    pub repo_uid: Uuid,
+    pub title: String,
+    pub body: String,
+    pub state: String,
+    pub author: Uuid,
+    pub milestone_uid: Option<Uuid>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+    pub closed_at: Option<chrono::NaiveDateTime>,
+}
+
+/// Conditions an issue listing is narrowed by; `None` matches everything.
+#[derive(Clone, Debug, Default)]
+pub struct IssueQuery {
+    pub state: Option<String>,
+    pub author: Option<Uuid>,
+    pub assignee: Option<Uuid>,
+    pub label: Option<Uuid>,
+    pub milestone: Option<Uuid>,
+}
+
+impl IssueModel {
+    pub fn is_open(&self) -> bool {
+        self.state == ISSUE_OPEN
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        title: &str,
+        body: &str,
+        author: Uuid,
+        milestone_uid: Option<Uuid>,
+    ) -> Result<IssueModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        WITH counter AS (
+            UPDATE repository SET next_number = next_number + 1
+            WHERE uid = $2
+            RETURNING next_number - 1 AS number
+        )
+        INSERT INTO issue (uid, number, repo_uid, title, body, state, author, milestone_uid, created_at, updated_at, closed_at)
+        SELECT $1, counter.number, $2, $3, $4, $5, $6, $7, $8, $8, NULL FROM counter
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(title)
+        .bind(body)
+        .bind(ISSUE_OPEN)
+        .bind(author)
+        .bind(milestone_uid)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(IssueModel {
+            uid: row.get("uid"),
+            number: row.get("number"),
+            repo_uid: row.get("repo_uid"),
+            title: row.get("title"),
+            body: row.get("body"),
+            state: row.get("state"),
+            author: row.get("author"),
+            milestone_uid: row.get("milestone_uid"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            closed_at: row.get("closed_at"),
+        })
+    }
+
+    pub async fn get_by_repo_uid_and_number(pool: &PgPool, repo_uid: Uuid, number: i64) -> Result<Option<IssueModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM issue
+        WHERE repo_uid = $1 AND number = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(number)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            repo_uid: r.get("repo_uid"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            milestone_uid: r.get("milestone_uid"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+        }))
+    }
+
+    /// Issues of a repository matching `query`, newest first.
+    pub async fn get_by_repo_uid(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        query: &IssueQuery,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<IssueModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM issue
+        WHERE repo_uid = $1
+            AND ($2::VARCHAR IS NULL OR state = $2)
+            AND ($3::UUID IS NULL OR author = $3)
+            AND ($4::UUID IS NULL OR EXISTS (SELECT 1 FROM issue_assignee a WHERE a.issue_uid = issue.uid AND a.user_uid = $4))
+            AND ($5::UUID IS NULL OR EXISTS (SELECT 1 FROM issue_label_link l WHERE l.issue_uid = issue.uid AND l.label_uid = $5))
+            AND ($6::UUID IS NULL OR milestone_uid = $6)
+        ORDER BY number DESC
+        LIMIT $7 OFFSET $8
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(&query.state)
+        .bind(query.author)
+        .bind(query.assignee)
+        .bind(query.label)
+        .bind(query.milestone)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        let issues = rows
+            .into_iter()
+            .map(|r| IssueModel {
+                uid: r.get("uid"),
+                number: r.get("number"),
+                repo_uid: r.get("repo_uid"),
+                title: r.get("title"),
+                body: r.get("body"),
+                state: r.get("state"),
+                author: r.get("author"),
+                milestone_uid: r.get("milestone_uid"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+                closed_at: r.get("closed_at"),
+            })
+            .collect();
+        Ok(issues)
+    }
+
+    pub async fn count_by_repo_uid(pool: &PgPool, repo_uid: Uuid, query: &IssueQuery) -> Result<i64, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT COUNT(*) FROM issue
+        WHERE repo_uid = $1
+            AND ($2::VARCHAR IS NULL OR state = $2)
+            AND ($3::UUID IS NULL OR author = $3)
+            AND ($4::UUID IS NULL OR EXISTS (SELECT 1 FROM issue_assignee a WHERE a.issue_uid = issue.uid AND a.user_uid = $4))
+            AND ($5::UUID IS NULL OR EXISTS (SELECT 1 FROM issue_label_link l WHERE l.issue_uid = issue.uid AND l.label_uid = $5))
+            AND ($6::UUID IS NULL OR milestone_uid = $6)
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(&query.state)
+        .bind(query.author)
+        .bind(query.assignee)
+        .bind(query.label)
+        .bind(query.milestone)
+        .fetch_one(pool)
+        .await?;
+        Ok(row.get::<i64, usize>(0))
+    }
+
+    pub async fn update(pool: &PgPool, uid: Uuid, title: Option<&str>, body: Option<&str>) -> Result<Option<IssueModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE issue
+        SET title = COALESCE($1, title),
+            body = COALESCE($2, body),
+            updated_at = $3
+        WHERE uid = $4
+        RETURNING *
+        "#,
+        )
+        .bind(title)
+        .bind(body)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            repo_uid: r.get("repo_uid"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            milestone_uid: r.get("milestone_uid"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+        }))
+    }
+
+    pub async fn update_milestone(pool: &PgPool, uid: Uuid, milestone_uid: Option<Uuid>) -> Result<(), Error> {
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        UPDATE issue
+        SET milestone_uid = $1,
+            updated_at = $2
+        WHERE uid = $3
+        "#,
+        )
+        .bind(milestone_uid)
+        .bind(now)
+        .bind(uid)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Moves an issue to `state`, setting `closed_at` when it is closed.
+    pub async fn update_state(pool: &PgPool, uid: Uuid, state: &str) -> Result<Option<IssueModel>, Error> {
+        let now = Local::now().naive_local();
+        let closed_at = (state == ISSUE_CLOSED).then_some(now);
+        let row = sqlx::query(
+            r#"
+        UPDATE issue
+        SET state = $1,
+            closed_at = $2,
+            updated_at = $3
+        WHERE uid = $4
+        RETURNING *
+        "#,
+        )
+        .bind(state)
+        .bind(closed_at)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            repo_uid: r.get("repo_uid"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            milestone_uid: r.get("milestone_uid"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+        }))
+    }
+}
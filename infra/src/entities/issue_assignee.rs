@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct IssueAssigneeModel {
+    pub issue_uid: Uuid,
+    pub user_uid: Uuid,
+}
+
+impl IssueAssigneeModel {
+    pub async fn get_by_issue_uid(pool: &PgPool, issue_uid: Uuid) -> Result<Vec<IssueAssigneeModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM issue_assignee
+        WHERE issue_uid = $1
+        "#,
+        )
+        .bind(issue_uid)
+        .fetch_all(pool)
+        .await?;
+        let assignees = rows
+            .into_iter()
+            .map(|r| IssueAssigneeModel {
+                issue_uid: r.get("issue_uid"),
+                user_uid: r.get("user_uid"),
+            })
+            .collect();
+        Ok(assignees)
+    }
+
+    /// Replaces the assignees of an issue.
+    pub async fn replace_by_issue_uid(pool: &PgPool, issue_uid: Uuid, user_uids: &[Uuid]) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM issue_assignee WHERE issue_uid = $1")
+            .bind(issue_uid)
+            .execute(&mut *tx)
+            .await?;
+        for user_uid in user_uids {
+            sqlx::query(
+                r#"
+            INSERT INTO issue_assignee (issue_uid, user_uid)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            )
+            .bind(issue_uid)
+            .bind(user_uid)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct IssueCommentModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub issue_uid: Uuid,
+    pub author: Uuid,
+    pub body: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl IssueCommentModel {
+    pub async fn create(pool: &PgPool, issue_uid: Uuid, author: Uuid, body: &str) -> Result<IssueCommentModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO issue_comment (uid, issue_uid, author, body, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $5)
+        "#,
+        )
+        .bind(uid)
+        .bind(issue_uid)
+        .bind(author)
+        .bind(body)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(IssueCommentModel {
+            uid,
+            issue_uid,
+            author,
+            body: body.to_string(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<IssueCommentModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM issue_comment
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueCommentModel {
+            uid: r.get("uid"),
+            issue_uid: r.get("issue_uid"),
+            author: r.get("author"),
+            body: r.get("body"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Comments of an issue, oldest first.
+    pub async fn get_by_issue_uid(pool: &PgPool, issue_uid: Uuid, limit: i32, offset: i32) -> Result<Vec<IssueCommentModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM issue_comment
+        WHERE issue_uid = $1
+        ORDER BY created_at ASC
+        LIMIT $2 OFFSET $3
+        "#,
+        )
+        .bind(issue_uid)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        let comments = rows
+            .into_iter()
+            .map(|r| IssueCommentModel {
+                uid: r.get("uid"),
+                issue_uid: r.get("issue_uid"),
+                author: r.get("author"),
+                body: r.get("body"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(comments)
+    }
+
+    pub async fn count_by_issue_uid(pool: &PgPool, issue_uid: Uuid) -> Result<i64, Error> {
+        let row = sqlx::query("SELECT COUNT(*) FROM issue_comment WHERE issue_uid = $1")
+            .bind(issue_uid)
+            .fetch_one(pool)
+            .await?;
+        Ok(row.get::<i64, usize>(0))
+    }
+
+    pub async fn update(pool: &PgPool, uid: Uuid, body: &str) -> Result<Option<IssueCommentModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE issue_comment
+        SET body = $1,
+            updated_at = $2
+        WHERE uid = $3
+        RETURNING *
+        "#,
+        )
+        .bind(body)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueCommentModel {
+            uid: r.get("uid"),
+            issue_uid: r.get("issue_uid"),
+            author: r.get("author"),
+            body: r.get("body"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM issue_comment WHERE uid = $1")
+            .bind(uid)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct IssueLabelModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub name: String,
+    /// Hex color including the leading `#`.
+    pub color: String,
+    pub description: String,
+}
+
+impl IssueLabelModel {
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        name: &str,
+        color: &str,
+        description: &str,
+    ) -> Result<IssueLabelModel, Error> {
+        let uid = Uuid::new_v4();
+        sqlx::query(
+            r#"
+        INSERT INTO issue_label (uid, repo_uid, name, color, description)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(name)
+        .bind(color)
+        .bind(description)
+        .execute(pool)
+        .await?;
+        Ok(IssueLabelModel {
+            uid,
+            repo_uid,
+            name: name.to_string(),
+            color: color.to_string(),
+            description: description.to_string(),
+        })
+    }
+
+    pub async fn get_by_repo_uid(pool: &PgPool, repo_uid: Uuid) -> Result<Vec<IssueLabelModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM issue_label
+        WHERE repo_uid = $1
+        ORDER BY name ASC
+        "#,
+        )
+        .bind(repo_uid)
+        .fetch_all(pool)
+        .await?;
+        let labels = rows
+            .into_iter()
+            .map(|r| IssueLabelModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                name: r.get("name"),
+                color: r.get("color"),
+                description: r.get("description"),
+            })
+            .collect();
+        Ok(labels)
+    }
+
+    pub async fn get_by_repo_uid_and_name(pool: &PgPool, repo_uid: Uuid, name: &str) -> Result<Option<IssueLabelModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM issue_label
+        WHERE repo_uid = $1 AND name = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueLabelModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            name: r.get("name"),
+            color: r.get("color"),
+            description: r.get("description"),
+        }))
+    }
+
+    /// Labels applied to an issue.
+    pub async fn get_by_issue_uid(pool: &PgPool, issue_uid: Uuid) -> Result<Vec<IssueLabelModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT l.* FROM issue_label l
+        JOIN issue_label_link k ON k.label_uid = l.uid
+        WHERE k.issue_uid = $1
+        ORDER BY l.name ASC
+        "#,
+        )
+        .bind(issue_uid)
+        .fetch_all(pool)
+        .await?;
+        let labels = rows
+            .into_iter()
+            .map(|r| IssueLabelModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                name: r.get("name"),
+                color: r.get("color"),
+                description: r.get("description"),
+            })
+            .collect();
+        Ok(labels)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        uid: Uuid,
+        name: Option<&str>,
+        color: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Option<IssueLabelModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        UPDATE issue_label
+        SET name = COALESCE($1, name),
+            color = COALESCE($2, color),
+            description = COALESCE($3, description)
+        WHERE uid = $4
+        RETURNING *
+        "#,
+        )
+        .bind(name)
+        .bind(color)
+        .bind(description)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| IssueLabelModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            name: r.get("name"),
+            color: r.get("color"),
+            description: r.get("description"),
+        }))
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM issue_label WHERE uid = $1")
+            .bind(uid)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces the labels applied to an issue.
+    pub async fn replace_by_issue_uid(pool: &PgPool, issue_uid: Uuid, label_uids: &[Uuid]) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM issue_label_link WHERE issue_uid = $1")
+            .bind(issue_uid)
+            .execute(&mut *tx)
+            .await?;
+        for label_uid in label_uids {
+            sqlx::query(
+                r#"
+            INSERT INTO issue_label_link (issue_uid, label_uid)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            )
+            .bind(issue_uid)
+            .bind(label_uid)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const JOB_QUEUED: &str = "queued";
+pub const JOB_RUNNING: &str = "running";
+pub const JOB_DONE: &str = "done";
+/// Failed on every attempt; left alone until retried by hand.
+pub const JOB_DEAD: &str = "dead";
+
+/// A unit of background work on a repository, run by the job workers.
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct JobModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub kind: String,
+    pub payload: Value,
+    /// Jobs of a repository sharing a key run in the order they were queued,
+    /// retries included; those without one in any order.
+    pub ordering_key: Option<String>,
+    pub state: String,
+    /// Attempts started so far, the running one included.
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// When the job may next be picked up.
+    pub run_at: NaiveDateTime,
+    /// While running, when the job is considered abandoned by its worker.
+    pub locked_until: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl JobModel {
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        kind: &str,
+        payload: &Value,
+        ordering_key: Option<&str>,
+        max_attempts: i32,
+    ) -> Result<JobModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO job (uid, repo_uid, kind, payload, ordering_key, state, attempts, max_attempts, run_at, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, 0, $7, $8, $8, $8)
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(kind)
+        .bind(payload)
+        .bind(ordering_key)
+        .bind(JOB_QUEUED)
+        .bind(max_attempts)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(JobModel {
+            uid,
+            repo_uid,
+            kind: kind.to_string(),
+            payload: payload.clone(),
+            ordering_key: ordering_key.map(|x| x.to_string()),
+            state: JOB_QUEUED.to_string(),
+            attempts: 0,
+            max_attempts,
+            run_at: now,
+            locked_until: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<JobModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM job
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| JobModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            kind: r.get("kind"),
+            payload: r.get("payload"),
+            ordering_key: r.get("ordering_key"),
+            state: r.get("state"),
+            attempts: r.get("attempts"),
+            max_attempts: r.get("max_attempts"),
+            run_at: r.get("run_at"),
+            locked_until: r.get("locked_until"),
+            last_error: r.get("last_error"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Jobs of a repository, optionally only those in `state`, newest first.
+    pub async fn get_by_repo_uid(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        state: Option<&str>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<JobModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM job
+        WHERE repo_uid = $1 AND ($2::VARCHAR IS NULL OR state = $2)
+        ORDER BY created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(state)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        let jobs = rows
+            .into_iter()
+            .map(|r| JobModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                kind: r.get("kind"),
+                payload: r.get("payload"),
+                ordering_key: r.get("ordering_key"),
+                state: r.get("state"),
+                attempts: r.get("attempts"),
+                max_attempts: r.get("max_attempts"),
+                run_at: r.get("run_at"),
+                locked_until: r.get("locked_until"),
+                last_error: r.get("last_error"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(jobs)
+    }
+
+    /// Takes the next job due, or one whose worker let its lock expire while
+    /// attempts remain, and locks it until `locked_until`. Concurrent workers
+    /// never take the same job, a repository runs one job at a time, and jobs
+    /// of a repository sharing an ordering key run in the order they were
+    /// queued, retries included.
+    pub async fn claim(pool: &PgPool, locked_until: NaiveDateTime) -> Result<Option<JobModel>, Error> {
+        let now = Local::now().naive_local();
+        let mut tx = pool.begin().await?;
+        // only repositories with a job that can run now, so those waiting on
+        // a running job or a retry do not crowd out the rest
+        let repos = sqlx::query(
+            r#"
+        SELECT j.repo_uid FROM job j
+        WHERE ((j.state = $1 AND j.run_at <= $2)
+            OR (j.state = $3 AND j.locked_until < $2 AND j.attempts < j.max_attempts))
+          AND NOT EXISTS (
+            SELECT 1 FROM job r
+            WHERE r.repo_uid = j.repo_uid AND r.state = $3 AND r.locked_until >= $2
+          )
+          AND NOT EXISTS (
+            SELECT 1 FROM job r
+            WHERE r.repo_uid = j.repo_uid AND r.ordering_key = j.ordering_key AND r.state IN ($1, $3)
+              AND (r.created_at, r.uid) < (j.created_at, j.uid)
+          )
+        GROUP BY j.repo_uid
+        ORDER BY MIN(j.run_at)
+        LIMIT 16
+        "#,
+        )
+        .bind(JOB_QUEUED)
+        .bind(now)
+        .bind(JOB_RUNNING)
+        .fetch_all(&mut *tx)
+        .await?;
+        for repo in repos {
+            let repo_uid: Uuid = repo.get("repo_uid");
+            // held until the claim commits, so whoever takes the lock next
+            // sees the job it made running
+            let locked: bool = sqlx::query("SELECT pg_try_advisory_xact_lock(hashtextextended($1::TEXT, 0))")
+                .bind(repo_uid)
+                .fetch_one(&mut *tx)
+                .await?
+                .get(0);
+            if !locked {
+                continue;
+            }
+            let row = sqlx::query(
+                r#"
+            UPDATE job
+            SET state = $1, attempts = attempts + 1, locked_until = $2, updated_at = $3
+            WHERE uid = (
+                SELECT uid FROM job j
+                WHERE j.repo_uid = $5
+                  AND ((j.state = $4 AND j.run_at <= $3)
+                    OR (j.state = $1 AND j.locked_until < $3 AND j.attempts < j.max_attempts))
+                  AND NOT EXISTS (
+                    SELECT 1 FROM job r
+                    WHERE r.repo_uid = j.repo_uid AND r.state = $1 AND r.locked_until >= $3
+                  )
+                  AND NOT EXISTS (
+                    SELECT 1 FROM job r
+                    WHERE r.repo_uid = j.repo_uid AND r.ordering_key = j.ordering_key AND r.state IN ($1, $4)
+                      AND (r.created_at, r.uid) < (j.created_at, j.uid)
+                  )
+                ORDER BY j.run_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+            )
+            .bind(JOB_RUNNING)
+            .bind(locked_until)
+            .bind(now)
+            .bind(JOB_QUEUED)
+            .bind(repo_uid)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(r) = row {
+                tx.commit().await?;
+                return Ok(Some(JobModel {
+                    uid: r.get("uid"),
+                    repo_uid: r.get("repo_uid"),
+                    kind: r.get("kind"),
+                    payload: r.get("payload"),
+                    ordering_key: r.get("ordering_key"),
+                    state: r.get("state"),
+                    attempts: r.get("attempts"),
+                    max_attempts: r.get("max_attempts"),
+                    run_at: r.get("run_at"),
+                    locked_until: r.get("locked_until"),
+                    last_error: r.get("last_error"),
+                    created_at: r.get("created_at"),
+                    updated_at: r.get("updated_at"),
+                }));
+            }
+        }
+        tx.commit().await?;
+        Ok(None)
+    }
+
+    /// Marks the attempt `attempt` of a job as succeeded, unless the job was
+    /// taken over after its lock expired.
+    pub async fn complete(pool: &PgPool, uid: Uuid, attempt: i32) -> Result<u64, Error> {
+        let now = Local::now().naive_local();
+        let result = sqlx::query(
+            r#"
+        UPDATE job
+        SET state = $1, locked_until = NULL, updated_at = $2
+        WHERE uid = $3 AND state = $4 AND attempts = $5
+        "#,
+        )
+        .bind(JOB_DONE)
+        .bind(now)
+        .bind(uid)
+        .bind(JOB_RUNNING)
+        .bind(attempt)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Records the failure of attempt `attempt`, queueing the job again at
+    /// `retry_at` or, without one, leaving it dead.
+    pub async fn fail(
+        pool: &PgPool,
+        uid: Uuid,
+        attempt: i32,
+        error: &str,
+        retry_at: Option<NaiveDateTime>,
+    ) -> Result<u64, Error> {
+        let now = Local::now().naive_local();
+        let state = if retry_at.is_some() { JOB_QUEUED } else { JOB_DEAD };
+        let result = sqlx::query(
+            r#"
+        UPDATE job
+        SET state = $1, run_at = COALESCE($2, run_at), locked_until = NULL, last_error = $3, updated_at = $4
+        WHERE uid = $5 AND state = $6 AND attempts = $7
+        "#,
+        )
+        .bind(state)
+        .bind(retry_at)
+        .bind(error)
+        .bind(now)
+        .bind(uid)
+        .bind(JOB_RUNNING)
+        .bind(attempt)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Kills running jobs whose lock expired on their last attempt.
+    pub async fn bury_expired(pool: &PgPool) -> Result<u64, Error> {
+        let now = Local::now().naive_local();
+        let result = sqlx::query(
+            r#"
+        UPDATE job
+        SET state = $1, locked_until = NULL, last_error = 'timed out', updated_at = $2
+        WHERE state = $3 AND locked_until < $2 AND attempts >= max_attempts
+        "#,
+        )
+        .bind(JOB_DEAD)
+        .bind(now)
+        .bind(JOB_RUNNING)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Queues a dead job again with a fresh set of attempts.
+    pub async fn retry(pool: &PgPool, uid: Uuid) -> Result<Option<JobModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE job
+        SET state = $1, attempts = 0, run_at = $2, updated_at = $2
+        WHERE uid = $3 AND state = $4
+        RETURNING *
+        "#,
+        )
+        .bind(JOB_QUEUED)
+        .bind(now)
+        .bind(uid)
+        .bind(JOB_DEAD)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| JobModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            kind: r.get("kind"),
+            payload: r.get("payload"),
+            ordering_key: r.get("ordering_key"),
+            state: r.get("state"),
+            attempts: r.get("attempts"),
+            max_attempts: r.get("max_attempts"),
+            run_at: r.get("run_at"),
+            locked_until: r.get("locked_until"),
+            last_error: r.get("last_error"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Deletes jobs that succeeded before `before`.
+    pub async fn delete_done(pool: &PgPool, before: NaiveDateTime) -> Result<u64, Error> {
+        let result = sqlx::query(
+            r#"
+        DELETE FROM job
+        WHERE state = $1 AND updated_at < $2
+        "#,
+        )
+        .bind(JOB_DONE)
+        .bind(before)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sqlx::postgres::PgPoolOptions;
+
+    /// One connection whose temporary `job` table, without the foreign keys,
+    /// shadows the real one; `None` without a `DATABASE_URL`.
+    async fn job_pool() -> Option<PgPool> {
+        let url = std::env::var("DATABASE_URL").ok()?;
+        let pool = PgPoolOptions::new().max_connections(1).connect(&url).await.unwrap();
+        sqlx::query("CREATE TEMP TABLE job (LIKE public.job INCLUDING DEFAULTS)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        Some(pool)
+    }
+
+    #[tokio::test]
+    async fn test_job_claim_ordering_key() {
+        let Some(pool) = job_pool().await else {
+            return;
+        };
+        let repo = Uuid::new_v4();
+        let locked_until = Local::now().naive_local() + chrono::Duration::minutes(10);
+        for _ in 0..2 {
+            JobModel::create(&pool, repo, "webhook_delivery", &json!({}), Some("a"), 5).await.unwrap();
+        }
+
+        // the first delivery to hook a fails and waits for its retry, holding
+        // back the second one
+        let failing = JobModel::claim(&pool, locked_until).await.unwrap().unwrap();
+        let retry_at = Local::now().naive_local() + chrono::Duration::hours(1);
+        JobModel::fail(&pool, failing.uid, failing.attempts, "refused", Some(retry_at)).await.unwrap();
+        assert!(JobModel::claim(&pool, locked_until).await.unwrap().is_none());
+
+        // but not the deliveries to hook b
+        let other = JobModel::create(&pool, repo, "webhook_delivery", &json!({}), Some("b"), 5).await.unwrap();
+        let claimed = JobModel::claim(&pool, locked_until).await.unwrap().unwrap();
+        assert_eq!(claimed.uid, other.uid);
+        // and the repository runs one job at a time
+        let gc = JobModel::create(&pool, repo, "repository_gc", &json!(null), None, 3).await.unwrap();
+        assert!(JobModel::claim(&pool, locked_until).await.unwrap().is_none());
+        JobModel::complete(&pool, claimed.uid, claimed.attempts).await.unwrap();
+        assert_eq!(JobModel::claim(&pool, locked_until).await.unwrap().unwrap().uid, gc.uid);
+    }
+}
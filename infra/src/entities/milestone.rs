@@ -0,0 +1,183 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const MILESTONE_OPEN: &str = "open";
+pub const MILESTONE_CLOSED: &str = "closed";
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct MilestoneModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub title: String,
+    pub description: String,
+    pub state: String,
+    pub due_on: Option<NaiveDate>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl MilestoneModel {
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        title: &str,
+        description: &str,
+        due_on: Option<NaiveDate>,
+    ) -> Result<MilestoneModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO milestone (uid, repo_uid, title, description, state, due_on, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $7)
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(title)
+        .bind(description)
+        .bind(MILESTONE_OPEN)
+        .bind(due_on)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(MilestoneModel {
+            uid,
+            repo_uid,
+            title: title.to_string(),
+            description: description.to_string(),
+            state: MILESTONE_OPEN.to_string(),
+            due_on,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<MilestoneModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM milestone
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| MilestoneModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            title: r.get("title"),
+            description: r.get("description"),
+            state: r.get("state"),
+            due_on: r.get("due_on"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn get_by_repo_uid_and_title(pool: &PgPool, repo_uid: Uuid, title: &str) -> Result<Option<MilestoneModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM milestone
+        WHERE repo_uid = $1 AND title = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(title)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| MilestoneModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            title: r.get("title"),
+            description: r.get("description"),
+            state: r.get("state"),
+            due_on: r.get("due_on"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Milestones of a repository, soonest due first, optionally limited to one state.
+    pub async fn get_by_repo_uid(pool: &PgPool, repo_uid: Uuid, state: Option<&str>) -> Result<Vec<MilestoneModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM milestone
+        WHERE repo_uid = $1 AND ($2::VARCHAR IS NULL OR state = $2)
+        ORDER BY due_on ASC NULLS LAST, title ASC
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(state)
+        .fetch_all(pool)
+        .await?;
+        let milestones = rows
+            .into_iter()
+            .map(|r| MilestoneModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                title: r.get("title"),
+                description: r.get("description"),
+                state: r.get("state"),
+                due_on: r.get("due_on"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(milestones)
+    }
+
+    /// Updates the given fields; `due_on` is replaced as a whole, so pass the
+    /// current value to keep it.
+    pub async fn update(
+        pool: &PgPool,
+        uid: Uuid,
+        title: Option<&str>,
+        description: Option<&str>,
+        state: Option<&str>,
+        due_on: Option<NaiveDate>,
+    ) -> Result<Option<MilestoneModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE milestone
+        SET title = COALESCE($1, title),
+            description = COALESCE($2, description),
+            state = COALESCE($3, state),
+            due_on = $4,
+            updated_at = $5
+        WHERE uid = $6
+        RETURNING *
+        "#,
+        )
+        .bind(title)
+        .bind(description)
+        .bind(state)
+        .bind(due_on)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| MilestoneModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            title: r.get("title"),
+            description: r.get("description"),
+            state: r.get("state"),
+            due_on: r.get("due_on"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM milestone WHERE uid = $1")
+            .bind(uid)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,5 +1,27 @@
+pub mod collaborator;
 pub mod git_branch;
 pub mod git_commit;
 pub mod git_tags;
+pub mod issue;
+pub mod issue_assignee;
+pub mod issue_comment;
+pub mod issue_label;
+pub mod milestone;
+pub mod organization;
+pub mod organization_member;
+pub mod personal_access_tokens;
+pub mod protected_branch;
+pub mod pull_request;
+pub mod pull_request_comment;
+pub mod pull_request_commit;
+pub mod pull_request_review;
+pub mod reference_event;
 pub mod repository;
+pub mod ssh_keys;
+pub mod team;
+pub mod team_member;
+pub mod team_repository;
 pub mod users;
+pub mod webhook;
+pub mod webhook_delivery;
+pub mod job;
@@ -0,0 +1,155 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+/// An account that owns repositories on behalf of its members. Organizations
+/// share the owner namespace with users, so `name` never collides with a username.
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct OrganizationModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub name: String,
+    pub description: String,
+    pub created_by: Uuid,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+    pub deleted_at: Option<chrono::NaiveDateTime>,
+}
+
+impl OrganizationModel {
+    pub async fn create(
+        pool: &PgPool,
+        name: &str,
+        description: &str,
+        created_by: Uuid,
+    ) -> Result<OrganizationModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO organization (uid, name, description, created_by, created_at, updated_at, deleted_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(name)
+        .bind(description)
+        .bind(created_by)
+        .bind(now)
+        .bind(now)
+        .bind(None::<chrono::NaiveDateTime>)
+        .fetch_one(pool)
+        .await?;
+        Ok(OrganizationModel {
+            uid: row.get("uid"),
+            name: row.get("name"),
+            description: row.get("description"),
+            created_by: row.get("created_by"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            deleted_at: row.get("deleted_at"),
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<OrganizationModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM organization
+        WHERE uid = $1 AND deleted_at IS NULL
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| OrganizationModel {
+            uid: r.get("uid"),
+            name: r.get("name"),
+            description: r.get("description"),
+            created_by: r.get("created_by"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            deleted_at: r.get("deleted_at"),
+        }))
+    }
+
+    pub async fn get_by_name(pool: &PgPool, name: &str) -> Result<Option<OrganizationModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM organization
+        WHERE name = $1 AND deleted_at IS NULL
+        "#,
+        )
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| OrganizationModel {
+            uid: r.get("uid"),
+            name: r.get("name"),
+            description: r.get("description"),
+            created_by: r.get("created_by"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            deleted_at: r.get("deleted_at"),
+        }))
+    }
+
+    pub async fn get_by_member(pool: &PgPool, user_uid: Uuid) -> Result<Vec<OrganizationModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT o.* FROM organization o
+        JOIN organization_member m ON m.org_uid = o.uid
+        WHERE m.user_uid = $1 AND o.deleted_at IS NULL
+        ORDER BY o.name ASC
+        "#,
+        )
+        .bind(user_uid)
+        .fetch_all(pool)
+        .await?;
+        let orgs = rows
+            .into_iter()
+            .map(|r| OrganizationModel {
+                uid: r.get("uid"),
+                name: r.get("name"),
+                description: r.get("description"),
+                created_by: r.get("created_by"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+                deleted_at: r.get("deleted_at"),
+            })
+            .collect();
+        Ok(orgs)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        uid: Uuid,
+        description: Option<&str>,
+    ) -> Result<Option<OrganizationModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE organization
+        SET description = COALESCE($1, description),
+            updated_at = $2
+        WHERE uid = $3 AND deleted_at IS NULL
+        RETURNING *
+        "#,
+        )
+        .bind(description)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| OrganizationModel {
+            uid: r.get("uid"),
+            name: r.get("name"),
+            description: r.get("description"),
+            created_by: r.get("created_by"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            deleted_at: r.get("deleted_at"),
+        }))
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const ORG_ROLE_OWNER: &str = "owner";
+pub const ORG_ROLE_MEMBER: &str = "member";
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct OrganizationMemberModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub org_uid: Uuid,
+    pub user_uid: Uuid,
+    /// `owner` or `member`; owners administer the organization and all of its repositories.
+    pub role: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl OrganizationMemberModel {
+    pub fn is_owner(&self) -> bool {
+        self.role == ORG_ROLE_OWNER
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        org_uid: Uuid,
+        user_uid: Uuid,
+        role: &str,
+    ) -> Result<OrganizationMemberModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO organization_member (uid, org_uid, user_uid, role, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(org_uid)
+        .bind(user_uid)
+        .bind(role)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(OrganizationMemberModel {
+            uid: row.get("uid"),
+            org_uid: row.get("org_uid"),
+            user_uid: row.get("user_uid"),
+            role: row.get("role"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    pub async fn get_by_org_uid_and_user_uid(
+        pool: &PgPool,
+        org_uid: Uuid,
+        user_uid: Uuid,
+    ) -> Result<Option<OrganizationMemberModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM organization_member
+        WHERE org_uid = $1 AND user_uid = $2
+        "#,
+        )
+        .bind(org_uid)
+        .bind(user_uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| OrganizationMemberModel {
+            uid: r.get("uid"),
+            org_uid: r.get("org_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    pub async fn get_by_org_uid(pool: &PgPool, org_uid: Uuid) -> Result<Vec<OrganizationMemberModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM organization_member
+        WHERE org_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(org_uid)
+        .fetch_all(pool)
+        .await?;
+        let members = rows
+            .into_iter()
+            .map(|r| OrganizationMemberModel {
+                uid: r.get("uid"),
+                org_uid: r.get("org_uid"),
+                user_uid: r.get("user_uid"),
+                role: r.get("role"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(members)
+    }
+
+    pub async fn update_role(
+        pool: &PgPool,
+        uid: Uuid,
+        role: &str,
+    ) -> Result<Option<OrganizationMemberModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        UPDATE organization_member
+        SET role = $1
+        WHERE uid = $2
+        RETURNING *
+        "#,
+        )
+        .bind(role)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| OrganizationMemberModel {
+            uid: r.get("uid"),
+            org_uid: r.get("org_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    /// Removes a member along with their memberships in the organization's teams.
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<OrganizationMemberModel>, Error> {
+        let mut tx = pool.begin().await?;
+        let row = sqlx::query(
+            r#"
+        DELETE FROM organization_member
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let member = row.map(|r| OrganizationMemberModel {
+            uid: r.get("uid"),
+            org_uid: r.get("org_uid"),
+            user_uid: r.get("user_uid"),
+            role: r.get("role"),
+            created_at: r.get("created_at"),
+        });
+        if let Some(member) = &member {
+            sqlx::query(
+                r#"
+            DELETE FROM team_member
+            WHERE user_uid = $1 AND team_uid IN (SELECT uid FROM team WHERE org_uid = $2)
+            "#,
+            )
+            .bind(member.user_uid)
+            .bind(member.org_uid)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(member)
+    }
+}
@@ -0,0 +1,179 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct PersonalAccessTokenModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub owner: Uuid,
+    pub name: String,
+    #[serde(skip_serializing)]
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl PersonalAccessTokenModel {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|x| x <= Utc::now().naive_utc())
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        owner: Uuid,
+        name: &str,
+        token_hash: &str,
+        scopes: &[String],
+        expires_at: Option<NaiveDateTime>,
+    ) -> Result<PersonalAccessTokenModel, Error> {
+        let uid = Uuid::new_v4();
+        let created_at = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO personal_access_tokens (uid, owner, name, token_hash, scopes, expires_at, last_used_at, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NULL, $7)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(owner)
+        .bind(name)
+        .bind(token_hash)
+        .bind(scopes)
+        .bind(expires_at)
+        .bind(created_at)
+        .fetch_one(pool)
+        .await?;
+        Ok(PersonalAccessTokenModel {
+            uid: row.get("uid"),
+            owner: row.get("owner"),
+            name: row.get("name"),
+            token_hash: row.get("token_hash"),
+            scopes: row.get("scopes"),
+            expires_at: row.get("expires_at"),
+            last_used_at: row.get("last_used_at"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<PersonalAccessTokenModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM personal_access_tokens
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PersonalAccessTokenModel {
+            uid: r.get("uid"),
+            owner: r.get("owner"),
+            name: r.get("name"),
+            token_hash: r.get("token_hash"),
+            scopes: r.get("scopes"),
+            expires_at: r.get("expires_at"),
+            last_used_at: r.get("last_used_at"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    pub async fn get_by_token_hash(
+        pool: &PgPool,
+        token_hash: &str,
+    ) -> Result<Option<PersonalAccessTokenModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM personal_access_tokens
+        WHERE token_hash = $1
+        "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PersonalAccessTokenModel {
+            uid: r.get("uid"),
+            owner: r.get("owner"),
+            name: r.get("name"),
+            token_hash: r.get("token_hash"),
+            scopes: r.get("scopes"),
+            expires_at: r.get("expires_at"),
+            last_used_at: r.get("last_used_at"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    pub async fn get_by_owner(
+        pool: &PgPool,
+        owner: Uuid,
+    ) -> Result<Vec<PersonalAccessTokenModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM personal_access_tokens
+        WHERE owner = $1
+        ORDER BY created_at DESC
+        "#,
+        )
+        .bind(owner)
+        .fetch_all(pool)
+        .await?;
+        let tokens = rows
+            .into_iter()
+            .map(|r| PersonalAccessTokenModel {
+                uid: r.get("uid"),
+                owner: r.get("owner"),
+                name: r.get("name"),
+                token_hash: r.get("token_hash"),
+                scopes: r.get("scopes"),
+                expires_at: r.get("expires_at"),
+                last_used_at: r.get("last_used_at"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(tokens)
+    }
+
+    pub async fn touch(pool: &PgPool, uid: Uuid) -> Result<u64, Error> {
+        let last_used_at = Utc::now().naive_utc();
+        let result = sqlx::query(
+            r#"
+        UPDATE personal_access_tokens
+        SET last_used_at = $1
+        WHERE uid = $2
+        "#,
+        )
+        .bind(last_used_at)
+        .bind(uid)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<PersonalAccessTokenModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM personal_access_tokens
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PersonalAccessTokenModel {
+            uid: r.get("uid"),
+            owner: r.get("owner"),
+            name: r.get("name"),
+            token_hash: r.get("token_hash"),
+            scopes: r.get("scopes"),
+            expires_at: r.get("expires_at"),
+            last_used_at: r.get("last_used_at"),
+            created_at: r.get("created_at"),
+        }))
+    }
+}
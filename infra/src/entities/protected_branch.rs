@@ -0,0 +1,250 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct ProtectedBranchModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub pattern: String,
+    pub allow_force_push: bool,
+    pub allow_deletion: bool,
+    pub push_users: Vec<Uuid>,
+    pub push_roles: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl ProtectedBranchModel {
+    /// Matches a branch name against the rule pattern, where `*` stands for any
+    /// run of characters except `/` and `**` for any run of characters.
+    pub fn matches(&self, branch: &str) -> bool {
+        fn glob(pattern: &[u8], name: &[u8]) -> bool {
+            match pattern {
+                [] => name.is_empty(),
+                [b'*', b'*', rest @ ..] => (0..=name.len()).any(|idx| glob(rest, &name[idx..])),
+                [b'*', rest @ ..] => (0..=name.len())
+                    .take_while(|idx| *idx == 0 || name[idx - 1] != b'/')
+                    .any(|idx| glob(rest, &name[idx..])),
+                [head, rest @ ..] => name.first() == Some(head) && glob(rest, &name[1..]),
+            }
+        }
+        glob(self.pattern.as_bytes(), branch.as_bytes())
+    }
+
+    /// An empty allow list leaves pushing open to everyone with write access.
+    pub fn restricts_push(&self) -> bool {
+        !self.push_users.is_empty() || !self.push_roles.is_empty()
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        pattern: &str,
+        allow_force_push: bool,
+        allow_deletion: bool,
+        push_users: &[Uuid],
+        push_roles: &[String],
+    ) -> Result<ProtectedBranchModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO protected_branch (uid, repo_uid, pattern, allow_force_push, allow_deletion, push_users, push_roles, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(pattern)
+        .bind(allow_force_push)
+        .bind(allow_deletion)
+        .bind(push_users)
+        .bind(push_roles)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(ProtectedBranchModel {
+            uid: row.get("uid"),
+            repo_uid: row.get("repo_uid"),
+            pattern: row.get("pattern"),
+            allow_force_push: row.get("allow_force_push"),
+            allow_deletion: row.get("allow_deletion"),
+            push_users: row.get("push_users"),
+            push_roles: row.get("push_roles"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<ProtectedBranchModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM protected_branch
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| ProtectedBranchModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            pattern: r.get("pattern"),
+            allow_force_push: r.get("allow_force_push"),
+            allow_deletion: r.get("allow_deletion"),
+            push_users: r.get("push_users"),
+            push_roles: r.get("push_roles"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn get_by_repo_uid(
+        pool: &PgPool,
+        repo_uid: Uuid,
+    ) -> Result<Vec<ProtectedBranchModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM protected_branch
+        WHERE repo_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(repo_uid)
+        .fetch_all(pool)
+        .await?;
+        let rules = rows
+            .into_iter()
+            .map(|r| ProtectedBranchModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                pattern: r.get("pattern"),
+                allow_force_push: r.get("allow_force_push"),
+                allow_deletion: r.get("allow_deletion"),
+                push_users: r.get("push_users"),
+                push_roles: r.get("push_roles"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(rules)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        uid: Uuid,
+        allow_force_push: Option<bool>,
+        allow_deletion: Option<bool>,
+        push_users: Option<&[Uuid]>,
+        push_roles: Option<&[String]>,
+    ) -> Result<Option<ProtectedBranchModel>, Error> {
+        let updated_at = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        UPDATE protected_branch
+        SET allow_force_push = COALESCE($1, allow_force_push),
+            allow_deletion = COALESCE($2, allow_deletion),
+            push_users = COALESCE($3, push_users),
+            push_roles = COALESCE($4, push_roles),
+            updated_at = $5
+        WHERE uid = $6
+        RETURNING *
+        "#,
+        )
+        .bind(allow_force_push)
+        .bind(allow_deletion)
+        .bind(push_users)
+        .bind(push_roles)
+        .bind(updated_at)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| ProtectedBranchModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            pattern: r.get("pattern"),
+            allow_force_push: r.get("allow_force_push"),
+            allow_deletion: r.get("allow_deletion"),
+            push_users: r.get("push_users"),
+            push_roles: r.get("push_roles"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<ProtectedBranchModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM protected_branch
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| ProtectedBranchModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            pattern: r.get("pattern"),
+            allow_force_push: r.get("allow_force_push"),
+            allow_deletion: r.get("allow_deletion"),
+            push_users: r.get("push_users"),
+            push_roles: r.get("push_roles"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str) -> ProtectedBranchModel {
+        let now = Utc::now().naive_utc();
+        ProtectedBranchModel {
+            uid: Uuid::new_v4(),
+            repo_uid: Uuid::new_v4(),
+            pattern: pattern.to_string(),
+            allow_force_push: false,
+            allow_deletion: false,
+            push_users: vec![],
+            push_roles: vec![],
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_protected_branch_matches_exact() {
+        assert!(rule("main").matches("main"));
+        assert!(!rule("main").matches("main2"));
+        assert!(!rule("main").matches("mai"));
+        assert!(!rule("main").matches("feature/main"));
+    }
+
+    #[test]
+    fn test_protected_branch_matches_star() {
+        assert!(rule("feature-*").matches("feature-x"));
+        assert!(rule("feature-*").matches("feature-"));
+        assert!(!rule("feature-*").matches("feature-x/y"));
+        assert!(rule("*").matches("main"));
+        assert!(!rule("*").matches("a/b"));
+        assert!(rule("**").matches("a/b"));
+    }
+
+    #[test]
+    fn test_protected_branch_matches_prefix() {
+        assert!(rule("release/*").matches("release/1.0"));
+        assert!(rule("release/*").matches("release/"));
+        assert!(!rule("release/*").matches("release/1/x"));
+        assert!(!rule("release/*").matches("release"));
+        assert!(rule("release/**").matches("release/1/x"));
+    }
+}
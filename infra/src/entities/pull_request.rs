@@ -0,0 +1,381 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const PULL_REQUEST_OPEN: &str = "open";
+pub const PULL_REQUEST_CLOSED: &str = "closed";
+pub const PULL_REQUEST_MERGED: &str = "merged";
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct PullRequestModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    /// Sequential per target repository, shared with issues.
+    pub number: i64,
+    pub target_repo_uid: Uuid,
+    pub target_branch: String,
+    pub source_repo_uid: Uuid,
+    pub source_branch: String,
+    pub title: String,
+    pub body: String,
+    pub state: String,
+    pub author: Uuid,
+    pub head_sha: String,
+    /// Where the target branch pointed when the head was last synced.
+    pub base_sha: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+    pub closed_at: Option<chrono::NaiveDateTime>,
+    pub merged_by: Option<Uuid>,
+    /// The commit the target branch was moved to by the merge.
+    pub merge_sha: Option<String>,
+}
+
+impl PullRequestModel {
+    pub fn is_open(&self) -> bool {
+        self.state == PULL_REQUEST_OPEN
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        target_repo_uid: Uuid,
+        target_branch: &str,
+        source_repo_uid: Uuid,
+        source_branch: &str,
+        title: &str,
+        body: &str,
+        author: Uuid,
+    ) -> Result<PullRequestModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        WITH counter AS (
+            UPDATE repository SET next_number = next_number + 1
+            WHERE uid = $2
+            RETURNING next_number - 1 AS number
+        )
+        INSERT INTO pull_request (uid, number, target_repo_uid, target_branch, source_repo_uid, source_branch,
+            title, body, state, author, head_sha, base_sha, created_at, updated_at, closed_at, merged_by, merge_sha)
+        SELECT $1, counter.number, $2, $3, $4, $5, $6, $7, $8, $9, '', '', $10, $11, NULL, NULL, NULL FROM counter
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(target_repo_uid)
+        .bind(target_branch)
+        .bind(source_repo_uid)
+        .bind(source_branch)
+        .bind(title)
+        .bind(body)
+        .bind(PULL_REQUEST_OPEN)
+        .bind(author)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(PullRequestModel {
+            uid: row.get("uid"),
+            number: row.get("number"),
+            target_repo_uid: row.get("target_repo_uid"),
+            target_branch: row.get("target_branch"),
+            source_repo_uid: row.get("source_repo_uid"),
+            source_branch: row.get("source_branch"),
+            title: row.get("title"),
+            body: row.get("body"),
+            state: row.get("state"),
+            author: row.get("author"),
+            head_sha: row.get("head_sha"),
+            base_sha: row.get("base_sha"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            closed_at: row.get("closed_at"),
+            merged_by: row.get("merged_by"),
+            merge_sha: row.get("merge_sha"),
+        })
+    }
+
+    pub async fn get_by_repo_uid_and_number(
+        pool: &PgPool,
+        target_repo_uid: Uuid,
+        number: i64,
+    ) -> Result<Option<PullRequestModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM pull_request
+        WHERE target_repo_uid = $1 AND number = $2
+        "#,
+        )
+        .bind(target_repo_uid)
+        .bind(number)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PullRequestModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            target_repo_uid: r.get("target_repo_uid"),
+            target_branch: r.get("target_branch"),
+            source_repo_uid: r.get("source_repo_uid"),
+            source_branch: r.get("source_branch"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            head_sha: r.get("head_sha"),
+            base_sha: r.get("base_sha"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+            merged_by: r.get("merged_by"),
+            merge_sha: r.get("merge_sha"),
+        }))
+    }
+
+    /// Pull requests into a repository, newest first, optionally limited to one state.
+    pub async fn get_by_repo_uid(
+        pool: &PgPool,
+        target_repo_uid: Uuid,
+        state: Option<&str>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<PullRequestModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM pull_request
+        WHERE target_repo_uid = $1 AND ($2::VARCHAR IS NULL OR state = $2)
+        ORDER BY number DESC
+        LIMIT $3 OFFSET $4
+        "#,
+        )
+        .bind(target_repo_uid)
+        .bind(state)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        let pulls = rows
+            .into_iter()
+            .map(|r| PullRequestModel {
+                uid: r.get("uid"),
+                number: r.get("number"),
+                target_repo_uid: r.get("target_repo_uid"),
+                target_branch: r.get("target_branch"),
+                source_repo_uid: r.get("source_repo_uid"),
+                source_branch: r.get("source_branch"),
+                title: r.get("title"),
+                body: r.get("body"),
+                state: r.get("state"),
+                author: r.get("author"),
+                head_sha: r.get("head_sha"),
+                base_sha: r.get("base_sha"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+                closed_at: r.get("closed_at"),
+                merged_by: r.get("merged_by"),
+                merge_sha: r.get("merge_sha"),
+            })
+            .collect();
+        Ok(pulls)
+    }
+
+    pub async fn count_by_repo_uid(pool: &PgPool, target_repo_uid: Uuid, state: Option<&str>) -> Result<i64, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT COUNT(*) FROM pull_request
+        WHERE target_repo_uid = $1 AND ($2::VARCHAR IS NULL OR state = $2)
+        "#,
+        )
+        .bind(target_repo_uid)
+        .bind(state)
+        .fetch_one(pool)
+        .await?;
+        Ok(row.get::<i64, usize>(0))
+    }
+
+    /// Open pull requests whose source or target is the given repository.
+    pub async fn get_open_by_repo_uid(pool: &PgPool, repo_uid: Uuid) -> Result<Vec<PullRequestModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM pull_request
+        WHERE (source_repo_uid = $1 OR target_repo_uid = $1) AND state = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(PULL_REQUEST_OPEN)
+        .fetch_all(pool)
+        .await?;
+        let pulls = rows
+            .into_iter()
+            .map(|r| PullRequestModel {
+                uid: r.get("uid"),
+                number: r.get("number"),
+                target_repo_uid: r.get("target_repo_uid"),
+                target_branch: r.get("target_branch"),
+                source_repo_uid: r.get("source_repo_uid"),
+                source_branch: r.get("source_branch"),
+                title: r.get("title"),
+                body: r.get("body"),
+                state: r.get("state"),
+                author: r.get("author"),
+                head_sha: r.get("head_sha"),
+                base_sha: r.get("base_sha"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+                closed_at: r.get("closed_at"),
+                merged_by: r.get("merged_by"),
+                merge_sha: r.get("merge_sha"),
+            })
+            .collect();
+        Ok(pulls)
+    }
+
+    pub async fn update(
+        pool: &PgPool,
+        uid: Uuid,
+        title: Option<&str>,
+        body: Option<&str>,
+        target_branch: Option<&str>,
+    ) -> Result<Option<PullRequestModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE pull_request
+        SET title = COALESCE($1, title),
+            body = COALESCE($2, body),
+            target_branch = COALESCE($3, target_branch),
+            updated_at = $4
+        WHERE uid = $5
+        RETURNING *
+        "#,
+        )
+        .bind(title)
+        .bind(body)
+        .bind(target_branch)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PullRequestModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            target_repo_uid: r.get("target_repo_uid"),
+            target_branch: r.get("target_branch"),
+            source_repo_uid: r.get("source_repo_uid"),
+            source_branch: r.get("source_branch"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            head_sha: r.get("head_sha"),
+            base_sha: r.get("base_sha"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+            merged_by: r.get("merged_by"),
+            merge_sha: r.get("merge_sha"),
+        }))
+    }
+
+    pub async fn update_state(pool: &PgPool, uid: Uuid, state: &str) -> Result<Option<PullRequestModel>, Error> {
+        let now = Local::now().naive_local();
+        let closed_at = (state != PULL_REQUEST_OPEN).then_some(now);
+        let row = sqlx::query(
+            r#"
+        UPDATE pull_request
+        SET state = $1,
+            closed_at = $2,
+            updated_at = $3
+        WHERE uid = $4
+        RETURNING *
+        "#,
+        )
+        .bind(state)
+        .bind(closed_at)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PullRequestModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            target_repo_uid: r.get("target_repo_uid"),
+            target_branch: r.get("target_branch"),
+            source_repo_uid: r.get("source_repo_uid"),
+            source_branch: r.get("source_branch"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            head_sha: r.get("head_sha"),
+            base_sha: r.get("base_sha"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+            merged_by: r.get("merged_by"),
+            merge_sha: r.get("merge_sha"),
+        }))
+    }
+
+    /// Marks an open pull request merged; returns `None` if it was no longer open.
+    pub async fn update_merged(pool: &PgPool, uid: Uuid, merged_by: Uuid, merge_sha: &str) -> Result<Option<PullRequestModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE pull_request
+        SET state = $1,
+            merged_by = $2,
+            merge_sha = $3,
+            closed_at = $4,
+            updated_at = $4
+        WHERE uid = $5 AND state = $6
+        RETURNING *
+        "#,
+        )
+        .bind(PULL_REQUEST_MERGED)
+        .bind(merged_by)
+        .bind(merge_sha)
+        .bind(now)
+        .bind(uid)
+        .bind(PULL_REQUEST_OPEN)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PullRequestModel {
+            uid: r.get("uid"),
+            number: r.get("number"),
+            target_repo_uid: r.get("target_repo_uid"),
+            target_branch: r.get("target_branch"),
+            source_repo_uid: r.get("source_repo_uid"),
+            source_branch: r.get("source_branch"),
+            title: r.get("title"),
+            body: r.get("body"),
+            state: r.get("state"),
+            author: r.get("author"),
+            head_sha: r.get("head_sha"),
+            base_sha: r.get("base_sha"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+            closed_at: r.get("closed_at"),
+            merged_by: r.get("merged_by"),
+            merge_sha: r.get("merge_sha"),
+        }))
+    }
+
+    pub async fn update_head(pool: &PgPool, uid: Uuid, head_sha: &str, base_sha: &str) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+        UPDATE pull_request
+        SET head_sha = $1,
+            base_sha = $2
+        WHERE uid = $3
+        "#,
+        )
+        .bind(head_sha)
+        .bind(base_sha)
+        .bind(uid)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const COMMENT_SIDE_LEFT: &str = "left";
+pub const COMMENT_SIDE_RIGHT: &str = "right";
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct PullRequestCommentModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub pull_uid: Uuid,
+    pub review_uid: Option<Uuid>,
+    /// The first comment of the thread; replies share its anchor.
+    pub in_reply_to: Option<Uuid>,
+    pub author: Uuid,
+    pub body: String,
+    pub path: String,
+    /// `left` for the base side of the diff, `right` for the head side.
+    pub side: String,
+    /// The commit whose version of `path` the line refers to: the head for
+    /// the right side, the merge base for the left side.
+    pub commit_sha: String,
+    pub line: i32,
+    pub original_commit_sha: String,
+    pub original_line: i32,
+    /// The commented line was changed by a later push and no longer moves with the head.
+    pub outdated: bool,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<chrono::NaiveDateTime>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl PullRequestCommentModel {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        pull_uid: Uuid,
+        review_uid: Option<Uuid>,
+        author: Uuid,
+        body: &str,
+        path: &str,
+        side: &str,
+        commit_sha: &str,
+        line: i32,
+    ) -> Result<PullRequestCommentModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO pull_request_comment (uid, pull_uid, review_uid, in_reply_to, author, body, path, side,
+            commit_sha, line, original_commit_sha, original_line, outdated, resolved_by, resolved_at, created_at, updated_at)
+        VALUES ($1, $2, $3, NULL, $4, $5, $6, $7, $8, $9, $8, $9, FALSE, NULL, NULL, $10, $10)
+        "#,
+        )
+        .bind(uid)
+        .bind(pull_uid)
+        .bind(review_uid)
+        .bind(author)
+        .bind(body)
+        .bind(path)
+        .bind(side)
+        .bind(commit_sha)
+        .bind(line)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(PullRequestCommentModel {
+            uid,
+            pull_uid,
+            review_uid,
+            in_reply_to: None,
+            author,
+            body: body.to_string(),
+            path: path.to_string(),
+            side: side.to_string(),
+            commit_sha: commit_sha.to_string(),
+            line,
+            original_commit_sha: commit_sha.to_string(),
+            original_line: line,
+            outdated: false,
+            resolved_by: None,
+            resolved_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Adds a reply to the thread started by `root`, copying its anchor.
+    pub async fn create_reply(
+        pool: &PgPool,
+        root: &PullRequestCommentModel,
+        author: Uuid,
+        body: &str,
+    ) -> Result<PullRequestCommentModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO pull_request_comment (uid, pull_uid, review_uid, in_reply_to, author, body, path, side,
+            commit_sha, line, original_commit_sha, original_line, outdated, resolved_by, resolved_at, created_at, updated_at)
+        VALUES ($1, $2, NULL, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NULL, NULL, $13, $13)
+        "#,
+        )
+        .bind(uid)
+        .bind(root.pull_uid)
+        .bind(root.uid)
+        .bind(author)
+        .bind(body)
+        .bind(&root.path)
+        .bind(&root.side)
+        .bind(&root.commit_sha)
+        .bind(root.line)
+        .bind(&root.original_commit_sha)
+        .bind(root.original_line)
+        .bind(root.outdated)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(PullRequestCommentModel {
+            uid,
+            pull_uid: root.pull_uid,
+            review_uid: None,
+            in_reply_to: Some(root.uid),
+            author,
+            body: body.to_string(),
+            path: root.path.clone(),
+            side: root.side.clone(),
+            commit_sha: root.commit_sha.clone(),
+            line: root.line,
+            original_commit_sha: root.original_commit_sha.clone(),
+            original_line: root.original_line,
+            outdated: root.outdated,
+            resolved_by: None,
+            resolved_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<PullRequestCommentModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM pull_request_comment
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| PullRequestCommentModel {
+            uid: r.get("uid"),
+            pull_uid: r.get("pull_uid"),
+            review_uid: r.get("review_uid"),
+            in_reply_to: r.get("in_reply_to"),
+            author: r.get("author"),
+            body: r.get("body"),
+            path: r.get("path"),
+            side: r.get("side"),
+            commit_sha: r.get("commit_sha"),
+            line: r.get("line"),
+            original_commit_sha: r.get("original_commit_sha"),
+            original_line: r.get("original_line"),
+            outdated: r.get("outdated"),
+            resolved_by: r.get("resolved_by"),
+            resolved_at: r.get("resolved_at"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    /// Comments of a pull request, oldest first.
+    pub async fn get_by_pull_uid(pool: &PgPool, pull_uid: Uuid) -> Result<Vec<PullRequestCommentModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM pull_request_comment
+        WHERE pull_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(pull_uid)
+        .fetch_all(pool)
+        .await?;
+        let comments = rows
+            .into_iter()
+            .map(|r| PullRequestCommentModel {
+                uid: r.get("uid"),
+                pull_uid: r.get("pull_uid"),
+                review_uid: r.get("review_uid"),
+                in_reply_to: r.get("in_reply_to"),
+                author: r.get("author"),
+                body: r.get("body"),
+                path: r.get("path"),
+                side: r.get("side"),
+                commit_sha: r.get("commit_sha"),
+                line: r.get("line"),
+                original_commit_sha: r.get("original_commit_sha"),
+                original_line: r.get("original_line"),
+                outdated: r.get("outdated"),
+                resolved_by: r.get("resolved_by"),
+                resolved_at: r.get("resolved_at"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(comments)
+    }
+
+    /// First comments of the threads that still follow the head.
+    pub async fn get_current_roots_by_pull_uid(pool: &PgPool, pull_uid: Uuid) -> Result<Vec<PullRequestCommentModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM pull_request_comment
+        WHERE pull_uid = $1 AND in_reply_to IS NULL AND outdated = FALSE
+        "#,
+        )
+        .bind(pull_uid)
+        .fetch_all(pool)
+        .await?;
+        let comments = rows
+            .into_iter()
+            .map(|r| PullRequestCommentModel {
+                uid: r.get("uid"),
+                pull_uid: r.get("pull_uid"),
+                review_uid: r.get("review_uid"),
+                in_reply_to: r.get("in_reply_to"),
+                author: r.get("author"),
+                body: r.get("body"),
+                path: r.get("path"),
+                side: r.get("side"),
+                commit_sha: r.get("commit_sha"),
+                line: r.get("line"),
+                original_commit_sha: r.get("original_commit_sha"),
+                original_line: r.get("original_line"),
+                outdated: r.get("outdated"),
+                resolved_by: r.get("resolved_by"),
+                resolved_at: r.get("resolved_at"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(comments)
+    }
+
+    /// Moves a whole thread to `line` of `commit_sha`.
+    pub async fn update_anchor(pool: &PgPool, root_uid: Uuid, commit_sha: &str, line: i32) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+        UPDATE pull_request_comment
+        SET commit_sha = $1,
+            line = $2
+        WHERE uid = $3 OR in_reply_to = $3
+        "#,
+        )
+        .bind(commit_sha)
+        .bind(line)
+        .bind(root_uid)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update_outdated(pool: &PgPool, root_uid: Uuid) -> Result<(), Error> {
+        sqlx::query(
+            r#"
+        UPDATE pull_request_comment
+        SET outdated = TRUE
+        WHERE uid = $1 OR in_reply_to = $1
+        "#,
+        )
+        .bind(root_uid)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks the thread started by `root_uid` resolved by `user`, or unresolved when `None`.
+    pub async fn update_resolved(pool: &PgPool, root_uid: Uuid, user: Option<Uuid>) -> Result<(), Error> {
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        UPDATE pull_request_comment
+        SET resolved_by = $1,
+            resolved_at = CASE WHEN $1::UUID IS NULL THEN NULL ELSE $2 END,
+            updated_at = $2
+        WHERE uid = $3
+        "#,
+        )
+        .bind(user)
+        .bind(now)
+        .bind(root_uid)
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
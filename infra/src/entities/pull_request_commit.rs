@@ -0,0 +1,76 @@
+use git::commit::list::GitCommit;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct PullRequestCommitModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub pull_uid: Uuid,
+    /// Order of the commit in the pull request, oldest first.
+    pub position: i32,
+    pub sha: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub timestamp: i64,
+}
+
+impl PullRequestCommitModel {
+    pub async fn get_by_pull_uid(pool: &PgPool, pull_uid: Uuid) -> Result<Vec<PullRequestCommitModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM pull_request_commit
+        WHERE pull_uid = $1
+        ORDER BY position ASC
+        "#,
+        )
+        .bind(pull_uid)
+        .fetch_all(pool)
+        .await?;
+        let commits = rows
+            .into_iter()
+            .map(|r| PullRequestCommitModel {
+                uid: r.get("uid"),
+                pull_uid: r.get("pull_uid"),
+                position: r.get("position"),
+                sha: r.get("sha"),
+                message: r.get("message"),
+                author_name: r.get("author_name"),
+                author_email: r.get("author_email"),
+                timestamp: r.get("timestamp"),
+            })
+            .collect();
+        Ok(commits)
+    }
+
+    /// Replaces the commit list of a pull request.
+    pub async fn replace(pool: &PgPool, pull_uid: Uuid, commits: &[GitCommit]) -> Result<(), Error> {
+        let mut tx = pool.begin().await?;
+        sqlx::query("DELETE FROM pull_request_commit WHERE pull_uid = $1")
+            .bind(pull_uid)
+            .execute(&mut *tx)
+            .await?;
+        for (position, commit) in commits.iter().enumerate() {
+            sqlx::query(
+                r#"
+            INSERT INTO pull_request_commit (uid, pull_uid, position, sha, message, author_name, author_email, timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(pull_uid)
+            .bind(position as i32)
+            .bind(&commit.hash)
+            .bind(&commit.message)
+            .bind(&commit.author)
+            .bind(&commit.email)
+            .bind(commit.timestamp)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
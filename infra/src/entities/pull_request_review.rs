@@ -0,0 +1,85 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const REVIEW_APPROVED: &str = "approved";
+pub const REVIEW_CHANGES_REQUESTED: &str = "changes_requested";
+pub const REVIEW_COMMENTED: &str = "commented";
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct PullRequestReviewModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub pull_uid: Uuid,
+    pub author: Uuid,
+    pub state: String,
+    pub body: String,
+    /// The head of the pull request the review was given on.
+    pub commit_sha: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl PullRequestReviewModel {
+    pub async fn create(
+        pool: &PgPool,
+        pull_uid: Uuid,
+        author: Uuid,
+        state: &str,
+        body: &str,
+        commit_sha: &str,
+    ) -> Result<PullRequestReviewModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO pull_request_review (uid, pull_uid, author, state, body, commit_sha, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        "#,
+        )
+        .bind(uid)
+        .bind(pull_uid)
+        .bind(author)
+        .bind(state)
+        .bind(body)
+        .bind(commit_sha)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(PullRequestReviewModel {
+            uid,
+            pull_uid,
+            author,
+            state: state.to_string(),
+            body: body.to_string(),
+            commit_sha: commit_sha.to_string(),
+            created_at: now,
+        })
+    }
+
+    pub async fn get_by_pull_uid(pool: &PgPool, pull_uid: Uuid) -> Result<Vec<PullRequestReviewModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM pull_request_review
+        WHERE pull_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(pull_uid)
+        .fetch_all(pool)
+        .await?;
+        let reviews = rows
+            .into_iter()
+            .map(|r| PullRequestReviewModel {
+                uid: r.get("uid"),
+                pull_uid: r.get("pull_uid"),
+                author: r.get("author"),
+                state: r.get("state"),
+                body: r.get("body"),
+                commit_sha: r.get("commit_sha"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(reviews)
+    }
+}
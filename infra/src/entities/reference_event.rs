@@ -0,0 +1,115 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+/// A commit that mentioned an issue or a pull request in its message.
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct ReferenceEventModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub issue_uid: Option<Uuid>,
+    pub pull_uid: Option<Uuid>,
+    /// The repository the commit was pushed to.
+    pub source_repo_uid: Uuid,
+    pub commit_sha: String,
+    pub author_name: String,
+    /// First line of the commit message.
+    pub summary: String,
+    /// The commit closed the issue.
+    pub closed: bool,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl ReferenceEventModel {
+    /// Records a reference; a commit is recorded once per issue or pull
+    /// request. Returns whether the reference was new.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        issue_uid: Option<Uuid>,
+        pull_uid: Option<Uuid>,
+        source_repo_uid: Uuid,
+        commit_sha: &str,
+        author_name: &str,
+        summary: &str,
+        closed: bool,
+    ) -> Result<bool, Error> {
+        let now = Local::now().naive_local();
+        let result = sqlx::query(
+            r#"
+        INSERT INTO reference_event (uid, issue_uid, pull_uid, source_repo_uid, commit_sha, author_name, summary, closed, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT DO NOTHING
+        "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(issue_uid)
+        .bind(pull_uid)
+        .bind(source_repo_uid)
+        .bind(commit_sha)
+        .bind(author_name)
+        .bind(summary)
+        .bind(closed)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get_by_issue_uid(pool: &PgPool, issue_uid: Uuid) -> Result<Vec<ReferenceEventModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM reference_event
+        WHERE issue_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(issue_uid)
+        .fetch_all(pool)
+        .await?;
+        let events = rows
+            .into_iter()
+            .map(|r| ReferenceEventModel {
+                uid: r.get("uid"),
+                issue_uid: r.get("issue_uid"),
+                pull_uid: r.get("pull_uid"),
+                source_repo_uid: r.get("source_repo_uid"),
+                commit_sha: r.get("commit_sha"),
+                author_name: r.get("author_name"),
+                summary: r.get("summary"),
+                closed: r.get("closed"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(events)
+    }
+
+    pub async fn get_by_pull_uid(pool: &PgPool, pull_uid: Uuid) -> Result<Vec<ReferenceEventModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM reference_event
+        WHERE pull_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(pull_uid)
+        .fetch_all(pool)
+        .await?;
+        let events = rows
+            .into_iter()
+            .map(|r| ReferenceEventModel {
+                uid: r.get("uid"),
+                issue_uid: r.get("issue_uid"),
+                pull_uid: r.get("pull_uid"),
+                source_repo_uid: r.get("source_repo_uid"),
+                commit_sha: r.get("commit_sha"),
+                author_name: r.get("author_name"),
+                summary: r.get("summary"),
+                closed: r.get("closed"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(events)
+    }
+}
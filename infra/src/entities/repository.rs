@@ -12,6 +12,9 @@ pub struct RepositoryModel {
     pub name: String,
     pub owner: Uuid,
     pub description: String,
+    pub is_public: bool,
+    /// The repository this one was forked from.
+    pub parent_uid: Option<Uuid>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
     pub deleted_at: Option<chrono::NaiveDateTime>,
@@ -30,13 +33,15 @@ impl RepositoryModel {
         name: &str,
         owner: Uuid,
         description: &str,
+        is_public: bool,
+        parent_uid: Option<Uuid>,
     ) -> Result<RepositoryModel, Error> {
         let uid = Uuid::new_v4();
         let now = Local::now().naive_local();
         let row = sqlx::query(
             r#"
-        INSERT INTO repository (uid, name, owner, description, created_at, updated_at, deleted_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO repository (uid, name, owner, description, is_public, parent_uid, created_at, updated_at, deleted_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING *
         "#,
         )
@@ -44,6 +49,8 @@ impl RepositoryModel {
         .bind(name)
         .bind(owner)
         .bind(description)
+        .bind(is_public)
+        .bind(parent_uid)
         .bind(now)
         .bind(now)
         .bind(None::<chrono::NaiveDateTime>)
@@ -54,6 +61,8 @@ impl RepositoryModel {
             name: row.get("name"),
             owner: row.get("owner"),
             description: row.get("description"),
+            is_public: row.get("is_public"),
+            parent_uid: row.get("parent_uid"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             deleted_at: row.get("deleted_at"),
@@ -75,6 +84,8 @@ impl RepositoryModel {
             name: r.get("name"),
             owner: r.get("owner"),
             description: r.get("description"),
+            is_public: r.get("is_public"),
+            parent_uid: r.get("parent_uid"),
             created_at: r.get("created_at"),
             updated_at: r.get("updated_at"),
             deleted_at: r.get("deleted_at"),
@@ -98,6 +109,36 @@ impl RepositoryModel {
                 name: r.get("name"),
                 owner: r.get("owner"),
                 description: r.get("description"),
+                is_public: r.get("is_public"),
+                parent_uid: r.get("parent_uid"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+                deleted_at: r.get("deleted_at"),
+            })
+            .collect();
+        Ok(repos)
+    }
+
+    pub async fn get_by_parent_uid(pool: &PgPool, parent_uid: Uuid) -> Result<Vec<RepositoryModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM repository
+        WHERE parent_uid = $1 AND deleted_at IS NULL
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(parent_uid)
+        .fetch_all(pool)
+        .await?;
+        let repos = rows
+            .into_iter()
+            .map(|r| RepositoryModel {
+                uid: r.get("uid"),
+                name: r.get("name"),
+                owner: r.get("owner"),
+                description: r.get("description"),
+                is_public: r.get("is_public"),
+                parent_uid: r.get("parent_uid"),
                 created_at: r.get("created_at"),
                 updated_at: r.get("updated_at"),
                 deleted_at: r.get("deleted_at"),
@@ -111,6 +152,7 @@ impl RepositoryModel {
         uid: Uuid,
         name: Option<&str>,
         description: Option<&str>,
+        is_public: Option<bool>,
     ) -> Result<Option<RepositoryModel>, Error> {
         let now = Local::now().naive_local();
         let row = sqlx::query(
@@ -118,13 +160,15 @@ impl RepositoryModel {
         UPDATE repository
         SET name = COALESCE($1, name),
             description = COALESCE($2, description),
-            updated_at = $3
-        WHERE uid = $4 AND deleted_at IS NULL
+            is_public = COALESCE($3, is_public),
+            updated_at = $4
+        WHERE uid = $5 AND deleted_at IS NULL
         RETURNING *
         "#,
         )
         .bind(name)
         .bind(description)
+        .bind(is_public)
         .bind(now)
         .bind(uid)
         .fetch_optional(pool)
@@ -134,6 +178,8 @@ impl RepositoryModel {
             name: r.get("name"),
             owner: r.get("owner"),
             description: r.get("description"),
+            is_public: r.get("is_public"),
+            parent_uid: r.get("parent_uid"),
             created_at: r.get("created_at"),
             updated_at: r.get("updated_at"),
             deleted_at: r.get("deleted_at"),
@@ -159,6 +205,8 @@ impl RepositoryModel {
             name: r.get("name"),
             owner: r.get("owner"),
             description: r.get("description"),
+            is_public: r.get("is_public"),
+            parent_uid: r.get("parent_uid"),
             created_at: r.get("created_at"),
             updated_at: r.get("updated_at"),
             deleted_at: r.get("deleted_at"),
@@ -182,6 +230,8 @@ impl RepositoryModel {
                 name: r.get("name"),
                 owner: r.get("owner"),
                 description: r.get("description"),
+                is_public: r.get("is_public"),
+                parent_uid: r.get("parent_uid"),
                 created_at: r.get("created_at"),
                 updated_at: r.get("updated_at"),
                 deleted_at: r.get("deleted_at"),
@@ -190,6 +240,7 @@ impl RepositoryModel {
         Ok(repos)
     }
 
+    /// Finds a repository by name under `owner`, which may name a user or an organization.
     pub async fn repository_find_by_owner_name_and_repo_name(
         pool: &PgPool,
         owner: String,
@@ -198,8 +249,9 @@ impl RepositoryModel {
         let rows = sqlx::query(
             r#"
                SELECT r.* FROM repository r
-                JOIN users u ON r.owner = u.uid
-                WHERE r.name = $1 AND u.username = $2
+                LEFT JOIN users u ON r.owner = u.uid
+                LEFT JOIN organization o ON r.owner = o.uid AND o.deleted_at IS NULL
+                WHERE r.name = $1 AND (u.username = $2 OR o.name = $2)
                 LIMIT 1
                 "#,
         )
@@ -212,6 +264,8 @@ impl RepositoryModel {
             name: r.get("name"),
             owner: r.get("owner"),
             description: r.get("description"),
+            is_public: r.get("is_public"),
+            parent_uid: r.get("parent_uid"),
             created_at: r.get("created_at"),
             updated_at: r.get("updated_at"),
             deleted_at: r.get("deleted_at"),
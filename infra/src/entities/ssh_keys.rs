@@ -0,0 +1,162 @@
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct SshKeyModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub owner: Uuid,
+    pub title: String,
+    pub fingerprint: String,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+impl SshKeyModel {
+    pub async fn create(
+        pool: &PgPool,
+        owner: Uuid,
+        title: &str,
+        fingerprint: &str,
+        content: &str,
+    ) -> Result<SshKeyModel, Error> {
+        let uid = Uuid::new_v4();
+        let created_at = Utc::now().naive_utc();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO ssh_keys (uid, owner, title, fingerprint, content, created_at, last_used_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NULL)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(owner)
+        .bind(title)
+        .bind(fingerprint)
+        .bind(content)
+        .bind(created_at)
+        .fetch_one(pool)
+        .await?;
+        Ok(SshKeyModel {
+            uid: row.get("uid"),
+            owner: row.get("owner"),
+            title: row.get("title"),
+            fingerprint: row.get("fingerprint"),
+            content: row.get("content"),
+            created_at: row.get("created_at"),
+            last_used_at: row.get("last_used_at"),
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<SshKeyModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM ssh_keys
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| SshKeyModel {
+            uid: r.get("uid"),
+            owner: r.get("owner"),
+            title: r.get("title"),
+            fingerprint: r.get("fingerprint"),
+            content: r.get("content"),
+            created_at: r.get("created_at"),
+            last_used_at: r.get("last_used_at"),
+        }))
+    }
+
+    pub async fn get_by_fingerprint(
+        pool: &PgPool,
+        fingerprint: &str,
+    ) -> Result<Option<SshKeyModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM ssh_keys
+        WHERE fingerprint = $1
+        "#,
+        )
+        .bind(fingerprint)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| SshKeyModel {
+            uid: r.get("uid"),
+            owner: r.get("owner"),
+            title: r.get("title"),
+            fingerprint: r.get("fingerprint"),
+            content: r.get("content"),
+            created_at: r.get("created_at"),
+            last_used_at: r.get("last_used_at"),
+        }))
+    }
+
+    pub async fn get_by_owner(pool: &PgPool, owner: Uuid) -> Result<Vec<SshKeyModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM ssh_keys
+        WHERE owner = $1
+        ORDER BY created_at DESC
+        "#,
+        )
+        .bind(owner)
+        .fetch_all(pool)
+        .await?;
+        let keys = rows
+            .into_iter()
+            .map(|r| SshKeyModel {
+                uid: r.get("uid"),
+                owner: r.get("owner"),
+                title: r.get("title"),
+                fingerprint: r.get("fingerprint"),
+                content: r.get("content"),
+                created_at: r.get("created_at"),
+                last_used_at: r.get("last_used_at"),
+            })
+            .collect();
+        Ok(keys)
+    }
+
+    pub async fn touch(pool: &PgPool, uid: Uuid) -> Result<u64, Error> {
+        let last_used_at = Utc::now().naive_utc();
+        let result = sqlx::query(
+            r#"
+        UPDATE ssh_keys
+        SET last_used_at = $1
+        WHERE uid = $2
+        "#,
+        )
+        .bind(last_used_at)
+        .bind(uid)
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<SshKeyModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM ssh_keys
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| SshKeyModel {
+            uid: r.get("uid"),
+            owner: r.get("owner"),
+            title: r.get("title"),
+            fingerprint: r.get("fingerprint"),
+            content: r.get("content"),
+            created_at: r.get("created_at"),
+            last_used_at: r.get("last_used_at"),
+        }))
+    }
+}
@@ -0,0 +1,121 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct TeamModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub org_uid: Uuid,
+    pub name: String,
+    pub description: String,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl TeamModel {
+    pub async fn create(
+        pool: &PgPool,
+        org_uid: Uuid,
+        name: &str,
+        description: &str,
+    ) -> Result<TeamModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO team (uid, org_uid, name, description, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(org_uid)
+        .bind(name)
+        .bind(description)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(TeamModel {
+            uid: row.get("uid"),
+            org_uid: row.get("org_uid"),
+            name: row.get("name"),
+            description: row.get("description"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    pub async fn get_by_org_uid_and_name(
+        pool: &PgPool,
+        org_uid: Uuid,
+        name: &str,
+    ) -> Result<Option<TeamModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM team
+        WHERE org_uid = $1 AND name = $2
+        "#,
+        )
+        .bind(org_uid)
+        .bind(name)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| TeamModel {
+            uid: r.get("uid"),
+            org_uid: r.get("org_uid"),
+            name: r.get("name"),
+            description: r.get("description"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn get_by_org_uid(pool: &PgPool, org_uid: Uuid) -> Result<Vec<TeamModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM team
+        WHERE org_uid = $1
+        ORDER BY name ASC
+        "#,
+        )
+        .bind(org_uid)
+        .fetch_all(pool)
+        .await?;
+        let teams = rows
+            .into_iter()
+            .map(|r| TeamModel {
+                uid: r.get("uid"),
+                org_uid: r.get("org_uid"),
+                name: r.get("name"),
+                description: r.get("description"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(teams)
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<TeamModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM team
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| TeamModel {
+            uid: r.get("uid"),
+            org_uid: r.get("org_uid"),
+            name: r.get("name"),
+            description: r.get("description"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct TeamMemberModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub team_uid: Uuid,
+    pub user_uid: Uuid,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl TeamMemberModel {
+    pub async fn create(pool: &PgPool, team_uid: Uuid, user_uid: Uuid) -> Result<TeamMemberModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO team_member (uid, team_uid, user_uid, created_at)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(team_uid)
+        .bind(user_uid)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(TeamMemberModel {
+            uid: row.get("uid"),
+            team_uid: row.get("team_uid"),
+            user_uid: row.get("user_uid"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    pub async fn get_by_team_uid_and_user_uid(
+        pool: &PgPool,
+        team_uid: Uuid,
+        user_uid: Uuid,
+    ) -> Result<Option<TeamMemberModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM team_member
+        WHERE team_uid = $1 AND user_uid = $2
+        "#,
+        )
+        .bind(team_uid)
+        .bind(user_uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| TeamMemberModel {
+            uid: r.get("uid"),
+            team_uid: r.get("team_uid"),
+            user_uid: r.get("user_uid"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    pub async fn get_by_team_uid(pool: &PgPool, team_uid: Uuid) -> Result<Vec<TeamMemberModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM team_member
+        WHERE team_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(team_uid)
+        .fetch_all(pool)
+        .await?;
+        let members = rows
+            .into_iter()
+            .map(|r| TeamMemberModel {
+                uid: r.get("uid"),
+                team_uid: r.get("team_uid"),
+                user_uid: r.get("user_uid"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(members)
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<TeamMemberModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM team_member
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| TeamMemberModel {
+            uid: r.get("uid"),
+            team_uid: r.get("team_uid"),
+            user_uid: r.get("user_uid"),
+            created_at: r.get("created_at"),
+        }))
+    }
+}
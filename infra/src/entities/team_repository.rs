@@ -0,0 +1,138 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+/// A role granted to every member of a team on one of the organization's repositories.
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct TeamRepositoryModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub team_uid: Uuid,
+    pub repo_uid: Uuid,
+    pub role: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+impl TeamRepositoryModel {
+    /// Grants `role` to the team, replacing any role it already had on the repository.
+    pub async fn upsert(
+        pool: &PgPool,
+        team_uid: Uuid,
+        repo_uid: Uuid,
+        role: &str,
+    ) -> Result<TeamRepositoryModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        INSERT INTO team_repository (uid, team_uid, repo_uid, role, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (team_uid, repo_uid) DO UPDATE SET role = EXCLUDED.role
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .bind(team_uid)
+        .bind(repo_uid)
+        .bind(role)
+        .bind(now)
+        .fetch_one(pool)
+        .await?;
+        Ok(TeamRepositoryModel {
+            uid: row.get("uid"),
+            team_uid: row.get("team_uid"),
+            repo_uid: row.get("repo_uid"),
+            role: row.get("role"),
+            created_at: row.get("created_at"),
+        })
+    }
+
+    pub async fn get_by_team_uid_and_repo_uid(
+        pool: &PgPool,
+        team_uid: Uuid,
+        repo_uid: Uuid,
+    ) -> Result<Option<TeamRepositoryModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM team_repository
+        WHERE team_uid = $1 AND repo_uid = $2
+        "#,
+        )
+        .bind(team_uid)
+        .bind(repo_uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| TeamRepositoryModel {
+            uid: r.get("uid"),
+            team_uid: r.get("team_uid"),
+            repo_uid: r.get("repo_uid"),
+            role: r.get("role"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
+    pub async fn get_by_team_uid(pool: &PgPool, team_uid: Uuid) -> Result<Vec<TeamRepositoryModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM team_repository
+        WHERE team_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(team_uid)
+        .fetch_all(pool)
+        .await?;
+        let repos = rows
+            .into_iter()
+            .map(|r| TeamRepositoryModel {
+                uid: r.get("uid"),
+                team_uid: r.get("team_uid"),
+                repo_uid: r.get("repo_uid"),
+                role: r.get("role"),
+                created_at: r.get("created_at"),
+            })
+            .collect();
+        Ok(repos)
+    }
+
+    /// The roles granted on `repo_uid` through every team `user_uid` belongs to.
+    pub async fn get_roles_by_repo_uid_and_user_uid(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        user_uid: Uuid,
+    ) -> Result<Vec<String>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT tr.role FROM team_repository tr
+        JOIN team_member tm ON tm.team_uid = tr.team_uid
+        WHERE tr.repo_uid = $1 AND tm.user_uid = $2
+        "#,
+        )
+        .bind(repo_uid)
+        .bind(user_uid)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().map(|r| r.get("role")).collect())
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<Option<TeamRepositoryModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        DELETE FROM team_repository
+        WHERE uid = $1
+        RETURNING *
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| TeamRepositoryModel {
+            uid: r.get("uid"),
+            team_uid: r.get("team_uid"),
+            repo_uid: r.get("repo_uid"),
+            role: r.get("role"),
+            created_at: r.get("created_at"),
+        }))
+    }
+}
@@ -0,0 +1,177 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+pub const WEBHOOK_CONTENT_JSON: &str = "json";
+pub const WEBHOOK_CONTENT_FORM: &str = "form";
+
+/// A URL notified of the events of a repository.
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct WebhookModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub url: String,
+    /// Key of the `X-Jzfs-Signature-256` HMAC; empty leaves deliveries unsigned.
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// `json` or `form`.
+    pub content_type: String,
+    /// Event names, or `*` for every event.
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+impl WebhookModel {
+    pub fn subscribes(&self, event: &str) -> bool {
+        self.events.iter().any(|x| x == "*" || x == event)
+    }
+
+    pub async fn create(
+        pool: &PgPool,
+        repo_uid: Uuid,
+        url: &str,
+        secret: &str,
+        content_type: &str,
+        events: &[String],
+        active: bool,
+    ) -> Result<WebhookModel, Error> {
+        let uid = Uuid::new_v4();
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO webhook (uid, repo_uid, url, secret, content_type, events, active, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)
+        "#,
+        )
+        .bind(uid)
+        .bind(repo_uid)
+        .bind(url)
+        .bind(secret)
+        .bind(content_type)
+        .bind(events)
+        .bind(active)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(WebhookModel {
+            uid,
+            repo_uid,
+            url: url.to_string(),
+            secret: secret.to_string(),
+            content_type: content_type.to_string(),
+            events: events.to_vec(),
+            active,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<WebhookModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM webhook
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| WebhookModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            url: r.get("url"),
+            secret: r.get("secret"),
+            content_type: r.get("content_type"),
+            events: r.get("events"),
+            active: r.get("active"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn get_by_repo_uid(pool: &PgPool, repo_uid: Uuid) -> Result<Vec<WebhookModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM webhook
+        WHERE repo_uid = $1
+        ORDER BY created_at ASC
+        "#,
+        )
+        .bind(repo_uid)
+        .fetch_all(pool)
+        .await?;
+        let webhooks = rows
+            .into_iter()
+            .map(|r| WebhookModel {
+                uid: r.get("uid"),
+                repo_uid: r.get("repo_uid"),
+                url: r.get("url"),
+                secret: r.get("secret"),
+                content_type: r.get("content_type"),
+                events: r.get("events"),
+                active: r.get("active"),
+                created_at: r.get("created_at"),
+                updated_at: r.get("updated_at"),
+            })
+            .collect();
+        Ok(webhooks)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update(
+        pool: &PgPool,
+        uid: Uuid,
+        url: Option<&str>,
+        secret: Option<&str>,
+        content_type: Option<&str>,
+        events: Option<&[String]>,
+        active: Option<bool>,
+    ) -> Result<Option<WebhookModel>, Error> {
+        let now = Local::now().naive_local();
+        let row = sqlx::query(
+            r#"
+        UPDATE webhook
+        SET url = COALESCE($1, url),
+            secret = COALESCE($2, secret),
+            content_type = COALESCE($3, content_type),
+            events = COALESCE($4, events),
+            active = COALESCE($5, active),
+            updated_at = $6
+        WHERE uid = $7
+        RETURNING *
+        "#,
+        )
+        .bind(url)
+        .bind(secret)
+        .bind(content_type)
+        .bind(events)
+        .bind(active)
+        .bind(now)
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| WebhookModel {
+            uid: r.get("uid"),
+            repo_uid: r.get("repo_uid"),
+            url: r.get("url"),
+            secret: r.get("secret"),
+            content_type: r.get("content_type"),
+            events: r.get("events"),
+            active: r.get("active"),
+            created_at: r.get("created_at"),
+            updated_at: r.get("updated_at"),
+        }))
+    }
+
+    pub async fn delete(pool: &PgPool, uid: Uuid) -> Result<(), Error> {
+        sqlx::query("DELETE FROM webhook WHERE uid = $1")
+            .bind(uid)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::{Decode, Encode, Error, FromRow, PgPool, Row};
+use uuid::Uuid;
+
+/// One attempt to deliver an event to a webhook.
+#[derive(Deserialize, Serialize, Encode, Decode, FromRow, Clone, Debug)]
+pub struct WebhookDeliveryModel {
+    #[sqlx(primary_key)]
+    pub uid: Uuid,
+    pub webhook_uid: Uuid,
+    pub event: String,
+    /// The JSON document sent, before any form encoding.
+    pub payload: String,
+    pub redelivery: bool,
+    /// `None` when no response was received.
+    pub status_code: Option<i32>,
+    pub response_body: Option<String>,
+    /// Why the request could not be completed.
+    pub error: Option<String>,
+    pub duration_ms: i64,
+    pub delivered_at: chrono::NaiveDateTime,
+}
+
+impl WebhookDeliveryModel {
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_some_and(|x| (200..300).contains(&x))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        pool: &PgPool,
+        uid: Uuid,
+        webhook_uid: Uuid,
+        event: &str,
+        payload: &str,
+        redelivery: bool,
+        status_code: Option<i32>,
+        response_body: Option<&str>,
+        error: Option<&str>,
+        duration_ms: i64,
+    ) -> Result<WebhookDeliveryModel, Error> {
+        let now = Local::now().naive_local();
+        sqlx::query(
+            r#"
+        INSERT INTO webhook_delivery (uid, webhook_uid, event, payload, redelivery, status_code, response_body, error, duration_ms, delivered_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "#,
+        )
+        .bind(uid)
+        .bind(webhook_uid)
+        .bind(event)
+        .bind(payload)
+        .bind(redelivery)
+        .bind(status_code)
+        .bind(response_body)
+        .bind(error)
+        .bind(duration_ms)
+        .bind(now)
+        .execute(pool)
+        .await?;
+        Ok(WebhookDeliveryModel {
+            uid,
+            webhook_uid,
+            event: event.to_string(),
+            payload: payload.to_string(),
+            redelivery,
+            status_code,
+            response_body: response_body.map(str::to_string),
+            error: error.map(str::to_string),
+            duration_ms,
+            delivered_at: now,
+        })
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: Uuid) -> Result<Option<WebhookDeliveryModel>, Error> {
+        let row = sqlx::query(
+            r#"
+        SELECT * FROM webhook_delivery
+        WHERE uid = $1
+        "#,
+        )
+        .bind(uid)
+        .fetch_optional(pool)
+        .await?;
+        Ok(row.map(|r| WebhookDeliveryModel {
+            uid: r.get("uid"),
+            webhook_uid: r.get("webhook_uid"),
+            event: r.get("event"),
+            payload: r.get("payload"),
+            redelivery: r.get("redelivery"),
+            status_code: r.get("status_code"),
+            response_body: r.get("response_body"),
+            error: r.get("error"),
+            duration_ms: r.get("duration_ms"),
+            delivered_at: r.get("delivered_at"),
+        }))
+    }
+
+    /// Deliveries of a webhook, newest first.
+    pub async fn get_by_webhook_uid(
+        pool: &PgPool,
+        webhook_uid: Uuid,
+        limit: i32,
+        offset: i32,
+    ) -> Result<Vec<WebhookDeliveryModel>, Error> {
+        let rows = sqlx::query(
+            r#"
+        SELECT * FROM webhook_delivery
+        WHERE webhook_uid = $1
+        ORDER BY delivered_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+        )
+        .bind(webhook_uid)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+        let deliveries = rows
+            .into_iter()
+            .map(|r| WebhookDeliveryModel {
+                uid: r.get("uid"),
+                webhook_uid: r.get("webhook_uid"),
+                event: r.get("event"),
+                payload: r.get("payload"),
+                redelivery: r.get("redelivery"),
+                status_code: r.get("status_code"),
+                response_body: r.get("response_body"),
+                error: r.get("error"),
+                duration_ms: r.get("duration_ms"),
+                delivered_at: r.get("delivered_at"),
+            })
+            .collect();
+        Ok(deliveries)
+    }
+}
@@ -0,0 +1,104 @@
+use crate::entities::collaborator::CollaboratorModel;
+use crate::entities::organization_member::OrganizationMemberModel;
+use crate::entities::repository::RepositoryModel;
+use crate::entities::team_repository::TeamRepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::App;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a user may do with a repository; each level includes the ones below it.
+/// Every level but `None` doubles as a collaborator role.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessLevel {
+    None,
+    Read,
+    Triage,
+    Write,
+    Maintain,
+    Admin,
+}
+
+impl AccessLevel {
+    pub fn from_role(role: &str) -> Option<AccessLevel> {
+        match role {
+            "read" => Some(AccessLevel::Read),
+            "triage" => Some(AccessLevel::Triage),
+            "write" => Some(AccessLevel::Write),
+            "maintain" => Some(AccessLevel::Maintain),
+            "admin" => Some(AccessLevel::Admin),
+            _ => None,
+        }
+    }
+
+    pub fn as_role(&self) -> &'static str {
+        match self {
+            AccessLevel::None => "none",
+            AccessLevel::Read => "read",
+            AccessLevel::Triage => "triage",
+            AccessLevel::Write => "write",
+            AccessLevel::Maintain => "maintain",
+            AccessLevel::Admin => "admin",
+        }
+    }
+}
+
+impl App {
+    /// Resolves the access level of `user` (`None` for anonymous) on `repo`.
+    pub async fn repository_access(&self, repo: &RepositoryModel, user: Option<Uuid>) -> AppResult<AccessLevel> {
+        let Some(user) = user else {
+            return Ok(if repo.is_public { AccessLevel::Read } else { AccessLevel::None });
+        };
+        if self.repository_owned_by(repo, user).await? {
+            return Ok(AccessLevel::Admin);
+        }
+        let role = CollaboratorModel::get_by_repo_uid_and_user_uid(&self.db, repo.uid, user)
+            .await?
+            .filter(|x| x.accepted)
+            .and_then(|x| AccessLevel::from_role(&x.role))
+            .unwrap_or(AccessLevel::None);
+        let team = TeamRepositoryModel::get_roles_by_repo_uid_and_user_uid(&self.db, repo.uid, user)
+            .await?
+            .iter()
+            .filter_map(|x| AccessLevel::from_role(x))
+            .max()
+            .unwrap_or(AccessLevel::None);
+        let public = if repo.is_public { AccessLevel::Read } else { AccessLevel::None };
+        Ok(role.max(team).max(public))
+    }
+
+    /// Whether `user` owns `repo`, either directly or as an owner of the
+    /// organization the repository belongs to.
+    pub async fn repository_owned_by(&self, repo: &RepositoryModel, user: Uuid) -> AppResult<bool> {
+        if user == repo.owner {
+            return Ok(true);
+        }
+        Ok(OrganizationMemberModel::get_by_org_uid_and_user_uid(&self.db, repo.owner, user)
+            .await?
+            .is_some_and(|x| x.is_owner()))
+    }
+
+    /// Fails unless `user` holds at least `level` on `repo`. Anonymous callers
+    /// get `AppError::UnAuth` so transports can ask for credentials, and users
+    /// who cannot see a private repository are told it does not exist.
+    pub async fn repository_authorize(&self, repo: &RepositoryModel, user: Option<Uuid>, level: AccessLevel) -> AppResult<AccessLevel> {
+        let access = self.repository_access(repo, user).await?;
+        if access >= level {
+            return Ok(access);
+        }
+        match (user, access) {
+            (None, _) => Err(AppError::UnAuth),
+            (Some(_), AccessLevel::None) => Err(AppError::Custom("Repository not found".to_string())),
+            (Some(_), _) => Err(AppError::Custom("Permission denied".to_string())),
+        }
+    }
+
+    /// Looks a repository up by owner and name and authorizes `user` on it.
+    pub async fn repository_authorized(&self, user: Option<Uuid>, repo: String, owner: String, level: AccessLevel) -> AppResult<RepositoryModel> {
+        let repo = RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, repo).await?
+            .ok_or(anyhow::anyhow!("Repository not found"))?;
+        self.repository_authorize(&repo, user, level).await?;
+        Ok(repo)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::entities::repository::RepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use git::archive::GitArchiveFormat;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Size of the chunks an archive is sent in.
+const ARCHIVE_CHUNK: usize = 64 * 1024;
+/// Cached archives not generated within this long are removed by the
+/// repository's gc.
+const ARCHIVE_CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryArchiveParam {
+    /// Directory every path is put under; defaults to `<repo>-<ref>/`, and an
+    /// empty one puts the files at the top level.
+    pub prefix: Option<String>,
+}
+
+/// An archive on its way to the client.
+pub struct RepositoryArchive {
+    pub file_name: String,
+    pub content_type: &'static str,
+    /// The archive in chunks; an error ends it early.
+    pub body: mpsc::Receiver<std::io::Result<Vec<u8>>>,
+}
+
+/// Sends what is written to it to an archive's client, in chunks, while
+/// writing it to the cache file.
+struct ArchiveSink {
+    file: File,
+    tx: mpsc::Sender<std::io::Result<Vec<u8>>>,
+}
+
+impl Write for ArchiveSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.file.write_all(buf)?;
+        self.tx
+            .blocking_send(Ok(buf.to_vec()))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "client went away"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Checks a requested prefix and adds the trailing `/`.
+fn archive_prefix(prefix: &str) -> AppResult<String> {
+    let prefix = prefix.trim_matches('/');
+    if prefix.is_empty() {
+        return Ok(String::new());
+    }
+    if prefix.split('/').any(|x| x.is_empty() || x == "." || x == "..") || prefix.contains('\\') {
+        return Err(AppError::Custom(format!("Invalid archive prefix: {}", prefix)));
+    }
+    Ok(format!("{}/", prefix))
+}
+
+fn archive_cache_dir(repo: &RepositoryModel) -> PathBuf {
+    git::root_data().join("archives").join(repo.uid.to_string())
+}
+
+/// Streams a cached archive.
+fn archive_send_file(path: &Path, tx: mpsc::Sender<std::io::Result<Vec<u8>>>) {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) => {
+            tx.blocking_send(Err(e)).ok();
+            return;
+        }
+    };
+    let mut buffer = vec![0; ARCHIVE_CHUNK];
+    loop {
+        match file.read(&mut buffer) {
+            Ok(0) => return,
+            Ok(n) => {
+                if tx.blocking_send(Ok(buffer[..n].to_vec())).is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                tx.blocking_send(Err(e)).ok();
+                return;
+            }
+        }
+    }
+}
+
+impl App {
+    /// The archive of `rev` in the format named by the extension of `name`,
+    /// such as `main.zip` or `v1.0.tar.gz`. Archives are cached by tree, so
+    /// every ref at the same commit shares one; a missing one is streamed as
+    /// it is generated and cached once complete.
+    pub async fn repository_archive(&self, user: Option<Uuid>, repo: String, owner: String, name: String, param: RepositoryArchiveParam) -> AppResult<RepositoryArchive> {
+        let Some((rev, format)) = GitArchiveFormat::split(&name) else {
+            return Err(AppError::Custom(format!("Unsupported archive: {}, expected .zip or .tar.gz", name)));
+        };
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let git = AppGit::new(repo.to_path());
+        let target = git.archive_target(rev)?;
+        let rev_label = rev
+            .chars()
+            .map(|x| if x.is_ascii_alphanumeric() || "._-".contains(x) { x } else { '-' })
+            .collect::<String>();
+        let label = format!("{}-{}", repo.name, rev_label);
+        let prefix = match param.prefix {
+            Some(prefix) => archive_prefix(&prefix)?,
+            None => format!("{}/", label),
+        };
+
+        let dir = archive_cache_dir(&repo);
+        std::fs::create_dir_all(&dir)?;
+        let prefix_hash = hex::encode(Sha256::digest(prefix.as_bytes()));
+        let path = dir.join(format!("{}-{}-{}.{}", target.tree, target.time, &prefix_hash[..16], format.extension()));
+        let (tx, rx) = mpsc::channel(16);
+        if path.exists() {
+            tokio::task::spawn_blocking(move || archive_send_file(&path, tx));
+        } else {
+            let partial = dir.join(format!(".{}.partial", Uuid::new_v4()));
+            let sink = ArchiveSink {
+                file: File::create(&partial)?,
+                tx: tx.clone(),
+            };
+            tokio::task::spawn_blocking(move || {
+                let out = BufWriter::with_capacity(ARCHIVE_CHUNK, sink);
+                match git.archive(&target, format, &prefix, out) {
+                    Ok(()) => {
+                        std::fs::rename(&partial, &path).ok();
+                    }
+                    Err(e) => {
+                        std::fs::remove_file(&partial).ok();
+                        tx.blocking_send(Err(std::io::Error::other(e.to_string()))).ok();
+                    }
+                }
+            });
+        }
+        Ok(RepositoryArchive {
+            file_name: format!("{}.{}", label, format.extension()),
+            content_type: format.content_type(),
+            body: rx,
+        })
+    }
+
+    /// Removes the cached archives of `repo` not generated recently.
+    pub(crate) fn archive_cache_prune(&self, repo: &RepositoryModel) -> AppResult<()> {
+        let dir = archive_cache_dir(repo);
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let modified = entry.metadata()?.modified()?;
+            if SystemTime::now().duration_since(modified).unwrap_or_default() > ARCHIVE_CACHE_TTL {
+                std::fs::remove_file(entry.path()).ok();
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use crate::App;
+use crate::entities::organization::OrganizationModel;
 use crate::entities::users::UsersModel;
 use crate::error::{AppError, AppResult};
+use crate::service::token::{AuthCredential, TOKEN_PREFIX};
 use crate::types::session::AuthSession;
 use serde::{Deserialize, Serialize};
 use sha256::Sha256Digest;
@@ -43,6 +45,9 @@ impl App {
         if UsersModel::get_by_username(&self.db, &param.username)
             .await?
             .is_some()
+            || OrganizationModel::get_by_name(&self.db, &param.username)
+            .await?
+            .is_some()
         {
             return Err(AppError::Custom("Username already exists".to_string()));
         }
@@ -50,4 +55,17 @@ impl App {
             UsersModel::create(&self.db, &param.username, &param.password, &param.email).await?;
         Ok(AuthSession::from(models))
     }
+
+    /// Authenticates the credentials of an `Authorization: Basic` header, where
+    /// the password may also be a personal access token. Any failure is
+    /// reported as `AppError::UnAuth` so the caller can challenge again.
+    pub async fn service_auth_basic(&self, username: String, password: String) -> AppResult<AuthCredential> {
+        if password.starts_with(TOKEN_PREFIX) {
+            return self.token_auth(&password).await;
+        }
+        let session = self.service_auth_login(AuthLoginParam { username, password })
+            .await
+            .map_err(|_| AppError::UnAuth)?;
+        Ok(AuthCredential { session, scopes: None })
+    }
 }
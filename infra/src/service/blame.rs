@@ -0,0 +1,15 @@
+use git::AppGit;
+use git::blame::{GitBlameParam, GitBlameResult};
+use crate::App;
+use crate::entities::repository::RepositoryModel;
+
+impl App {
+    pub async fn blame(&self, repo: RepositoryModel, path: &str, branch: Option<String>, commit: Option<String>) -> anyhow::Result<GitBlameResult> {
+        let git = AppGit::new(repo.to_path());
+        git.blame(GitBlameParam {
+            path: path.to_string(),
+            branch,
+            commit,
+        })
+    }
+}
@@ -1,7 +1,84 @@
+use crate::entities::git_branch::GitBranchModel;
+use crate::entities::git_commit::GitCommitModel;
+use crate::error::AppResult;
+use crate::service::access::AccessLevel;
 use crate::App;
+use git::branch::create::GitBranchCreateParam;
+use git::branch::list::GitBranchListResult;
+use git::receive::GitRefUpdate;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryBranchCreateParam {
+    pub name: String,
+    pub from: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryBranchRenameParam {
+    pub name: String,
+    pub new_name: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryBranchDefaultParam {
+    pub name: String,
+}
 
 impl App {
-    // pub async fn service_repo_git_branch_list(&self, repo_uid: Uuid) -> AppResult<Vec<GitBranchModel>> {
-    //     return self.repo_git_branch_list(repo_uid).await;
-    // }
-}
\ No newline at end of file
+    pub async fn repository_branch_create(&self, user: Uuid, repo: String, owner: String, param: RepositoryBranchCreateParam) -> AppResult<GitBranchListResult> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let git = AppGit::new(repo.to_path());
+        let branch = git.branch_create(GitBranchCreateParam {
+            name: param.name,
+            from: param.from,
+        })?;
+        let update = GitRefUpdate::new(format!("refs/heads/{}", branch.name), None, Some(branch.head.as_str()));
+        self.sync_refs(&repo, Some(user), &[update]).await?;
+        Ok(branch)
+    }
+
+    pub async fn repository_branch_delete(&self, user: Uuid, repo: String, owner: String, name: String) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        self.protected_branch_deletable(&repo, &name).await?;
+        let git = AppGit::new(repo.to_path());
+        let head = git.branch_list()?.into_iter().find(|x| x.name == name).map(|x| x.head);
+        git.branch_delete(&name)?;
+        if let Some(head) = head {
+            let update = GitRefUpdate::new(format!("refs/heads/{}", name), Some(head.as_str()), None);
+            self.sync_refs(&repo, Some(user), &[update]).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn repository_branch_rename(&self, user: Uuid, repo: String, owner: String, param: RepositoryBranchRenameParam) -> AppResult<GitBranchListResult> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        self.protected_branch_deletable(&repo, &param.name).await?;
+        let git = AppGit::new(repo.to_path());
+        let branch = git.branch_rename(&param.name, &param.new_name)?;
+        match GitBranchModel::get_by_repo_uid_and_name(&self.db, repo.uid, &param.name).await? {
+            Some(model) => {
+                GitBranchModel::update(&self.db, model.uid, Some(&branch.name), Some(&branch.head)).await?;
+                GitCommitModel::update_branch_name(&self.db, model.uid, &branch.name).await?;
+            }
+            None => {
+                GitBranchModel::create(&self.db, repo.uid, &branch.name, &branch.head).await?;
+            }
+        }
+        let updates = [
+            GitRefUpdate::new(format!("refs/heads/{}", param.name), Some(branch.head.as_str()), None),
+            GitRefUpdate::new(format!("refs/heads/{}", branch.name), None, Some(branch.head.as_str())),
+        ];
+        self.webhook_refs(&repo, &git, Some(user), &updates).await;
+        Ok(branch)
+    }
+
+    pub async fn repository_branch_set_default(&self, user: Uuid, repo: String, owner: String, param: RepositoryBranchDefaultParam) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let git = AppGit::new(repo.to_path());
+        git.branch_set_default(&param.name)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,160 @@
+use crate::entities::collaborator::CollaboratorModel;
+use crate::entities::repository::RepositoryModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CollaboratorInviteParam {
+    pub username: String,
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CollaboratorUpdateParam {
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryCollaborator {
+    pub uid: Uuid,
+    pub user_uid: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub accepted: bool,
+    pub created_at: NaiveDateTime,
+    pub accepted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CollaboratorInvitation {
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub repo_name: String,
+    pub owner_name: String,
+    pub role: String,
+    pub invited_by: String,
+    pub created_at: NaiveDateTime,
+}
+
+pub(crate) fn collaborator_role(role: &str) -> AppResult<AccessLevel> {
+    AccessLevel::from_role(role).ok_or(AppError::Custom(format!(
+        "Unknown role: {}, expected one of read, triage, write, maintain, admin",
+        role
+    )))
+}
+
+impl App {
+    async fn collaborator_find(&self, repo: &RepositoryModel, username: &str) -> AppResult<(UsersModel, CollaboratorModel)> {
+        let Some(user) = UsersModel::get_by_username(&self.db, username).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        let Some(collaborator) = CollaboratorModel::get_by_repo_uid_and_user_uid(&self.db, repo.uid, user.uid).await? else {
+            return Err(AppError::Custom("Collaborator not found".to_string()));
+        };
+        Ok((user, collaborator))
+    }
+
+    pub async fn repository_collaborators(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<Vec<RepositoryCollaborator>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Write).await?;
+        let mut result = vec![];
+        for collaborator in CollaboratorModel::get_by_repo_uid(&self.db, repo.uid).await? {
+            let Some(user) = UsersModel::get_by_uid(&self.db, collaborator.user_uid).await? else {
+                continue;
+            };
+            result.push(RepositoryCollaborator {
+                uid: collaborator.uid,
+                user_uid: user.uid,
+                username: user.username,
+                email: user.email,
+                role: collaborator.role,
+                accepted: collaborator.accepted,
+                created_at: collaborator.created_at,
+                accepted_at: collaborator.accepted_at,
+            });
+        }
+        Ok(result)
+    }
+
+    pub async fn repository_collaborator_invite(&self, user: Uuid, repo: String, owner: String, param: CollaboratorInviteParam) -> AppResult<CollaboratorModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let role = collaborator_role(&param.role)?;
+        let Some(invitee) = UsersModel::get_by_username(&self.db, &param.username).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        if invitee.uid == repo.owner {
+            return Err(AppError::Custom("The owner cannot be a collaborator".to_string()));
+        }
+        if CollaboratorModel::get_by_repo_uid_and_user_uid(&self.db, repo.uid, invitee.uid).await?.is_some() {
+            return Err(AppError::Custom("User is already a collaborator or invited".to_string()));
+        }
+        Ok(CollaboratorModel::create(&self.db, repo.uid, invitee.uid, role.as_role(), user).await?)
+    }
+
+    pub async fn repository_collaborator_update(&self, user: Uuid, repo: String, owner: String, username: String, param: CollaboratorUpdateParam) -> AppResult<CollaboratorModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let role = collaborator_role(&param.role)?;
+        let (_, collaborator) = self.collaborator_find(&repo, &username).await?;
+        CollaboratorModel::update_role(&self.db, collaborator.uid, role.as_role()).await?
+            .ok_or(AppError::Custom("Collaborator not found".to_string()))
+    }
+
+    /// Removes a collaborator or withdraws an invitation; collaborators may also remove themselves.
+    pub async fn repository_collaborator_remove(&self, user: Uuid, repo: String, owner: String, username: String) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let (target, collaborator) = self.collaborator_find(&repo, &username).await?;
+        if target.uid != user {
+            self.repository_authorize(&repo, Some(user), AccessLevel::Admin).await?;
+        }
+        CollaboratorModel::delete(&self.db, collaborator.uid).await?;
+        Ok(())
+    }
+
+    pub async fn collaborator_invitations(&self, user: Uuid) -> AppResult<Vec<CollaboratorInvitation>> {
+        let mut result = vec![];
+        for invitation in CollaboratorModel::get_pending_by_user_uid(&self.db, user).await? {
+            let Some(repo) = RepositoryModel::get_by_uid(&self.db, invitation.repo_uid).await? else {
+                continue;
+            };
+            let owner_name = self.repository_owner_name(&repo).await?;
+            let invited_by = UsersModel::get_by_uid(&self.db, invitation.invited_by).await?
+                .map(|x| x.username)
+                .unwrap_or_default();
+            result.push(CollaboratorInvitation {
+                uid: invitation.uid,
+                repo_uid: repo.uid,
+                repo_name: repo.name,
+                owner_name,
+                role: invitation.role,
+                invited_by,
+                created_at: invitation.created_at,
+            });
+        }
+        Ok(result)
+    }
+
+    pub async fn collaborator_invitation_accept(&self, user: Uuid, uid: Uuid) -> AppResult<CollaboratorModel> {
+        match CollaboratorModel::get_by_uid(&self.db, uid).await? {
+            Some(invitation) if invitation.user_uid == user && !invitation.accepted => {
+                CollaboratorModel::accept(&self.db, uid).await?
+                    .ok_or(AppError::Custom("Invitation not found".to_string()))
+            }
+            _ => Err(AppError::Custom("Invitation not found".to_string())),
+        }
+    }
+
+    pub async fn collaborator_invitation_decline(&self, user: Uuid, uid: Uuid) -> AppResult<()> {
+        match CollaboratorModel::get_by_uid(&self.db, uid).await? {
+            Some(invitation) if invitation.user_uid == user && !invitation.accepted => {
+                CollaboratorModel::delete(&self.db, uid).await?;
+                Ok(())
+            }
+            _ => Err(AppError::Custom("Invitation not found".to_string())),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::Local;
+use git::commit::edit::{GitCommitEditParam, GitFileAction};
+use git::receive::policy::GitCommitPolicy;
+use git::receive::GitRefUpdate;
+use git::tree::msg_tree::GitTreeAuthors;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RepositoryContentEncoding {
+    #[default]
+    Text,
+    Base64,
+}
+
+/// A change to one file, as [`GitFileAction`] with the content encoded as
+/// given by [`RepositoryContentsParam::encoding`].
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum RepositoryFileAction {
+    Create {
+        path: String,
+        content: String,
+        #[serde(default)]
+        executable: bool,
+    },
+    Update {
+        path: String,
+        content: String,
+        #[serde(default)]
+        executable: Option<bool>,
+    },
+    Delete { path: String },
+    Move {
+        from: String,
+        path: String,
+        #[serde(default)]
+        content: Option<String>,
+    },
+    Chmod { path: String, executable: bool },
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryContentsParam {
+    pub branch: String,
+    /// The commit the branch is at as the client last saw it; required
+    /// unless the branch is being created as a root commit.
+    pub parent: Option<String>,
+    pub message: String,
+    #[serde(default)]
+    pub encoding: RepositoryContentEncoding,
+    pub actions: Vec<RepositoryFileAction>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryContentsResult {
+    pub sha: String,
+}
+
+fn decode(encoding: RepositoryContentEncoding, content: String) -> AppResult<Vec<u8>> {
+    match encoding {
+        RepositoryContentEncoding::Text => Ok(content.into_bytes()),
+        RepositoryContentEncoding::Base64 => STANDARD
+            .decode(content.as_bytes())
+            .map_err(|e| AppError::Custom(format!("Invalid base64 content: {}", e))),
+    }
+}
+
+impl RepositoryFileAction {
+    fn into_git(self, encoding: RepositoryContentEncoding) -> AppResult<GitFileAction> {
+        Ok(match self {
+            RepositoryFileAction::Create { path, content, executable } => GitFileAction::Create {
+                path,
+                content: decode(encoding, content)?,
+                executable,
+            },
+            RepositoryFileAction::Update { path, content, executable } => GitFileAction::Update {
+                path,
+                content: decode(encoding, content)?,
+                executable,
+            },
+            RepositoryFileAction::Delete { path } => GitFileAction::Delete { path },
+            RepositoryFileAction::Move { from, path, content } => GitFileAction::Move {
+                from,
+                path,
+                content: content.map(|x| decode(encoding, x)).transpose()?,
+            },
+            RepositoryFileAction::Chmod { path, executable } => GitFileAction::Chmod { path, executable },
+        })
+    }
+}
+
+impl App {
+    /// Commits a batch of file changes made in the browser onto a branch as
+    /// one commit by `user`. Fails, writing nothing, if the branch is no
+    /// longer at `parent`.
+    pub async fn repository_contents_commit(&self, user: Uuid, repo: String, owner: String, param: RepositoryContentsParam) -> AppResult<RepositoryContentsResult> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let Some(caller) = UsersModel::get_by_uid(&self.db, user).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        if param.message.trim().is_empty() {
+            return Err(AppError::Custom("Commit message is required".to_string()));
+        }
+        let actions = param
+            .actions
+            .into_iter()
+            .map(|x| x.into_git(param.encoding))
+            .collect::<AppResult<Vec<_>>>()?;
+
+        // The edit only ever fast-forwards or creates the branch, so only the
+        // push restrictions of a protection rule can refuse it. A root commit
+        // has no oid yet; the check never looks at `new` of a created branch.
+        let git = AppGit::new(repo.to_path());
+        let refname = format!("refs/heads/{}", param.branch);
+        let existed = git.branch_list()?.iter().any(|x| x.name == param.branch);
+        if existed && param.parent.is_none() {
+            return Err(AppError::Custom(format!("Branch {} exists, its head is required as parent", param.branch)));
+        }
+        let parent = param.parent.clone();
+        let check = GitRefUpdate {
+            old: if existed { parent.clone().unwrap_or_default() } else { GitRefUpdate::ZERO.to_string() },
+            new: parent.clone().unwrap_or_default(),
+            name: refname.clone(),
+        };
+        let violations = self.protected_branch_check(&repo, Some(user), &[check], &[]).await?;
+        if let Some(violation) = violations.first() {
+            return Err(AppError::Custom(violation.reason.clone()));
+        }
+
+        let now = Local::now().naive_local();
+        let signature = GitTreeAuthors {
+            name: caller.username.clone(),
+            email: caller.email.clone(),
+            time: now.and_utc().timestamp(),
+        };
+        // The same file size and message rules as a push, checked before the
+        // branch moves.
+        let policy = GitCommitPolicy::from_env();
+        let sha = git.commit_edit(
+            GitCommitEditParam {
+                branch: param.branch,
+                parent: param.parent,
+                message: param.message,
+                actions,
+                author: signature.clone(),
+                committer: signature,
+            },
+            &policy,
+        )?;
+        let old = parent.as_deref().filter(|_| existed);
+        let update = GitRefUpdate::new(refname, old, Some(sha.as_str()));
+        self.sync_refs(&repo, Some(user), &[update]).await?;
+        Ok(RepositoryContentsResult { sha })
+    }
+}
@@ -0,0 +1,28 @@
+use crate::error::AppResult;
+use crate::service::access::AccessLevel;
+use crate::App;
+use git::diff::commit::GitDiffCommitResult;
+use git::diff::compare::{GitDiffCompareParam, GitDiffCompareResult};
+use git::merge::mergeability::GitMergeability;
+use git::AppGit;
+use uuid::Uuid;
+
+impl App {
+    pub async fn repository_commit_diff(&self, user: Option<Uuid>, repo: String, owner: String, sha: String) -> AppResult<GitDiffCommitResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let git = AppGit::new(repo.to_path());
+        Ok(git.diff_commit(&sha)?)
+    }
+
+    pub async fn repository_compare(&self, user: Option<Uuid>, repo: String, owner: String, param: GitDiffCompareParam) -> AppResult<GitDiffCompareResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let git = AppGit::new(repo.to_path());
+        Ok(git.diff_compare(param)?)
+    }
+
+    pub async fn repository_mergeability(&self, user: Option<Uuid>, repo: String, owner: String, base: String, head: String) -> AppResult<GitMergeability> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let git = AppGit::new(repo.to_path());
+        Ok(git.mergeability(&base, &head)?)
+    }
+}
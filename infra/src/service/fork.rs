@@ -0,0 +1,152 @@
+use crate::entities::repository::RepositoryModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryForkParam {
+    /// Organization to fork into; defaults to the caller.
+    #[serde(default)]
+    pub owner: Option<String>,
+    /// Name of the fork; defaults to the name of the source repository.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryFork {
+    pub uid: Uuid,
+    pub name: String,
+    pub owner: Uuid,
+    pub owner_name: String,
+    pub description: String,
+    pub is_public: bool,
+    pub parent_uid: Option<Uuid>,
+    pub created_at: chrono::NaiveDateTime,
+    pub updated_at: chrono::NaiveDateTime,
+}
+
+/// Every repository sharing history with a repository: the root it was
+/// (transitively) forked from and all forks below that root the caller may
+/// see. `root` is left out when the caller cannot read it.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryForkNetwork {
+    pub root: Option<RepositoryFork>,
+    pub forks: Vec<RepositoryFork>,
+}
+
+/// Forks are chained through alternates, and git only follows a few levels of those.
+const FORK_DEPTH_LIMIT: usize = 4;
+
+impl App {
+    async fn repository_fork_entry(&self, repo: RepositoryModel) -> AppResult<RepositoryFork> {
+        let owner_name = self.repository_owner_name(&repo).await?;
+        Ok(RepositoryFork {
+            uid: repo.uid,
+            name: repo.name,
+            owner: repo.owner,
+            owner_name,
+            description: repo.description,
+            is_public: repo.is_public,
+            parent_uid: repo.parent_uid,
+            created_at: repo.created_at,
+            updated_at: repo.updated_at,
+        })
+    }
+
+    /// The repository `repo` was (transitively) forked from, or `repo` itself.
+    pub(crate) async fn repository_fork_root(&self, mut repo: RepositoryModel) -> AppResult<RepositoryModel> {
+        while let Some(parent) = repo.parent_uid {
+            match RepositoryModel::get_by_uid(&self.db, parent).await? {
+                Some(parent) => repo = parent,
+                None => break,
+            }
+        }
+        Ok(repo)
+    }
+
+    /// Forks a repository under the caller or one of their organizations. The
+    /// fork keeps the visibility of its source.
+    pub async fn repository_fork(&self, user: Uuid, repo: String, owner: String, param: RepositoryForkParam) -> AppResult<RepositoryModel> {
+        let source = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let Some(caller) = UsersModel::get_by_uid(&self.db, user).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        let mut depth = 0;
+        let mut parent = source.parent_uid;
+        while let Some(uid) = parent {
+            depth += 1;
+            parent = RepositoryModel::get_by_uid(&self.db, uid).await?.and_then(|x| x.parent_uid);
+        }
+        if depth >= FORK_DEPTH_LIMIT {
+            return Err(AppError::Custom("Fork of a fork is nested too deeply, fork its parent instead".to_string()));
+        }
+        let (owner_uid, owner_name) = self.repository_owner_resolve(&caller, param.owner.as_deref()).await?;
+        if owner_uid == source.owner && param.name.as_ref().is_none_or(|x| *x == source.name) {
+            return Err(AppError::Custom("Cannot fork a repository into its own owner".to_string()));
+        }
+        let name = param.name.unwrap_or(source.name.clone());
+        if RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner_name, name.clone())
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Custom("Repository already exists".to_string()));
+        }
+        let fork = RepositoryModel::create(
+            &self.db,
+            &name,
+            owner_uid,
+            &source.description,
+            source.is_public,
+            Some(source.uid),
+        ).await?;
+        if let Err(e) = AppGit::new(source.to_path()).fork(&AppGit::new(fork.to_path())) {
+            sqlx::query("DELETE FROM repository WHERE uid = $1")
+                .bind(fork.uid)
+                .execute(&self.db)
+                .await?;
+            return Err(e.into());
+        }
+        self.sync_hook(fork.clone()).await?;
+        Ok(fork)
+    }
+
+    /// Direct forks of a repository that the caller may read.
+    pub async fn repository_forks(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<Vec<RepositoryFork>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let mut result = vec![];
+        for fork in RepositoryModel::get_by_parent_uid(&self.db, repo.uid).await? {
+            if self.repository_access(&fork, user).await? >= AccessLevel::Read {
+                result.push(self.repository_fork_entry(fork).await?);
+            }
+        }
+        Ok(result)
+    }
+
+    pub async fn repository_fork_network(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<RepositoryForkNetwork> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let root = self.repository_fork_root(repo).await?;
+        let mut forks = vec![];
+        let mut queue = VecDeque::from([root.uid]);
+        while let Some(uid) = queue.pop_front() {
+            for fork in RepositoryModel::get_by_parent_uid(&self.db, uid).await? {
+                queue.push_back(fork.uid);
+                // hidden forks still link their visible children into the network
+                if self.repository_access(&fork, user).await? >= AccessLevel::Read {
+                    forks.push(self.repository_fork_entry(fork).await?);
+                }
+            }
+        }
+        let root = if self.repository_access(&root, user).await? >= AccessLevel::Read {
+            Some(self.repository_fork_entry(root).await?)
+        } else {
+            None
+        };
+        Ok(RepositoryForkNetwork { root, forks })
+    }
+}
@@ -0,0 +1,275 @@
+use crate::entities::issue::{IssueModel, IssueQuery, ISSUE_CLOSED, ISSUE_OPEN};
+use crate::entities::issue_assignee::IssueAssigneeModel;
+use crate::entities::issue_comment::IssueCommentModel;
+use crate::entities::issue_label::IssueLabelModel;
+use crate::entities::milestone::MilestoneModel;
+use crate::entities::repository::RepositoryModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::types::pager::QueryPager;
+use crate::App;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueCreateParam {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    /// Label names; setting labels, assignees or a milestone needs triage access.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Usernames.
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    /// Milestone title.
+    #[serde(default)]
+    pub milestone: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueUpdateParam {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    /// Replaces the labels of the issue.
+    pub labels: Option<Vec<String>>,
+    /// Replaces the assignees of the issue.
+    pub assignees: Option<Vec<String>>,
+    /// Milestone title; an empty string removes the milestone.
+    pub milestone: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueFilter {
+    /// `open`, `closed` or `all`.
+    pub state: Option<String>,
+    /// Label name.
+    pub label: Option<String>,
+    /// Username.
+    pub assignee: Option<String>,
+    /// Username.
+    pub author: Option<String>,
+    /// Milestone title.
+    pub milestone: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueDetail {
+    #[serde(flatten)]
+    pub issue: IssueModel,
+    pub labels: Vec<IssueLabelModel>,
+    /// Usernames.
+    pub assignees: Vec<String>,
+    pub milestone: Option<MilestoneModel>,
+    pub comments: i64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueListResult {
+    pub total: i64,
+    pub list: Vec<IssueDetail>,
+}
+
+impl App {
+    pub(crate) async fn issue_find(&self, repo: &RepositoryModel, number: i64) -> AppResult<IssueModel> {
+        IssueModel::get_by_repo_uid_and_number(&self.db, repo.uid, number).await?
+            .ok_or(AppError::Custom("Issue not found".to_string()))
+    }
+
+    /// Authors may edit, close and reopen their own issues; everybody else
+    /// needs at least triage access.
+    async fn issue_manageable(&self, repo: &RepositoryModel, issue: &IssueModel, user: Uuid) -> AppResult<()> {
+        if issue.author != user {
+            self.repository_authorize(repo, Some(user), AccessLevel::Triage).await?;
+        }
+        Ok(())
+    }
+
+    async fn issue_labels_resolve(&self, repo: &RepositoryModel, names: &[String]) -> AppResult<Vec<Uuid>> {
+        let mut labels = vec![];
+        for name in names {
+            let Some(label) = IssueLabelModel::get_by_repo_uid_and_name(&self.db, repo.uid, name).await? else {
+                return Err(AppError::Custom(format!("Label not found: {}", name)));
+            };
+            labels.push(label.uid);
+        }
+        Ok(labels)
+    }
+
+    /// Assignees must be able to read the repository.
+    async fn issue_assignees_resolve(&self, repo: &RepositoryModel, usernames: &[String]) -> AppResult<Vec<Uuid>> {
+        let mut assignees = vec![];
+        for username in usernames {
+            let Some(user) = UsersModel::get_by_username(&self.db, username).await? else {
+                return Err(AppError::Custom(format!("User not found: {}", username)));
+            };
+            if self.repository_access(repo, Some(user.uid)).await? < AccessLevel::Read {
+                return Err(AppError::Custom(format!("User cannot be assigned: {}", username)));
+            }
+            assignees.push(user.uid);
+        }
+        Ok(assignees)
+    }
+
+    async fn issue_milestone_resolve(&self, repo: &RepositoryModel, title: &str) -> AppResult<Option<Uuid>> {
+        if title.is_empty() {
+            return Ok(None);
+        }
+        let Some(milestone) = MilestoneModel::get_by_repo_uid_and_title(&self.db, repo.uid, title).await? else {
+            return Err(AppError::Custom(format!("Milestone not found: {}", title)));
+        };
+        Ok(Some(milestone.uid))
+    }
+
+    pub(crate) async fn issue_detail(&self, issue: IssueModel) -> AppResult<IssueDetail> {
+        let labels = IssueLabelModel::get_by_issue_uid(&self.db, issue.uid).await?;
+        let mut assignees = vec![];
+        for assignee in IssueAssigneeModel::get_by_issue_uid(&self.db, issue.uid).await? {
+            if let Some(user) = UsersModel::get_by_uid(&self.db, assignee.user_uid).await? {
+                assignees.push(user.username);
+            }
+        }
+        let milestone = match issue.milestone_uid {
+            Some(uid) => MilestoneModel::get_by_uid(&self.db, uid).await?,
+            None => None,
+        };
+        let comments = IssueCommentModel::count_by_issue_uid(&self.db, issue.uid).await?;
+        Ok(IssueDetail { issue, labels, assignees, milestone, comments })
+    }
+
+    pub async fn issue_create(&self, user: Uuid, repo: String, owner: String, param: IssueCreateParam) -> AppResult<IssueDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let title = param.title.trim();
+        if title.is_empty() {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        let milestone = param.milestone.as_deref().unwrap_or_default();
+        if !param.labels.is_empty() || !param.assignees.is_empty() || !milestone.is_empty() {
+            self.repository_authorize(&repo, Some(user), AccessLevel::Triage).await?;
+        }
+        let labels = self.issue_labels_resolve(&repo, &param.labels).await?;
+        let assignees = self.issue_assignees_resolve(&repo, &param.assignees).await?;
+        let milestone = self.issue_milestone_resolve(&repo, milestone).await?;
+        let issue = IssueModel::create(&self.db, repo.uid, title, &param.body, user, milestone).await?;
+        IssueLabelModel::replace_by_issue_uid(&self.db, issue.uid, &labels).await?;
+        IssueAssigneeModel::replace_by_issue_uid(&self.db, issue.uid, &assignees).await?;
+        let detail = self.issue_detail(issue).await?;
+        self.webhook_issue(&repo, Some(user), "opened", &detail).await;
+        Ok(detail)
+    }
+
+    /// Lists issues; a filter naming an unknown label, user or milestone matches nothing.
+    pub async fn issue_list(&self, user: Option<Uuid>, repo: String, owner: String, pager: QueryPager, filter: IssueFilter) -> AppResult<IssueListResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let empty = IssueListResult { total: 0, list: vec![] };
+        let mut query = IssueQuery {
+            state: filter.state.filter(|x| x != "all"),
+            ..Default::default()
+        };
+        if let Some(name) = filter.label {
+            match IssueLabelModel::get_by_repo_uid_and_name(&self.db, repo.uid, &name).await? {
+                Some(label) => query.label = Some(label.uid),
+                None => return Ok(empty),
+            }
+        }
+        if let Some(username) = filter.assignee {
+            match UsersModel::get_by_username(&self.db, &username).await? {
+                Some(assignee) => query.assignee = Some(assignee.uid),
+                None => return Ok(empty),
+            }
+        }
+        if let Some(username) = filter.author {
+            match UsersModel::get_by_username(&self.db, &username).await? {
+                Some(author) => query.author = Some(author.uid),
+                None => return Ok(empty),
+            }
+        }
+        if let Some(title) = filter.milestone {
+            match MilestoneModel::get_by_repo_uid_and_title(&self.db, repo.uid, &title).await? {
+                Some(milestone) => query.milestone = Some(milestone.uid),
+                None => return Ok(empty),
+            }
+        }
+        let total = IssueModel::count_by_repo_uid(&self.db, repo.uid, &query).await?;
+        let mut list = vec![];
+        for issue in IssueModel::get_by_repo_uid(&self.db, repo.uid, &query, pager.limit, pager.page * pager.limit).await? {
+            list.push(self.issue_detail(issue).await?);
+        }
+        Ok(IssueListResult { total, list })
+    }
+
+    pub async fn issue_get(&self, user: Option<Uuid>, repo: String, owner: String, number: i64) -> AppResult<IssueDetail> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        self.issue_detail(issue).await
+    }
+
+    pub async fn issue_update(&self, user: Uuid, repo: String, owner: String, number: i64, param: IssueUpdateParam) -> AppResult<IssueDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        self.issue_manageable(&repo, &issue, user).await?;
+        let title = param.title.as_deref().map(str::trim);
+        if title.is_some_and(|x| x.is_empty()) {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        if param.labels.is_some() || param.assignees.is_some() || param.milestone.is_some() {
+            self.repository_authorize(&repo, Some(user), AccessLevel::Triage).await?;
+        }
+        let labels = match &param.labels {
+            Some(names) => Some(self.issue_labels_resolve(&repo, names).await?),
+            None => None,
+        };
+        let assignees = match &param.assignees {
+            Some(usernames) => Some(self.issue_assignees_resolve(&repo, usernames).await?),
+            None => None,
+        };
+        let milestone = match &param.milestone {
+            Some(title) => Some(self.issue_milestone_resolve(&repo, title).await?),
+            None => None,
+        };
+        let issue = IssueModel::update(&self.db, issue.uid, title, param.body.as_deref()).await?
+            .ok_or(AppError::Custom("Issue not found".to_string()))?;
+        if let Some(labels) = labels {
+            IssueLabelModel::replace_by_issue_uid(&self.db, issue.uid, &labels).await?;
+        }
+        if let Some(assignees) = assignees {
+            IssueAssigneeModel::replace_by_issue_uid(&self.db, issue.uid, &assignees).await?;
+        }
+        if let Some(milestone) = milestone {
+            IssueModel::update_milestone(&self.db, issue.uid, milestone).await?;
+        }
+        let issue = self.issue_find(&repo, number).await?;
+        let detail = self.issue_detail(issue).await?;
+        self.webhook_issue(&repo, Some(user), "edited", &detail).await;
+        Ok(detail)
+    }
+
+    pub async fn issue_close(&self, user: Uuid, repo: String, owner: String, number: i64) -> AppResult<IssueDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        self.issue_manageable(&repo, &issue, user).await?;
+        if !issue.is_open() {
+            return Err(AppError::Custom("Issue is not open".to_string()));
+        }
+        let issue = IssueModel::update_state(&self.db, issue.uid, ISSUE_CLOSED).await?
+            .ok_or(AppError::Custom("Issue not found".to_string()))?;
+        let detail = self.issue_detail(issue).await?;
+        self.webhook_issue(&repo, Some(user), "closed", &detail).await;
+        Ok(detail)
+    }
+
+    pub async fn issue_reopen(&self, user: Uuid, repo: String, owner: String, number: i64) -> AppResult<IssueDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        self.issue_manageable(&repo, &issue, user).await?;
+        if issue.is_open() {
+            return Err(AppError::Custom("Issue is already open".to_string()));
+        }
+        let issue = IssueModel::update_state(&self.db, issue.uid, ISSUE_OPEN).await?
+            .ok_or(AppError::Custom("Issue not found".to_string()))?;
+        let detail = self.issue_detail(issue).await?;
+        self.webhook_issue(&repo, Some(user), "reopened", &detail).await;
+        Ok(detail)
+    }
+}
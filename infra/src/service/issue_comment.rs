@@ -0,0 +1,70 @@
+use crate::entities::issue_comment::IssueCommentModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::types::pager::QueryPager;
+use crate::App;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueCommentParam {
+    pub body: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct IssueCommentListResult {
+    pub total: i64,
+    pub list: Vec<IssueCommentModel>,
+}
+
+impl App {
+    async fn issue_comment_find(&self, issue_uid: Uuid, uid: Uuid) -> AppResult<IssueCommentModel> {
+        IssueCommentModel::get_by_uid(&self.db, uid).await?
+            .filter(|x| x.issue_uid == issue_uid)
+            .ok_or(AppError::Custom("Comment not found".to_string()))
+    }
+
+    pub async fn issue_comments(&self, user: Option<Uuid>, repo: String, owner: String, number: i64, pager: QueryPager) -> AppResult<IssueCommentListResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        let list = IssueCommentModel::get_by_issue_uid(&self.db, issue.uid, pager.limit, pager.page * pager.limit).await?;
+        let total = IssueCommentModel::count_by_issue_uid(&self.db, issue.uid).await?;
+        Ok(IssueCommentListResult { total, list })
+    }
+
+    pub async fn issue_comment_create(&self, user: Uuid, repo: String, owner: String, number: i64, param: IssueCommentParam) -> AppResult<IssueCommentModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        if param.body.trim().is_empty() {
+            return Err(AppError::Custom("Comment body is required".to_string()));
+        }
+        Ok(IssueCommentModel::create(&self.db, issue.uid, user, &param.body).await?)
+    }
+
+    /// Only the author may edit a comment.
+    pub async fn issue_comment_update(&self, user: Uuid, repo: String, owner: String, number: i64, uid: Uuid, param: IssueCommentParam) -> AppResult<IssueCommentModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        let comment = self.issue_comment_find(issue.uid, uid).await?;
+        if comment.author != user {
+            return Err(AppError::Custom("Permission denied".to_string()));
+        }
+        if param.body.trim().is_empty() {
+            return Err(AppError::Custom("Comment body is required".to_string()));
+        }
+        IssueCommentModel::update(&self.db, comment.uid, &param.body).await?
+            .ok_or(AppError::Custom("Comment not found".to_string()))
+    }
+
+    /// Authors may delete their own comments; everybody else needs triage access.
+    pub async fn issue_comment_delete(&self, user: Uuid, repo: String, owner: String, number: i64, uid: Uuid) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        let comment = self.issue_comment_find(issue.uid, uid).await?;
+        if comment.author != user {
+            self.repository_authorize(&repo, Some(user), AccessLevel::Triage).await?;
+        }
+        IssueCommentModel::delete(&self.db, comment.uid).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use crate::entities::job::{JobModel, JOB_DEAD, JOB_DONE, JOB_QUEUED, JOB_RUNNING};
+use crate::entities::repository::RepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::types::pager::QueryPager;
+use crate::App;
+use chrono::Local;
+use git::receive::GitRefUpdate;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tracing::error;
+use uuid::Uuid;
+
+/// How long an attempt may run before its job is handed to another worker.
+const JOB_VISIBILITY: Duration = Duration::from_secs(10 * 60);
+/// How long an idle worker waits before looking for work again.
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// How often expired locks and old finished jobs are cleaned up.
+const JOB_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+/// Finished jobs are kept this long.
+const JOB_RETENTION_DAYS: i64 = 7;
+/// The wait before the second attempt, doubled for each attempt after it.
+const JOB_BACKOFF_BASE: Duration = Duration::from_secs(30);
+const JOB_BACKOFF_MAX: Duration = Duration::from_secs(60 * 60);
+
+const JOB_STATES: [&str; 4] = [JOB_QUEUED, JOB_RUNNING, JOB_DONE, JOB_DEAD];
+
+/// Background work on a repository, stored as the `kind` and `payload` of a
+/// job.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
+pub enum Job {
+    /// Applies the ref updates of a push to the database.
+    SyncRefs {
+        sender: Option<Uuid>,
+        updates: Vec<GitRefUpdate>,
+    },
+    /// Sends one event to one webhook.
+    WebhookDelivery {
+        webhook_uid: Uuid,
+        event: String,
+        payload: String,
+    },
+    /// Packs loose objects when enough have piled up and drops stale cached
+    /// archives.
+    RepositoryGc,
+}
+
+impl Job {
+    fn max_attempts(&self) -> i32 {
+        match self {
+            Job::SyncRefs { .. } => 5,
+            Job::WebhookDelivery { .. } => 5,
+            Job::RepositoryGc => 3,
+        }
+    }
+
+    /// Jobs sharing a key run in the order they were queued: the syncs of a
+    /// repository, and the deliveries to one webhook, so a failing endpoint
+    /// only holds back its own.
+    fn ordering_key(&self, repo: &RepositoryModel) -> Option<String> {
+        match self {
+            Job::SyncRefs { .. } => Some(repo.uid.to_string()),
+            Job::WebhookDelivery { webhook_uid, .. } => Some(webhook_uid.to_string()),
+            Job::RepositoryGc => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct JobFilter {
+    /// `queued`, `running`, `done` or `dead`.
+    pub state: Option<String>,
+}
+
+/// The wait before retrying after attempt `attempt` failed.
+fn job_backoff(attempt: i32) -> Duration {
+    let exponent = attempt.clamp(1, 16) as u32 - 1;
+    JOB_BACKOFF_BASE.saturating_mul(1 << exponent).min(JOB_BACKOFF_MAX)
+}
+
+impl App {
+    /// Queues `job` on `repo` for the job workers.
+    pub async fn job_enqueue(&self, repo: &RepositoryModel, job: Job) -> AppResult<JobModel> {
+        let value = serde_json::to_value(&job)?;
+        let kind = value["kind"].as_str().unwrap_or_default();
+        let ordering_key = job.ordering_key(repo);
+        Ok(JobModel::create(&self.db, repo.uid, kind, &value["payload"], ordering_key.as_deref(), job.max_attempts()).await?)
+    }
+
+    /// Starts `count` workers running queued jobs, and the sweeper that kills
+    /// jobs abandoned on their last attempt and drops old finished ones.
+    pub fn job_workers(&self, count: usize) {
+        for _ in 0..count {
+            let app = self.clone();
+            tokio::spawn(async move {
+                loop {
+                    match app.job_run_next().await {
+                        Ok(true) => {}
+                        Ok(false) => tokio::time::sleep(JOB_POLL_INTERVAL).await,
+                        Err(e) => {
+                            error!("Job worker failed: {}", e);
+                            tokio::time::sleep(JOB_POLL_INTERVAL).await;
+                        }
+                    }
+                }
+            });
+        }
+        let app = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = app.job_sweep().await {
+                    error!("Job sweep failed: {}", e);
+                }
+                tokio::time::sleep(JOB_SWEEP_INTERVAL).await;
+            }
+        });
+    }
+
+    async fn job_sweep(&self) -> AppResult<()> {
+        JobModel::bury_expired(&self.db).await?;
+        let before = Local::now().naive_local() - chrono::Duration::days(JOB_RETENTION_DAYS);
+        JobModel::delete_done(&self.db, before).await?;
+        Ok(())
+    }
+
+    /// Runs the next job due, if any, and records its outcome. Returns whether
+    /// a job was run.
+    async fn job_run_next(&self) -> AppResult<bool> {
+        let locked_until = Local::now().naive_local() + JOB_VISIBILITY;
+        let Some(job) = JobModel::claim(&self.db, locked_until).await? else {
+            return Ok(false);
+        };
+        let result = match tokio::time::timeout(JOB_VISIBILITY, self.job_run(&job)).await {
+            Ok(result) => result,
+            Err(_) => Err(AppError::Custom("timed out".to_string())),
+        };
+        match result {
+            Ok(()) => {
+                JobModel::complete(&self.db, job.uid, job.attempts).await?;
+            }
+            Err(e) => {
+                let retry_at = (job.attempts < job.max_attempts)
+                    .then(|| Local::now().naive_local() + job_backoff(job.attempts));
+                JobModel::fail(&self.db, job.uid, job.attempts, &e.to_string(), retry_at).await?;
+            }
+        }
+        Ok(true)
+    }
+
+    async fn job_run(&self, model: &JobModel) -> AppResult<()> {
+        let job: Job = serde_json::from_value(json!({"kind": model.kind, "payload": model.payload}))?;
+        let Some(repo) = RepositoryModel::get_by_uid(&self.db, model.repo_uid).await? else {
+            return Ok(());
+        };
+        match job {
+            Job::SyncRefs { sender, updates } => self.sync_refs(&repo, sender, &updates).await,
+            Job::WebhookDelivery { webhook_uid, event, payload } => {
+                self.webhook_job(webhook_uid, &event, &payload, model.attempts > 1).await
+            }
+            Job::RepositoryGc => {
+                let forked = !RepositoryModel::get_by_parent_uid(&self.db, repo.uid).await?.is_empty();
+                AppGit::new(repo.to_path()).gc(forked)?;
+                self.archive_cache_prune(&repo)
+            }
+        }
+    }
+
+    /// Jobs of a repository, newest first, optionally only those in `state`.
+    pub async fn job_list(&self, user: Uuid, repo: String, owner: String, pager: QueryPager, filter: JobFilter) -> AppResult<Vec<JobModel>> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        if let Some(state) = filter.state.as_deref().filter(|x| !JOB_STATES.contains(x)) {
+            return Err(AppError::Custom(format!("Invalid job state: {}", state)));
+        }
+        Ok(JobModel::get_by_repo_uid(&self.db, repo.uid, filter.state.as_deref(), pager.limit, pager.page * pager.limit).await?)
+    }
+
+    /// Queues a dead job again with a fresh set of attempts.
+    pub async fn job_retry(&self, user: Uuid, repo: String, owner: String, uid: Uuid) -> AppResult<JobModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let job = JobModel::get_by_uid(&self.db, uid).await?
+            .filter(|x| x.repo_uid == repo.uid)
+            .ok_or(AppError::Custom("Job not found".to_string()))?;
+        if job.state != JOB_DEAD {
+            return Err(AppError::Custom(format!("Job is {}, only dead jobs can be retried", job.state)));
+        }
+        JobModel::retry(&self.db, job.uid).await?
+            .ok_or(AppError::Custom("Job is no longer dead".to_string()))
+    }
+}
@@ -0,0 +1,84 @@
+use crate::entities::issue_label::IssueLabelModel;
+use crate::entities::repository::RepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct LabelCreateParam {
+    pub name: String,
+    /// Hex color such as `#d73a4a`.
+    pub color: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct LabelUpdateParam {
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
+fn label_name(name: &str) -> AppResult<&str> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 50 {
+        return Err(AppError::Custom("Label name must be 1 to 50 characters".to_string()));
+    }
+    Ok(name)
+}
+
+fn label_color(color: &str) -> AppResult<&str> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|x| x.is_ascii_hexdigit());
+    if !valid {
+        return Err(AppError::Custom(format!("Invalid color: {}, expected #rrggbb", color)));
+    }
+    Ok(color)
+}
+
+impl App {
+    async fn label_find(&self, repo: &RepositoryModel, name: &str) -> AppResult<IssueLabelModel> {
+        IssueLabelModel::get_by_repo_uid_and_name(&self.db, repo.uid, name).await?
+            .ok_or(AppError::Custom(format!("Label not found: {}", name)))
+    }
+
+    pub async fn label_list(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<Vec<IssueLabelModel>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        Ok(IssueLabelModel::get_by_repo_uid(&self.db, repo.uid).await?)
+    }
+
+    pub async fn label_create(&self, user: Uuid, repo: String, owner: String, param: LabelCreateParam) -> AppResult<IssueLabelModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let name = label_name(&param.name)?;
+        let color = label_color(&param.color)?;
+        if IssueLabelModel::get_by_repo_uid_and_name(&self.db, repo.uid, name).await?.is_some() {
+            return Err(AppError::Custom(format!("Label already exists: {}", name)));
+        }
+        Ok(IssueLabelModel::create(&self.db, repo.uid, name, color, &param.description).await?)
+    }
+
+    pub async fn label_update(&self, user: Uuid, repo: String, owner: String, name: String, param: LabelUpdateParam) -> AppResult<IssueLabelModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let label = self.label_find(&repo, &name).await?;
+        let new_name = param.name.as_deref().map(label_name).transpose()?;
+        let color = param.color.as_deref().map(label_color).transpose()?;
+        if let Some(new_name) = new_name.filter(|x| *x != label.name)
+            && IssueLabelModel::get_by_repo_uid_and_name(&self.db, repo.uid, new_name).await?.is_some()
+        {
+            return Err(AppError::Custom(format!("Label already exists: {}", new_name)));
+        }
+        IssueLabelModel::update(&self.db, label.uid, new_name, color, param.description.as_deref()).await?
+            .ok_or(AppError::Custom(format!("Label not found: {}", name)))
+    }
+
+    pub async fn label_delete(&self, user: Uuid, repo: String, owner: String, name: String) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let label = self.label_find(&repo, &name).await?;
+        IssueLabelModel::delete(&self.db, label.uid).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::entities::issue::{IssueModel, IssueQuery, ISSUE_CLOSED, ISSUE_OPEN};
+use crate::entities::milestone::{MilestoneModel, MILESTONE_CLOSED, MILESTONE_OPEN};
+use crate::entities::repository::RepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MilestoneCreateParam {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// `YYYY-MM-DD`.
+    #[serde(default)]
+    pub due_on: Option<NaiveDate>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MilestoneUpdateParam {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// `open` or `closed`.
+    pub state: Option<String>,
+    /// `YYYY-MM-DD`; an empty string removes the due date.
+    pub due_on: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MilestoneFilter {
+    /// `open`, `closed` or `all`.
+    pub state: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MilestoneDetail {
+    #[serde(flatten)]
+    pub milestone: MilestoneModel,
+    pub open_issues: i64,
+    pub closed_issues: i64,
+}
+
+impl App {
+    async fn milestone_find(&self, repo: &RepositoryModel, uid: Uuid) -> AppResult<MilestoneModel> {
+        MilestoneModel::get_by_uid(&self.db, uid).await?
+            .filter(|x| x.repo_uid == repo.uid)
+            .ok_or(AppError::Custom("Milestone not found".to_string()))
+    }
+
+    async fn milestone_detail(&self, milestone: MilestoneModel) -> AppResult<MilestoneDetail> {
+        let mut query = IssueQuery {
+            state: Some(ISSUE_OPEN.to_string()),
+            milestone: Some(milestone.uid),
+            ..Default::default()
+        };
+        let open_issues = IssueModel::count_by_repo_uid(&self.db, milestone.repo_uid, &query).await?;
+        query.state = Some(ISSUE_CLOSED.to_string());
+        let closed_issues = IssueModel::count_by_repo_uid(&self.db, milestone.repo_uid, &query).await?;
+        Ok(MilestoneDetail { milestone, open_issues, closed_issues })
+    }
+
+    pub async fn milestone_list(&self, user: Option<Uuid>, repo: String, owner: String, filter: MilestoneFilter) -> AppResult<Vec<MilestoneDetail>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let state = filter.state.as_deref().filter(|x| *x != "all");
+        let mut result = vec![];
+        for milestone in MilestoneModel::get_by_repo_uid(&self.db, repo.uid, state).await? {
+            result.push(self.milestone_detail(milestone).await?);
+        }
+        Ok(result)
+    }
+
+    pub async fn milestone_create(&self, user: Uuid, repo: String, owner: String, param: MilestoneCreateParam) -> AppResult<MilestoneDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let title = param.title.trim();
+        if title.is_empty() {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        if MilestoneModel::get_by_repo_uid_and_title(&self.db, repo.uid, title).await?.is_some() {
+            return Err(AppError::Custom(format!("Milestone already exists: {}", title)));
+        }
+        let milestone = MilestoneModel::create(&self.db, repo.uid, title, &param.description, param.due_on).await?;
+        self.milestone_detail(milestone).await
+    }
+
+    pub async fn milestone_update(&self, user: Uuid, repo: String, owner: String, uid: Uuid, param: MilestoneUpdateParam) -> AppResult<MilestoneDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let milestone = self.milestone_find(&repo, uid).await?;
+        let title = param.title.as_deref().map(str::trim);
+        if title == Some("") {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        if let Some(title) = title.filter(|x| *x != milestone.title)
+            && MilestoneModel::get_by_repo_uid_and_title(&self.db, repo.uid, title).await?.is_some()
+        {
+            return Err(AppError::Custom(format!("Milestone already exists: {}", title)));
+        }
+        if let Some(state) = param.state.as_deref()
+            && state != MILESTONE_OPEN
+            && state != MILESTONE_CLOSED
+        {
+            return Err(AppError::Custom(format!("Invalid state: {}, expected open or closed", state)));
+        }
+        let due_on = match param.due_on.as_deref() {
+            None => milestone.due_on,
+            Some("") => None,
+            Some(date) => Some(
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| AppError::Custom(format!("Invalid date: {}, expected YYYY-MM-DD", date)))?,
+            ),
+        };
+        let milestone = MilestoneModel::update(
+            &self.db,
+            milestone.uid,
+            title,
+            param.description.as_deref(),
+            param.state.as_deref(),
+            due_on,
+        ).await?
+            .ok_or(AppError::Custom("Milestone not found".to_string()))?;
+        self.milestone_detail(milestone).await
+    }
+
+    /// Deleting a milestone keeps its issues and only clears their milestone.
+    pub async fn milestone_delete(&self, user: Uuid, repo: String, owner: String, uid: Uuid) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let milestone = self.milestone_find(&repo, uid).await?;
+        MilestoneModel::delete(&self.db, milestone.uid).await?;
+        Ok(())
+    }
+}
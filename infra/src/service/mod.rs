@@ -2,4 +2,27 @@ pub mod auth;
 pub mod repository;
 pub mod sync_hook;
 pub mod branch;
-pub mod cat_file;
\ No newline at end of file
+pub mod cat_file;
+pub mod diff;
+pub mod blame;
+pub mod tag;
+pub mod protected_branch;
+pub mod ssh_key;
+pub mod access;
+pub mod token;
+pub mod collaborator;
+pub mod organization;
+pub mod team;
+pub mod fork;
+pub mod pull_request;
+pub mod pull_merge;
+pub mod pull_review;
+pub mod issue;
+pub mod issue_comment;
+pub mod label;
+pub mod milestone;
+pub mod reference;
+pub mod webhook;
+pub mod job;
+pub mod archive;
+pub mod contents;
@@ -0,0 +1,179 @@
+use crate::entities::organization::OrganizationModel;
+use crate::entities::organization_member::{OrganizationMemberModel, ORG_ROLE_MEMBER, ORG_ROLE_OWNER};
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::App;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OrganizationCreateParam {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OrganizationUpdateParam {
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OrganizationMemberParam {
+    pub username: String,
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OrganizationMemberUpdateParam {
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct OrganizationMember {
+    pub uid: Uuid,
+    pub user_uid: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+fn org_role(role: &str) -> AppResult<&'static str> {
+    match role {
+        ORG_ROLE_OWNER => Ok(ORG_ROLE_OWNER),
+        ORG_ROLE_MEMBER => Ok(ORG_ROLE_MEMBER),
+        role => Err(AppError::Custom(format!(
+            "Unknown role: {}, expected one of owner, member",
+            role
+        ))),
+    }
+}
+
+impl App {
+    /// Looks an organization up by name and checks that `user` belongs to it,
+    /// as an owner when `owner` is set. Non-members are told it does not exist.
+    pub(crate) async fn org_authorized(&self, user: Uuid, org: &str, owner: bool) -> AppResult<(OrganizationModel, OrganizationMemberModel)> {
+        let Some(org) = OrganizationModel::get_by_name(&self.db, org).await? else {
+            return Err(AppError::Custom("Organization not found".to_string()));
+        };
+        let Some(member) = OrganizationMemberModel::get_by_org_uid_and_user_uid(&self.db, org.uid, user).await? else {
+            return Err(AppError::Custom("Organization not found".to_string()));
+        };
+        if owner && !member.is_owner() {
+            return Err(AppError::Custom("Permission denied".to_string()));
+        }
+        Ok((org, member))
+    }
+
+    async fn org_member_find(&self, org: &OrganizationModel, username: &str) -> AppResult<(UsersModel, OrganizationMemberModel)> {
+        let Some(user) = UsersModel::get_by_username(&self.db, username).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        let Some(member) = OrganizationMemberModel::get_by_org_uid_and_user_uid(&self.db, org.uid, user.uid).await? else {
+            return Err(AppError::Custom("Member not found".to_string()));
+        };
+        Ok((user, member))
+    }
+
+    /// Refuses changes that would leave an organization without an owner.
+    async fn org_keeps_owner(&self, org: &OrganizationModel, member: &OrganizationMemberModel) -> AppResult<()> {
+        if !member.is_owner() {
+            return Ok(());
+        }
+        let owners = OrganizationMemberModel::get_by_org_uid(&self.db, org.uid)
+            .await?
+            .iter()
+            .filter(|x| x.is_owner())
+            .count();
+        if owners <= 1 {
+            return Err(AppError::Custom("Organization must keep at least one owner".to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn org_create(&self, user: Uuid, param: OrganizationCreateParam) -> AppResult<OrganizationModel> {
+        let name = param.name.trim();
+        if name.is_empty() || name.contains('/') {
+            return Err(AppError::Custom("Invalid organization name".to_string()));
+        }
+        if UsersModel::get_by_username(&self.db, name).await?.is_some()
+            || OrganizationModel::get_by_name(&self.db, name).await?.is_some()
+        {
+            return Err(AppError::Custom("Name already exists".to_string()));
+        }
+        let org = OrganizationModel::create(&self.db, name, &param.description, user).await?;
+        OrganizationMemberModel::create(&self.db, org.uid, user, ORG_ROLE_OWNER).await?;
+        Ok(org)
+    }
+
+    pub async fn org_list(&self, user: Uuid) -> AppResult<Vec<OrganizationModel>> {
+        Ok(OrganizationModel::get_by_member(&self.db, user).await?)
+    }
+
+    pub async fn org_info(&self, org: String) -> AppResult<OrganizationModel> {
+        OrganizationModel::get_by_name(&self.db, &org).await?
+            .ok_or(AppError::Custom("Organization not found".to_string()))
+    }
+
+    pub async fn org_update(&self, user: Uuid, org: String, param: OrganizationUpdateParam) -> AppResult<OrganizationModel> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        OrganizationModel::update(&self.db, org.uid, param.description.as_deref()).await?
+            .ok_or(AppError::Custom("Organization not found".to_string()))
+    }
+
+    pub async fn org_members(&self, user: Uuid, org: String) -> AppResult<Vec<OrganizationMember>> {
+        let (org, _) = self.org_authorized(user, &org, false).await?;
+        let mut result = vec![];
+        for member in OrganizationMemberModel::get_by_org_uid(&self.db, org.uid).await? {
+            let Some(user) = UsersModel::get_by_uid(&self.db, member.user_uid).await? else {
+                continue;
+            };
+            result.push(OrganizationMember {
+                uid: member.uid,
+                user_uid: user.uid,
+                username: user.username,
+                email: user.email,
+                role: member.role,
+                created_at: member.created_at,
+            });
+        }
+        Ok(result)
+    }
+
+    pub async fn org_member_add(&self, user: Uuid, org: String, param: OrganizationMemberParam) -> AppResult<OrganizationMemberModel> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let role = org_role(&param.role)?;
+        let Some(target) = UsersModel::get_by_username(&self.db, &param.username).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        if OrganizationMemberModel::get_by_org_uid_and_user_uid(&self.db, org.uid, target.uid).await?.is_some() {
+            return Err(AppError::Custom("User is already a member".to_string()));
+        }
+        Ok(OrganizationMemberModel::create(&self.db, org.uid, target.uid, role).await?)
+    }
+
+    pub async fn org_member_update(&self, user: Uuid, org: String, username: String, param: OrganizationMemberUpdateParam) -> AppResult<OrganizationMemberModel> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let role = org_role(&param.role)?;
+        let (_, member) = self.org_member_find(&org, &username).await?;
+        if role != ORG_ROLE_OWNER {
+            self.org_keeps_owner(&org, &member).await?;
+        }
+        OrganizationMemberModel::update_role(&self.db, member.uid, role).await?
+            .ok_or(AppError::Custom("Member not found".to_string()))
+    }
+
+    /// Removes a member from the organization and its teams; members may also leave on their own.
+    pub async fn org_member_remove(&self, user: Uuid, org: String, username: String) -> AppResult<()> {
+        let (org, caller) = self.org_authorized(user, &org, false).await?;
+        let (target, member) = self.org_member_find(&org, &username).await?;
+        if target.uid != user && !caller.is_owner() {
+            return Err(AppError::Custom("Permission denied".to_string()));
+        }
+        self.org_keeps_owner(&org, &member).await?;
+        OrganizationMemberModel::delete(&self.db, member.uid).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use crate::entities::protected_branch::ProtectedBranchModel;
+use crate::entities::repository::RepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use git::receive::GitRefUpdate;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ProtectedBranchCreateParam {
+    pub pattern: String,
+    #[serde(default)]
+    pub allow_force_push: bool,
+    #[serde(default)]
+    pub allow_deletion: bool,
+    #[serde(default)]
+    pub push_users: Vec<Uuid>,
+    #[serde(default)]
+    pub push_roles: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ProtectedBranchUpdateParam {
+    pub allow_force_push: Option<bool>,
+    pub allow_deletion: Option<bool>,
+    pub push_users: Option<Vec<Uuid>>,
+    pub push_roles: Option<Vec<String>>,
+}
+
+/// A ref update refused by a protection rule.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ProtectedBranchViolation {
+    pub refname: String,
+    pub reason: String,
+}
+
+impl App {
+    /// Whether the pusher holds one of the `push_roles` of a rule: `owner` for
+    /// repository (or organization) owners, or a role at or below their access level.
+    fn protected_branch_role_allowed(owner: bool, access: AccessLevel, roles: &[String]) -> bool {
+        roles.iter().any(|role| match role.as_str() {
+            "owner" => owner,
+            role => AccessLevel::from_role(role).is_some_and(|x| x <= access),
+        })
+    }
+
+    pub async fn protected_branch_list(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<Vec<ProtectedBranchModel>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        Ok(ProtectedBranchModel::get_by_repo_uid(&self.db, repo.uid).await?)
+    }
+
+    pub async fn protected_branch_create(&self, user: Uuid, repo: String, owner: String, param: ProtectedBranchCreateParam) -> AppResult<ProtectedBranchModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let pattern = param.pattern.trim().trim_start_matches("refs/heads/");
+        if pattern.is_empty() {
+            return Err(AppError::Custom("Pattern is required".to_string()));
+        }
+        let rules = ProtectedBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
+        if rules.iter().any(|x| x.pattern == pattern) {
+            return Err(AppError::Custom("Protection rule already exists".to_string()));
+        }
+        Ok(ProtectedBranchModel::create(
+            &self.db,
+            repo.uid,
+            pattern,
+            param.allow_force_push,
+            param.allow_deletion,
+            &param.push_users,
+            &param.push_roles,
+        ).await?)
+    }
+
+    pub async fn protected_branch_update(&self, user: Uuid, repo: String, owner: String, uid: Uuid, param: ProtectedBranchUpdateParam) -> AppResult<ProtectedBranchModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        match ProtectedBranchModel::get_by_uid(&self.db, uid).await? {
+            Some(rule) if rule.repo_uid == repo.uid => {}
+            _ => return Err(AppError::Custom("Protection rule not found".to_string())),
+        }
+        ProtectedBranchModel::update(
+            &self.db,
+            uid,
+            param.allow_force_push,
+            param.allow_deletion,
+            param.push_users.as_deref(),
+            param.push_roles.as_deref(),
+        ).await?
+            .ok_or(AppError::Custom("Protection rule not found".to_string()))
+    }
+
+    pub async fn protected_branch_delete(&self, user: Uuid, repo: String, owner: String, uid: Uuid) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        match ProtectedBranchModel::get_by_uid(&self.db, uid).await? {
+            Some(rule) if rule.repo_uid == repo.uid => {
+                ProtectedBranchModel::delete(&self.db, uid).await?;
+                Ok(())
+            }
+            _ => Err(AppError::Custom("Protection rule not found".to_string())),
+        }
+    }
+
+    /// Checks the ref updates of a push against the repository's protection rules.
+    /// `pack` is the pack data sent along with the commands, needed to tell
+    /// fast-forwards from force pushes before the objects are in the repository.
+    pub async fn protected_branch_check(
+        &self,
+        repo: &RepositoryModel,
+        user: Option<Uuid>,
+        updates: &[GitRefUpdate],
+        pack: &[u8],
+    ) -> AppResult<Vec<ProtectedBranchViolation>> {
+        let rules = ProtectedBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
+        if rules.is_empty() {
+            return Ok(vec![]);
+        }
+        let access = self.repository_access(repo, user).await?;
+        let owner = match user {
+            Some(user) => self.repository_owned_by(repo, user).await?,
+            None => false,
+        };
+        let git = AppGit::new(repo.to_path());
+        let mut quarantine = None;
+        let mut violations = vec![];
+        for update in updates {
+            let Some(branch) = update.branch() else {
+                continue;
+            };
+            let matched = rules.iter().filter(|x| x.matches(branch)).collect::<Vec<_>>();
+            if matched.is_empty() {
+                continue;
+            }
+            let reason = if matched.iter().any(|rule| {
+                rule.restricts_push()
+                    && !user.is_some_and(|user| rule.push_users.contains(&user))
+                    && !Self::protected_branch_role_allowed(owner, access, &rule.push_roles)
+            }) {
+                Some("protected branch: you are not allowed to push to this branch")
+            } else if update.is_delete() {
+                (!matched.iter().all(|x| x.allow_deletion))
+                    .then_some("protected branch: deletion is not allowed")
+            } else if update.is_create() || matched.iter().all(|x| x.allow_force_push) {
+                None
+            } else {
+                if quarantine.is_none() {
+                    quarantine = Some(git.quarantine(pack)?);
+                }
+                let fast_forward = quarantine
+                    .as_ref()
+                    .map(|x| x.is_fast_forward(&update.old, &update.new))
+                    .transpose()?
+                    .unwrap_or(false);
+                (!fast_forward).then_some("protected branch: force push is not allowed")
+            };
+            if let Some(reason) = reason {
+                violations.push(ProtectedBranchViolation {
+                    refname: update.name.clone(),
+                    reason: reason.to_string(),
+                });
+            }
+        }
+        Ok(violations)
+    }
+
+    /// Refuses deleting or renaming away a branch that a protection rule keeps.
+    pub(crate) async fn protected_branch_deletable(&self, repo: &RepositoryModel, branch: &str) -> AppResult<()> {
+        let rules = ProtectedBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
+        if rules.iter().any(|x| x.matches(branch) && !x.allow_deletion) {
+            return Err(AppError::Custom("Protected branch cannot be deleted".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::entities::pull_request::PullRequestModel;
+use crate::entities::pull_request_commit::PullRequestCommitModel;
+use crate::entities::repository::RepositoryModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use chrono::Local;
+use git::merge::{GitMergeParam, GitMergeResult, GitMergeStrategy};
+use git::receive::policy::GitCommitPolicy;
+use git::receive::GitRefUpdate;
+use git::tree::msg_tree::GitTreeAuthors;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestMergeParam {
+    pub strategy: GitMergeStrategy,
+    /// Message of the merge or squash commit; a default naming the pull request is used otherwise.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl App {
+    async fn pull_request_merge_message(&self, pull: &PullRequestModel, strategy: GitMergeStrategy) -> AppResult<String> {
+        Ok(match strategy {
+            GitMergeStrategy::Squash => {
+                let commits = PullRequestCommitModel::get_by_pull_uid(&self.db, pull.uid).await?;
+                let summary = commits
+                    .iter()
+                    .map(|x| format!("* {}", x.message.lines().next().unwrap_or_default()))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!("{} (#{})\n\n{}", pull.title, pull.number, summary)
+            }
+            _ => {
+                let source = if pull.source_repo_uid == pull.target_repo_uid {
+                    pull.source_branch.clone()
+                } else {
+                    let owner = match RepositoryModel::get_by_uid(&self.db, pull.source_repo_uid).await? {
+                        Some(repo) => self.repository_owner_name(&repo).await?,
+                        None => String::new(),
+                    };
+                    format!("{}/{}", owner, pull.source_branch)
+                };
+                format!("Merge pull request #{} from {}\n\n{}", pull.number, source, pull.title)
+            }
+        })
+    }
+
+    /// Merges an open pull request into its target branch. Conflicts are
+    /// returned as a result rather than an error, and leave everything untouched.
+    pub async fn pull_request_merge(&self, user: Uuid, repo: String, owner: String, number: i64, param: PullRequestMergeParam) -> AppResult<GitMergeResult> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        if !pull.is_open() {
+            return Err(AppError::Custom("Pull request is not open".to_string()));
+        }
+        self.pull_request_refresh(&pull).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        let Some(caller) = UsersModel::get_by_uid(&self.db, user).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+
+        // Every strategy fast-forwards the target, so only the push
+        // restrictions of a protection rule can refuse the merge.
+        let update = GitRefUpdate {
+            old: pull.base_sha.clone(),
+            new: pull.base_sha.clone(),
+            name: format!("refs/heads/{}", pull.target_branch),
+        };
+        let violations = self.protected_branch_check(&repo, Some(user), &[update], &[]).await?;
+        if let Some(violation) = violations.first() {
+            return Err(AppError::Custom(violation.reason.clone()));
+        }
+
+        let message = match param.message.filter(|x| !x.trim().is_empty()) {
+            Some(message) => message,
+            None => self.pull_request_merge_message(&pull, param.strategy).await?,
+        };
+        let now = Local::now().naive_local();
+        let signature = GitTreeAuthors {
+            name: caller.username.clone(),
+            email: caller.email.clone(),
+            time: now.and_utc().timestamp(),
+        };
+        // The commits the merge creates follow the same file size and
+        // message rules as pushed ones.
+        let policy = GitCommitPolicy::from_env();
+        let result = AppGit::new(repo.to_path()).merge(
+            GitMergeParam {
+                target: format!("refs/heads/{}", pull.target_branch),
+                expected: pull.base_sha.clone(),
+                head: pull.head_sha.clone(),
+                strategy: param.strategy,
+                message,
+                author: signature.clone(),
+                committer: signature,
+            },
+            &policy,
+        )?;
+        if let GitMergeResult::Merged { sha } = &result {
+            if let Some(pull) = PullRequestModel::update_merged(&self.db, pull.uid, user, sha).await? {
+                self.webhook_pull_request(&repo, Some(user), "closed", &pull).await;
+            }
+            let target = format!("refs/heads/{}", pull.target_branch);
+            let update = GitRefUpdate::new(target, Some(pull.base_sha.as_str()), Some(sha.as_str()));
+            self.sync_refs(&repo, Some(user), &[update]).await?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,256 @@
+use crate::entities::pull_request::{PullRequestModel, PULL_REQUEST_CLOSED, PULL_REQUEST_OPEN};
+use crate::entities::pull_request_commit::PullRequestCommitModel;
+use crate::entities::repository::RepositoryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::types::pager::QueryPager;
+use crate::App;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestCreateParam {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub target_branch: String,
+    pub source_branch: String,
+    /// Owner and name of the fork the source branch lives in; defaults to the target repository.
+    #[serde(default)]
+    pub source_owner: Option<String>,
+    #[serde(default)]
+    pub source_repo: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestUpdateParam {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub target_branch: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestFilter {
+    pub state: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestListResult {
+    pub total: i64,
+    pub list: Vec<PullRequestModel>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestDetail {
+    #[serde(flatten)]
+    pub pull: PullRequestModel,
+    pub commits: Vec<PullRequestCommitModel>,
+}
+
+impl App {
+    pub(crate) async fn pull_request_find(&self, repo: &RepositoryModel, number: i64) -> AppResult<PullRequestModel> {
+        PullRequestModel::get_by_repo_uid_and_number(&self.db, repo.uid, number).await?
+            .ok_or(AppError::Custom("Pull request not found".to_string()))
+    }
+
+    /// Authors may edit, close and reopen their own pull requests; everybody
+    /// else needs at least triage access to the target repository.
+    async fn pull_request_manageable(&self, repo: &RepositoryModel, pull: &PullRequestModel, user: Uuid) -> AppResult<()> {
+        if pull.author != user {
+            self.repository_authorize(repo, Some(user), AccessLevel::Triage).await?;
+        }
+        Ok(())
+    }
+
+    /// Copies the source branch into `refs/pull/{number}/head` of the target
+    /// repository, records the head, base and commit list of the pull request
+    /// and moves its review comments along.
+    pub(crate) async fn pull_request_refresh(&self, pull: &PullRequestModel) -> AppResult<()> {
+        let (Some(target), Some(source)) = (
+            RepositoryModel::get_by_uid(&self.db, pull.target_repo_uid).await?,
+            RepositoryModel::get_by_uid(&self.db, pull.source_repo_uid).await?,
+        ) else {
+            return Err(AppError::Custom("Repository not found".to_string()));
+        };
+        let git = AppGit::new(target.to_path());
+        let head = git.pull_head_update(&AppGit::new(source.to_path()), &pull.source_branch, pull.number)?;
+        let result = git.pull_commits(&pull.target_branch, &head)?;
+        if head != pull.head_sha || result.base != pull.base_sha {
+            PullRequestCommitModel::replace(&self.db, pull.uid, &result.commits).await?;
+            PullRequestModel::update_head(&self.db, pull.uid, &head, &result.base).await?;
+            self.pull_request_comments_follow(&git, pull.uid, &head, result.merge_base.as_deref()).await?;
+            // a new pull request is announced as opened instead
+            if head != pull.head_sha
+                && !pull.head_sha.is_empty()
+                && let Some(pull) = PullRequestModel::get_by_repo_uid_and_number(&self.db, target.uid, pull.number).await?
+            {
+                self.webhook_pull_request(&target, None, "synchronize", &pull).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Refreshes the open pull requests from or into `repo` after a push.
+    pub(crate) async fn pull_request_sync(&self, repo: &RepositoryModel) -> AppResult<()> {
+        for pull in PullRequestModel::get_open_by_repo_uid(&self.db, repo.uid).await? {
+            // a deleted source branch leaves the pull request at its last head
+            self.pull_request_refresh(&pull).await.ok();
+        }
+        Ok(())
+    }
+
+    /// Refuses a second open pull request between the same pair of branches.
+    async fn pull_request_duplicate(
+        &self,
+        target: &RepositoryModel,
+        target_branch: &str,
+        source_uid: Uuid,
+        source_branch: &str,
+        except: Option<Uuid>,
+    ) -> AppResult<()> {
+        let duplicate = PullRequestModel::get_open_by_repo_uid(&self.db, target.uid)
+            .await?
+            .into_iter()
+            .any(|x| {
+                Some(x.uid) != except
+                    && x.target_repo_uid == target.uid
+                    && x.target_branch == target_branch
+                    && x.source_repo_uid == source_uid
+                    && x.source_branch == source_branch
+            });
+        if duplicate {
+            return Err(AppError::Custom("A pull request for these branches is already open".to_string()));
+        }
+        Ok(())
+    }
+
+    pub async fn pull_request_create(&self, user: Uuid, repo: String, owner: String, param: PullRequestCreateParam) -> AppResult<PullRequestDetail> {
+        let target = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let title = param.title.trim();
+        if title.is_empty() {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        let source = match (param.source_owner, param.source_repo) {
+            (Some(owner), Some(repo)) => {
+                let source = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+                let same_network = self.repository_fork_root(source.clone()).await?.uid
+                    == self.repository_fork_root(target.clone()).await?.uid;
+                if !same_network {
+                    return Err(AppError::Custom("Source repository is not a fork of the target".to_string()));
+                }
+                source
+            }
+            (None, None) => target.clone(),
+            _ => return Err(AppError::Custom("Both source_owner and source_repo are required".to_string())),
+        };
+        if source.uid == target.uid && param.source_branch == param.target_branch {
+            return Err(AppError::Custom("Source and target branch are the same".to_string()));
+        }
+        let target_git = AppGit::new(target.to_path());
+        if !target_git.branch_list()?.iter().any(|x| x.name == param.target_branch) {
+            return Err(AppError::Custom(format!("Branch not found: {}", param.target_branch)));
+        }
+        if !AppGit::new(source.to_path()).branch_list()?.iter().any(|x| x.name == param.source_branch) {
+            return Err(AppError::Custom(format!("Branch not found: {}", param.source_branch)));
+        }
+        self.pull_request_duplicate(&target, &param.target_branch, source.uid, &param.source_branch, None).await?;
+        let pull = PullRequestModel::create(
+            &self.db,
+            target.uid,
+            &param.target_branch,
+            source.uid,
+            &param.source_branch,
+            title,
+            &param.body,
+            user,
+        ).await?;
+        self.pull_request_refresh(&pull).await?;
+        let detail = self.pull_request_detail(&target, pull.number).await?;
+        self.webhook_pull_request(&target, Some(user), "opened", &detail.pull).await;
+        Ok(detail)
+    }
+
+    async fn pull_request_detail(&self, repo: &RepositoryModel, number: i64) -> AppResult<PullRequestDetail> {
+        let pull = self.pull_request_find(repo, number).await?;
+        let commits = PullRequestCommitModel::get_by_pull_uid(&self.db, pull.uid).await?;
+        Ok(PullRequestDetail { pull, commits })
+    }
+
+    pub async fn pull_request_list(&self, user: Option<Uuid>, repo: String, owner: String, pager: QueryPager, filter: PullRequestFilter) -> AppResult<PullRequestListResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let state = filter.state.as_deref().filter(|x| *x != "all");
+        let list = PullRequestModel::get_by_repo_uid(&self.db, repo.uid, state, pager.limit, pager.page * pager.limit).await?;
+        let total = PullRequestModel::count_by_repo_uid(&self.db, repo.uid, state).await?;
+        Ok(PullRequestListResult { total, list })
+    }
+
+    pub async fn pull_request_get(&self, user: Option<Uuid>, repo: String, owner: String, number: i64) -> AppResult<PullRequestDetail> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        self.pull_request_detail(&repo, number).await
+    }
+
+    pub async fn pull_request_update(&self, user: Uuid, repo: String, owner: String, number: i64, param: PullRequestUpdateParam) -> AppResult<PullRequestDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        self.pull_request_manageable(&repo, &pull, user).await?;
+        let title = param.title.as_deref().map(str::trim);
+        if title.is_some_and(|x| x.is_empty()) {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        if let Some(branch) = &param.target_branch {
+            if !pull.is_open() {
+                return Err(AppError::Custom("Pull request is not open".to_string()));
+            }
+            if !AppGit::new(repo.to_path()).branch_list()?.iter().any(|x| x.name == *branch) {
+                return Err(AppError::Custom(format!("Branch not found: {}", branch)));
+            }
+            if pull.source_repo_uid == repo.uid && pull.source_branch == *branch {
+                return Err(AppError::Custom("Source and target branch are the same".to_string()));
+            }
+            self.pull_request_duplicate(&repo, branch, pull.source_repo_uid, &pull.source_branch, Some(pull.uid)).await?;
+        }
+        let pull = PullRequestModel::update(
+            &self.db,
+            pull.uid,
+            title,
+            param.body.as_deref(),
+            param.target_branch.as_deref(),
+        ).await?
+            .ok_or(AppError::Custom("Pull request not found".to_string()))?;
+        if param.target_branch.is_some() {
+            self.pull_request_refresh(&pull).await?;
+        }
+        let detail = self.pull_request_detail(&repo, number).await?;
+        self.webhook_pull_request(&repo, Some(user), "edited", &detail.pull).await;
+        Ok(detail)
+    }
+
+    pub async fn pull_request_close(&self, user: Uuid, repo: String, owner: String, number: i64) -> AppResult<PullRequestModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        self.pull_request_manageable(&repo, &pull, user).await?;
+        if !pull.is_open() {
+            return Err(AppError::Custom("Pull request is not open".to_string()));
+        }
+        let pull = PullRequestModel::update_state(&self.db, pull.uid, PULL_REQUEST_CLOSED).await?
+            .ok_or(AppError::Custom("Pull request not found".to_string()))?;
+        self.webhook_pull_request(&repo, Some(user), "closed", &pull).await;
+        Ok(pull)
+    }
+
+    pub async fn pull_request_reopen(&self, user: Uuid, repo: String, owner: String, number: i64) -> AppResult<PullRequestDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        self.pull_request_manageable(&repo, &pull, user).await?;
+        if pull.state != PULL_REQUEST_CLOSED {
+            return Err(AppError::Custom("Only closed pull requests can be reopened".to_string()));
+        }
+        self.pull_request_duplicate(&repo, &pull.target_branch, pull.source_repo_uid, &pull.source_branch, Some(pull.uid)).await?;
+        self.pull_request_refresh(&pull).await?;
+        PullRequestModel::update_state(&self.db, pull.uid, PULL_REQUEST_OPEN).await?;
+        let detail = self.pull_request_detail(&repo, number).await?;
+        self.webhook_pull_request(&repo, Some(user), "reopened", &detail.pull).await;
+        Ok(detail)
+    }
+}
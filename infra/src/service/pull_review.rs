@@ -0,0 +1,280 @@
+use crate::entities::pull_request::PullRequestModel;
+use crate::entities::pull_request_comment::{PullRequestCommentModel, COMMENT_SIDE_LEFT, COMMENT_SIDE_RIGHT};
+use crate::entities::pull_request_commit::PullRequestCommitModel;
+use crate::entities::pull_request_review::{
+    PullRequestReviewModel, REVIEW_APPROVED, REVIEW_CHANGES_REQUESTED, REVIEW_COMMENTED,
+};
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PullRequestReviewEvent {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestReviewCommentParam {
+    pub path: String,
+    /// `left` or `right`, defaults to `right`.
+    #[serde(default)]
+    pub side: Option<String>,
+    pub line: i32,
+    pub body: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestReviewParam {
+    pub event: PullRequestReviewEvent,
+    #[serde(default)]
+    pub body: String,
+    /// The commit of the pull request that was reviewed; defaults to its head.
+    #[serde(default)]
+    pub commit_sha: Option<String>,
+    #[serde(default)]
+    pub comments: Vec<PullRequestReviewCommentParam>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestCommentParam {
+    pub body: String,
+    /// Replies to the thread of this comment; the anchor fields are ignored then.
+    #[serde(default)]
+    pub in_reply_to: Option<Uuid>,
+    #[serde(default)]
+    pub commit_sha: Option<String>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub side: Option<String>,
+    #[serde(default)]
+    pub line: Option<i32>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestReviewDetail {
+    #[serde(flatten)]
+    pub review: PullRequestReviewModel,
+    pub comments: Vec<PullRequestCommentModel>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct PullRequestThread {
+    #[serde(flatten)]
+    pub comment: PullRequestCommentModel,
+    pub replies: Vec<PullRequestCommentModel>,
+}
+
+/// Validates an anchor given against `commit_sha` and returns the side and
+/// the commit whose version of the file the line refers to.
+fn comment_anchor(
+    git: &AppGit,
+    pull: &PullRequestModel,
+    commit_sha: &str,
+    path: &str,
+    side: Option<&str>,
+    line: i32,
+) -> AppResult<(String, String)> {
+    let side = side.unwrap_or(COMMENT_SIDE_RIGHT);
+    let anchor = match side {
+        COMMENT_SIDE_RIGHT => commit_sha.to_string(),
+        COMMENT_SIDE_LEFT => git
+            .merge_base(&pull.base_sha, commit_sha)?
+            .ok_or(AppError::Custom("Pull request has no merge base".to_string()))?,
+        _ => return Err(AppError::Custom(format!("Invalid side: {}", side))),
+    };
+    let count = git
+        .file_line_count(&anchor, path)?
+        .ok_or(AppError::Custom(format!("File not found: {}", path)))?;
+    if line < 1 || line as usize > count {
+        return Err(AppError::Custom(format!("Line {} is outside of {}", line, path)));
+    }
+    Ok((side.to_string(), anchor))
+}
+
+impl App {
+    /// Moves the threads that still follow the head to `head` (right side) or
+    /// `merge_base` (left side), marking them outdated when their line changed.
+    pub(crate) async fn pull_request_comments_follow(
+        &self,
+        git: &AppGit,
+        pull_uid: Uuid,
+        head: &str,
+        merge_base: Option<&str>,
+    ) -> AppResult<()> {
+        for root in PullRequestCommentModel::get_current_roots_by_pull_uid(&self.db, pull_uid).await? {
+            let target = match root.side.as_str() {
+                COMMENT_SIDE_LEFT => merge_base,
+                _ => Some(head),
+            };
+            let Some(target) = target else {
+                PullRequestCommentModel::update_outdated(&self.db, root.uid).await?;
+                continue;
+            };
+            if root.commit_sha == target {
+                continue;
+            }
+            match git.line_remap(&root.commit_sha, target, &root.path, root.line as u32) {
+                Ok(Some(line)) => PullRequestCommentModel::update_anchor(&self.db, root.uid, target, line as i32).await?,
+                _ => PullRequestCommentModel::update_outdated(&self.db, root.uid).await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `commit_sha` belongs to the pull request, defaulting to its head.
+    async fn pull_request_commit_resolve(&self, pull: &PullRequestModel, commit_sha: Option<String>) -> AppResult<String> {
+        let Some(commit_sha) = commit_sha.filter(|x| *x != pull.head_sha) else {
+            return Ok(pull.head_sha.clone());
+        };
+        let commits = PullRequestCommitModel::get_by_pull_uid(&self.db, pull.uid).await?;
+        if !commits.iter().any(|x| x.sha == commit_sha) {
+            return Err(AppError::Custom(format!("Commit is not part of the pull request: {}", commit_sha)));
+        }
+        Ok(commit_sha)
+    }
+
+    async fn pull_request_comment_find(&self, pull: &PullRequestModel, uid: Uuid) -> AppResult<PullRequestCommentModel> {
+        PullRequestCommentModel::get_by_uid(&self.db, uid).await?
+            .filter(|x| x.pull_uid == pull.uid)
+            .ok_or(AppError::Custom("Comment not found".to_string()))
+    }
+
+    async fn pull_request_thread(&self, pull: &PullRequestModel, root: Uuid) -> AppResult<PullRequestThread> {
+        self.pull_request_threads(pull).await?
+            .into_iter()
+            .find(|x| x.comment.uid == root)
+            .ok_or(AppError::Custom("Comment not found".to_string()))
+    }
+
+    async fn pull_request_threads(&self, pull: &PullRequestModel) -> AppResult<Vec<PullRequestThread>> {
+        let comments = PullRequestCommentModel::get_by_pull_uid(&self.db, pull.uid).await?;
+        let (roots, replies): (Vec<_>, Vec<_>) = comments.into_iter().partition(|x| x.in_reply_to.is_none());
+        Ok(roots
+            .into_iter()
+            .map(|comment| PullRequestThread {
+                replies: replies.iter().filter(|x| x.in_reply_to == Some(comment.uid)).cloned().collect(),
+                comment,
+            })
+            .collect())
+    }
+
+    pub async fn pull_request_reviews(&self, user: Option<Uuid>, repo: String, owner: String, number: i64) -> AppResult<Vec<PullRequestReviewModel>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        Ok(PullRequestReviewModel::get_by_pull_uid(&self.db, pull.uid).await?)
+    }
+
+    pub async fn pull_request_review_create(&self, user: Uuid, repo: String, owner: String, number: i64, param: PullRequestReviewParam) -> AppResult<PullRequestReviewDetail> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        if !pull.is_open() {
+            return Err(AppError::Custom("Pull request is not open".to_string()));
+        }
+        let state = match param.event {
+            PullRequestReviewEvent::Approve if pull.author == user => {
+                return Err(AppError::Custom("Cannot approve your own pull request".to_string()));
+            }
+            PullRequestReviewEvent::Approve => REVIEW_APPROVED,
+            PullRequestReviewEvent::RequestChanges if param.body.trim().is_empty() => {
+                return Err(AppError::Custom("A body is required to request changes".to_string()));
+            }
+            PullRequestReviewEvent::RequestChanges => REVIEW_CHANGES_REQUESTED,
+            PullRequestReviewEvent::Comment if param.body.trim().is_empty() && param.comments.is_empty() => {
+                return Err(AppError::Custom("A review needs a body or comments".to_string()));
+            }
+            PullRequestReviewEvent::Comment => REVIEW_COMMENTED,
+        };
+        let commit_sha = self.pull_request_commit_resolve(&pull, param.commit_sha).await?;
+        let git = AppGit::new(repo.to_path());
+        let mut anchors = vec![];
+        for comment in &param.comments {
+            if comment.body.trim().is_empty() {
+                return Err(AppError::Custom("Comment body is required".to_string()));
+            }
+            anchors.push(comment_anchor(&git, &pull, &commit_sha, &comment.path, comment.side.as_deref(), comment.line)?);
+        }
+        let review = PullRequestReviewModel::create(&self.db, pull.uid, user, state, &param.body, &commit_sha).await?;
+        for (comment, (side, anchor)) in param.comments.iter().zip(anchors) {
+            PullRequestCommentModel::create(
+                &self.db,
+                pull.uid,
+                Some(review.uid),
+                user,
+                &comment.body,
+                &comment.path,
+                &side,
+                &anchor,
+                comment.line,
+            ).await?;
+        }
+        if commit_sha != pull.head_sha {
+            let merge_base = git.merge_base(&pull.base_sha, &pull.head_sha)?;
+            self.pull_request_comments_follow(&git, pull.uid, &pull.head_sha, merge_base.as_deref()).await?;
+        }
+        let comments = PullRequestCommentModel::get_by_pull_uid(&self.db, pull.uid).await?
+            .into_iter()
+            .filter(|x| x.review_uid == Some(review.uid))
+            .collect();
+        Ok(PullRequestReviewDetail { review, comments })
+    }
+
+    pub async fn pull_request_comments(&self, user: Option<Uuid>, repo: String, owner: String, number: i64) -> AppResult<Vec<PullRequestThread>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        self.pull_request_threads(&pull).await
+    }
+
+    pub async fn pull_request_comment_create(&self, user: Uuid, repo: String, owner: String, number: i64, param: PullRequestCommentParam) -> AppResult<PullRequestCommentModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        if param.body.trim().is_empty() {
+            return Err(AppError::Custom("Comment body is required".to_string()));
+        }
+        if let Some(uid) = param.in_reply_to {
+            let comment = self.pull_request_comment_find(&pull, uid).await?;
+            let root = match comment.in_reply_to {
+                Some(root) => self.pull_request_comment_find(&pull, root).await?,
+                None => comment,
+            };
+            return Ok(PullRequestCommentModel::create_reply(&self.db, &root, user, &param.body).await?);
+        }
+        let (Some(path), Some(line)) = (param.path, param.line) else {
+            return Err(AppError::Custom("Either in_reply_to or path and line are required".to_string()));
+        };
+        let commit_sha = self.pull_request_commit_resolve(&pull, param.commit_sha).await?;
+        let git = AppGit::new(repo.to_path());
+        let (side, anchor) = comment_anchor(&git, &pull, &commit_sha, &path, param.side.as_deref(), line)?;
+        let comment = PullRequestCommentModel::create(&self.db, pull.uid, None, user, &param.body, &path, &side, &anchor, line).await?;
+        if commit_sha == pull.head_sha {
+            return Ok(comment);
+        }
+        let merge_base = git.merge_base(&pull.base_sha, &pull.head_sha)?;
+        self.pull_request_comments_follow(&git, pull.uid, &pull.head_sha, merge_base.as_deref()).await?;
+        PullRequestCommentModel::get_by_uid(&self.db, comment.uid).await?
+            .ok_or(AppError::Custom("Comment not found".to_string()))
+    }
+
+    /// Resolves or unresolves the thread a comment belongs to. Allowed for the
+    /// pull request author, the thread author and anyone with triage access.
+    pub async fn pull_request_comment_resolve(&self, user: Uuid, repo: String, owner: String, number: i64, uid: Uuid, resolved: bool) -> AppResult<PullRequestThread> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        let comment = self.pull_request_comment_find(&pull, uid).await?;
+        let root = match comment.in_reply_to {
+            Some(root) => self.pull_request_comment_find(&pull, root).await?,
+            None => comment,
+        };
+        if pull.author != user && root.author != user {
+            self.repository_authorize(&repo, Some(user), AccessLevel::Triage).await?;
+        }
+        PullRequestCommentModel::update_resolved(&self.db, root.uid, resolved.then_some(user)).await?;
+        self.pull_request_thread(&pull, root.uid).await
+    }
+}
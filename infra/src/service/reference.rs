@@ -0,0 +1,126 @@
+use crate::entities::issue::{IssueModel, ISSUE_CLOSED};
+use crate::entities::pull_request::PullRequestModel;
+use crate::entities::reference_event::ReferenceEventModel;
+use crate::entities::repository::RepositoryModel;
+use crate::error::AppResult;
+use crate::service::access::AccessLevel;
+use crate::App;
+use chrono::NaiveDateTime;
+use git::commit::list::GitCommit;
+use git::commit::references::commit_references;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ReferenceEvent {
+    pub uid: Uuid,
+    pub source_owner: String,
+    pub source_repo: String,
+    pub commit_sha: String,
+    pub author_name: String,
+    pub summary: String,
+    pub closed: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl App {
+    /// A fixing commit closes an issue only when pushed by a user with triage
+    /// access to the issue's repository. Commit authors are set freely by
+    /// whoever made the commit, so only the authenticated `sender` counts, and
+    /// without one references are recorded but close nothing.
+    async fn commit_may_close(&self, sender: Option<Uuid>, target: &RepositoryModel) -> AppResult<bool> {
+        if sender.is_none() {
+            return Ok(false);
+        }
+        Ok(self.repository_access(target, sender).await? >= AccessLevel::Triage)
+    }
+
+    /// Records the issues and pull requests mentioned by commits that `sender`
+    /// landed on the default branch of `repo`, closing the issues they fix.
+    pub(crate) async fn commit_references_sync(&self, repo: &RepositoryModel, sender: Option<Uuid>, commits: &[GitCommit]) -> AppResult<()> {
+        for commit in commits {
+            let summary = commit.message.lines().next().unwrap_or_default();
+            for reference in commit_references(&commit.message) {
+                let target = match reference.repository {
+                    None => repo.clone(),
+                    Some((owner, name)) => {
+                        match RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, name).await? {
+                            Some(target) => target,
+                            None => continue,
+                        }
+                    }
+                };
+                if let Some(issue) = IssueModel::get_by_repo_uid_and_number(&self.db, target.uid, reference.number).await? {
+                    let close = reference.closes && issue.is_open() && self.commit_may_close(sender, &target).await?;
+                    let created = ReferenceEventModel::create(
+                        &self.db,
+                        Some(issue.uid),
+                        None,
+                        repo.uid,
+                        &commit.hash,
+                        &commit.author,
+                        summary,
+                        close,
+                    ).await?;
+                    // a commit seen again, e.g. after a fork, must not close a reopened issue
+                    if created && close
+                        && let Some(issue) = IssueModel::update_state(&self.db, issue.uid, ISSUE_CLOSED).await?
+                    {
+                        let detail = self.issue_detail(issue).await?;
+                        self.webhook_issue(&target, sender, "closed", &detail).await;
+                    }
+                } else if let Some(pull) = PullRequestModel::get_by_repo_uid_and_number(&self.db, target.uid, reference.number).await? {
+                    ReferenceEventModel::create(
+                        &self.db,
+                        None,
+                        Some(pull.uid),
+                        repo.uid,
+                        &commit.hash,
+                        &commit.author,
+                        summary,
+                        false,
+                    ).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Leaves out references from repositories `user` cannot read.
+    async fn reference_events_visible(&self, user: Option<Uuid>, events: Vec<ReferenceEventModel>) -> AppResult<Vec<ReferenceEvent>> {
+        let mut result = vec![];
+        for event in events {
+            let Some(source) = RepositoryModel::get_by_uid(&self.db, event.source_repo_uid).await? else {
+                continue;
+            };
+            if self.repository_access(&source, user).await? < AccessLevel::Read {
+                continue;
+            }
+            result.push(ReferenceEvent {
+                uid: event.uid,
+                source_owner: self.repository_owner_name(&source).await?,
+                source_repo: source.name,
+                commit_sha: event.commit_sha,
+                author_name: event.author_name,
+                summary: event.summary,
+                closed: event.closed,
+                created_at: event.created_at,
+            });
+        }
+        Ok(result)
+    }
+
+    pub async fn issue_events(&self, user: Option<Uuid>, repo: String, owner: String, number: i64) -> AppResult<Vec<ReferenceEvent>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let issue = self.issue_find(&repo, number).await?;
+        let events = ReferenceEventModel::get_by_issue_uid(&self.db, issue.uid).await?;
+        self.reference_events_visible(user, events).await
+    }
+
+    pub async fn pull_request_events(&self, user: Option<Uuid>, repo: String, owner: String, number: i64) -> AppResult<Vec<ReferenceEvent>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let pull = self.pull_request_find(&repo, number).await?;
+        let events = ReferenceEventModel::get_by_pull_uid(&self.db, pull.uid).await?;
+        self.reference_events_visible(user, events).await
+    }
+}
@@ -1,7 +1,10 @@
+use crate::entities::organization::OrganizationModel;
+use crate::entities::organization_member::OrganizationMemberModel;
 use crate::entities::repository::RepositoryModel;
 use crate::entities::users::UsersModel;
 use crate::error::{AppError, AppResult};
 use crate::types::pager::QueryPager;
+use crate::service::access::AccessLevel;
 use crate::App;
 use chrono::Local;
 use git::blob::insert::GitBlobInsertDataParam;
@@ -19,6 +22,9 @@ pub struct RepositoryInitParam {
     pub description: String,
     pub initial: bool,
     pub is_public: bool,
+    /// Organization to create the repository under; defaults to the caller.
+    #[serde(default)]
+    pub owner: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -50,6 +56,7 @@ pub struct RepositoryModelList {
     pub owner: Uuid,
     pub owner_name: String,
     pub description: String,
+    pub is_public: bool,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
 }
@@ -61,13 +68,14 @@ pub struct RepositoryModelResult {
 }
 
 impl App {
-    pub async fn repository_init(&self, owner: Uuid, param: RepositoryInitParam) -> AppResult<()> {
-        let Some(owner) = UsersModel::get_by_uid(&self.db, owner).await? else {
+    pub async fn repository_init(&self, user: Uuid, param: RepositoryInitParam) -> AppResult<()> {
+        let Some(owner) = UsersModel::get_by_uid(&self.db, user).await? else {
             return Err(AppError::Custom("User not found".to_string()));
         };
+        let (owner_uid, owner_name) = self.repository_owner_resolve(&owner, param.owner.as_deref()).await?;
         if RepositoryModel::repository_find_by_owner_name_and_repo_name(
             &self.db,
-            owner.username.clone(),
+            owner_name,
             param.name.clone(),
         )
         .await?
@@ -76,7 +84,7 @@ impl App {
             return Err(AppError::Custom("Repository already exists".to_string()));
         }
         let repo =
-            RepositoryModel::create(&self.db, &param.name, owner.uid, &param.description).await?;
+            RepositoryModel::create(&self.db, &param.name, owner_uid, &param.description, param.is_public, None).await?;
         if param.initial {
             let git = AppGit::new(repo.to_path());
             git.init()?;
@@ -102,8 +110,11 @@ impl App {
         }
         Ok(())
     }
-       pub async fn repository_list(
+    /// Lists the repositories visible to `user`: public ones, plus the private
+    /// ones they own, collaborate on, or reach through an organization.
+    pub async fn repository_list(
         &self,
+        user: Option<Uuid>,
         pager: QueryPager,
         filter: RepositoryFilter,
     ) -> AppResult<RepositoryModelResult> {
@@ -116,79 +127,63 @@ impl App {
             RepositoryFilterSort::UpdatedDesc => "r.updated_at DESC",
         };
 
-        let base_query = "SELECT r.uid, r.owner, u.username as owner_name, r.deleted_at, r.name, r.description, r.updated_at,\
-         r.created_at FROM repository r JOIN users u ON u.uid = r.owner".to_string();
+        let owner_join = "LEFT JOIN users u ON u.uid = r.owner LEFT JOIN organization o ON o.uid = r.owner";
+        let base_query = format!("SELECT r.uid, r.owner, COALESCE(u.username, o.name) as owner_name, r.deleted_at, r.name, r.description, r.is_public, r.updated_at,\
+         r.created_at FROM repository r {}", owner_join);
 
-        let where_clause = if let Some(ref _name) = filter.name {
-            "WHERE r.deleted_at IS NULL AND (r.name LIKE $3 OR r.description LIKE $4)".to_string()
+        let visible = "(r.is_public OR r.owner = $1 OR EXISTS (SELECT 1 FROM repository_collaborator c \
+         WHERE c.repo_uid = r.uid AND c.user_uid = $1 AND c.accepted) OR EXISTS (SELECT 1 FROM organization_member m \
+         WHERE m.org_uid = r.owner AND m.user_uid = $1 AND m.role = 'owner') OR EXISTS (SELECT 1 FROM team_repository tr \
+         JOIN team_member tm ON tm.team_uid = tr.team_uid WHERE tr.repo_uid = r.uid AND tm.user_uid = $1))";
+        let (where_clause, limit_clause) = if filter.name.is_some() {
+            (
+                format!("WHERE r.deleted_at IS NULL AND {} AND (r.name LIKE $2 OR r.description LIKE $2)", visible),
+                "LIMIT $3 OFFSET $4",
+            )
         } else {
-            "WHERE r.deleted_at IS NULL".to_string()
+            (
+                format!("WHERE r.deleted_at IS NULL AND {}", visible),
+                "LIMIT $2 OFFSET $3",
+            )
         };
 
         let order_clause = format!("ORDER BY {}", sort_query);
-        let limit_clause = "LIMIT $1 OFFSET $2".to_string();
 
         let query = format!("{} {} {} {}", base_query, where_clause, order_clause, limit_clause);
+        let count_query = format!("SELECT COUNT(*) FROM repository r {} {}", owner_join, where_clause);
 
-        let result = if let Some(name) = filter.name.clone() {
+        let mut list_query = sqlx::query(&query).bind(user);
+        let mut count_query = sqlx::query(&count_query).bind(user);
+        if let Some(name) = filter.name {
             let search_term = format!("%{}%", name);
-            sqlx::query(&query)
-                .bind(pager.limit)
-                .bind(pager.page * pager.limit)
-                .bind(&search_term)
-                .bind(&search_term)
-                .fetch_all(&self.db)
-                .await
-                .map(|rows| {
-                    rows.into_iter()
-                        .map(|r| RepositoryModelList {
-                            uid: r.get("uid"),
-                            name: r.get("name"),
-                            owner: r.get("owner"),
-                            owner_name: r.get("owner_name"),
-                            description: r.get("description"),
-                            created_at: r.get("created_at"),
-                            updated_at: r.get("updated_at"),
-                        })
-                        .collect::<Vec<_>>()
-                })?
-        } else {
-            sqlx::query(&query)
-                .bind(pager.limit)
-                .bind(pager.page * pager.limit)
-                .fetch_all(&self.db)
-                .await
-                .map(|rows| {
-                    rows.into_iter()
-                        .map(|r| RepositoryModelList {
-                            uid: r.get("uid"),
-                            name: r.get("name"),
-                            owner: r.get("owner"),
-                            owner_name: r.get("owner_name"),
-                            description: r.get("description"),
-                            created_at: r.get("created_at"),
-                            updated_at: r.get("updated_at"),
-                        })
-                        .collect::<Vec<_>>()
-                })?
-        };
+            list_query = list_query.bind(search_term.clone());
+            count_query = count_query.bind(search_term);
+        }
+        let result = list_query
+            .bind(pager.limit)
+            .bind(pager.page * pager.limit)
+            .fetch_all(&self.db)
+            .await
+            .map(|rows| {
+                rows.into_iter()
+                    .map(|r| RepositoryModelList {
+                        uid: r.get("uid"),
+                        name: r.get("name"),
+                        owner: r.get("owner"),
+                        owner_name: r.get("owner_name"),
+                        description: r.get("description"),
+                        is_public: r.get("is_public"),
+                        created_at: r.get("created_at"),
+                        updated_at: r.get("updated_at"),
+                    })
+                    .collect::<Vec<_>>()
+            })?;
 
-        let count = if let Some(name) = filter.name {
-        let search_term = format!("%{}%", name);
-        let count_query = format!("SELECT COUNT(*) FROM repository r JOIN users u ON u.uid = r.owner {}", where_clause);
-            sqlx::query(&count_query)
-                .bind(&search_term)
-                .bind(&search_term)
-                .fetch_one(&self.db)
-                .await
-                .map(|x| x.get::<i64, usize>(0))
-        } else {
-            let count_query = format!("SELECT COUNT(*) FROM repository r JOIN users u ON u.uid = r.owner {}", where_clause);
-            sqlx::query(&count_query)
-                .fetch_one(&self.db)
-                .await
-                .map(|x| x.get::<i64, usize>(0))
-        }.unwrap_or(0);
+        let count = count_query
+            .fetch_one(&self.db)
+            .await
+            .map(|x| x.get::<i64, usize>(0))
+            .unwrap_or(0);
 
 
         Ok(RepositoryModelResult {
@@ -197,9 +192,37 @@ impl App {
         })
     }
 
-    pub async fn repository_dash(&self, repo: String, owner: String) -> AppResult<RepositoryDashResult> {
-        let repo = RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, repo).await?
-            .ok_or(anyhow::anyhow!("Repository not found"))?;
+    /// Resolves who a new repository will belong to: `user` themselves, or
+    /// the organization named `org`, which they must own.
+    pub(crate) async fn repository_owner_resolve(&self, user: &UsersModel, org: Option<&str>) -> AppResult<(Uuid, String)> {
+        match org {
+            Some(org) if org != user.username => {
+                let Some(org) = OrganizationModel::get_by_name(&self.db, org).await? else {
+                    return Err(AppError::Custom("Organization not found".to_string()));
+                };
+                let member = OrganizationMemberModel::get_by_org_uid_and_user_uid(&self.db, org.uid, user.uid).await?;
+                if !member.is_some_and(|x| x.is_owner()) {
+                    return Err(AppError::Custom("Permission denied".to_string()));
+                }
+                Ok((org.uid, org.name))
+            }
+            _ => Ok((user.uid, user.username.clone())),
+        }
+    }
+
+    /// The username or organization name `repo` lives under.
+    pub async fn repository_owner_name(&self, repo: &RepositoryModel) -> AppResult<String> {
+        if let Some(user) = UsersModel::get_by_uid(&self.db, repo.owner).await? {
+            return Ok(user.username);
+        }
+        Ok(OrganizationModel::get_by_uid(&self.db, repo.owner)
+            .await?
+            .map(|x| x.name)
+            .unwrap_or_default())
+    }
+
+    pub async fn repository_dash(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<RepositoryDashResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
         let git = AppGit::new(repo.to_path());
         let branches = git.branch_list()?;
         Ok(RepositoryDashResult {
@@ -208,9 +231,8 @@ impl App {
         })
     }
 
-    pub async fn repository_tree(&self, repo: String, owner: String, path: String) -> AppResult<RepositoryTreeResult> {
-        let repo = RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, repo).await?
-            .ok_or(anyhow::anyhow!("Repository not found"))?;
+    pub async fn repository_tree(&self, user: Option<Uuid>, repo: String, owner: String, path: String) -> AppResult<RepositoryTreeResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
         let git = AppGit::new(repo.to_path());
         let tree = git.tree_msg(StateTreeParam {
             head: None,
@@ -223,9 +245,8 @@ impl App {
         })
     }
 
-    pub async fn repository_commits(&self, repo: String, owner: String, page: i32, limit: i32) -> AppResult<RepositoryCommitsResult> {
-        let repo = RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, repo).await?
-            .ok_or(anyhow::anyhow!("Repository not found"))?;
+    pub async fn repository_commits(&self, user: Option<Uuid>, repo: String, owner: String, page: i32, limit: i32) -> AppResult<RepositoryCommitsResult> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
         let git = AppGit::new(repo.to_path());
         let commits_result = git.commit_list(GitCommitListParam {
             start: None,
@@ -242,9 +263,8 @@ impl App {
             limit,
         })
     }
-    pub async fn repository_branch(&self, repo: String, owner: String) -> AppResult<Vec<GitBranchListResult>> {
-        let repo = RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, repo).await?
-            .ok_or(anyhow::anyhow!("Repository not found"))?;
+    pub async fn repository_branch(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<Vec<GitBranchListResult>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
         let git = AppGit::new(repo.to_path());
         let branch_result = git.branch_list()?;
         Ok(branch_result)
@@ -0,0 +1,81 @@
+use crate::entities::ssh_keys::SshKeyModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::App;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SshKeyCreateParam {
+    pub title: String,
+    pub content: String,
+}
+
+/// Parses an OpenSSH public key line (`<type> <base64> [comment]`) and returns
+/// the normalized `<type> <base64>` form with its `SHA256:` fingerprint, as
+/// printed by `ssh-keygen -lf`.
+pub fn ssh_key_fingerprint(content: &str) -> AppResult<(String, String)> {
+    let mut parts = content.split_whitespace();
+    let (Some(kind), Some(encoded)) = (parts.next(), parts.next()) else {
+        return Err(AppError::Custom("Invalid SSH public key".to_string()));
+    };
+    let blob = STANDARD
+        .decode(encoded)
+        .map_err(|_| AppError::Custom("Invalid SSH public key".to_string()))?;
+    // The key blob starts with its own length-prefixed algorithm name.
+    let declared = blob
+        .get(..4)
+        .map(|x| u32::from_be_bytes([x[0], x[1], x[2], x[3]]) as usize)
+        .and_then(|len| blob.get(4..4 + len));
+    if declared != Some(kind.as_bytes()) {
+        return Err(AppError::Custom("Invalid SSH public key".to_string()));
+    }
+    let fingerprint = format!("SHA256:{}", STANDARD_NO_PAD.encode(Sha256::digest(&blob)));
+    Ok((format!("{} {}", kind, encoded), fingerprint))
+}
+
+impl App {
+    pub async fn ssh_key_list(&self, user: Uuid) -> AppResult<Vec<SshKeyModel>> {
+        Ok(SshKeyModel::get_by_owner(&self.db, user).await?)
+    }
+
+    pub async fn ssh_key_create(&self, user: Uuid, param: SshKeyCreateParam) -> AppResult<SshKeyModel> {
+        let title = param.title.trim();
+        if title.is_empty() {
+            return Err(AppError::Custom("Title is required".to_string()));
+        }
+        let (content, fingerprint) = ssh_key_fingerprint(&param.content)?;
+        if SshKeyModel::get_by_fingerprint(&self.db, &fingerprint).await?.is_some() {
+            return Err(AppError::Custom("SSH key already in use".to_string()));
+        }
+        Ok(SshKeyModel::create(&self.db, user, title, &fingerprint, &content).await?)
+    }
+
+    pub async fn ssh_key_delete(&self, user: Uuid, uid: Uuid) -> AppResult<()> {
+        match SshKeyModel::get_by_uid(&self.db, uid).await? {
+            Some(key) if key.owner == user => {
+                SshKeyModel::delete(&self.db, uid).await?;
+                Ok(())
+            }
+            _ => Err(AppError::Custom("SSH key not found".to_string())),
+        }
+    }
+
+    /// Resolves the owner of the key with the given fingerprint and records the use.
+    pub async fn ssh_key_auth(&self, fingerprint: &str) -> AppResult<Option<UsersModel>> {
+        let Some(key) = SshKeyModel::get_by_fingerprint(&self.db, fingerprint).await? else {
+            return Ok(None);
+        };
+        let Some(user) = UsersModel::get_by_uid(&self.db, key.owner).await? else {
+            return Ok(None);
+        };
+        if user.deleted_at.is_some() {
+            return Ok(None);
+        }
+        SshKeyModel::touch(&self.db, key.uid).await?;
+        Ok(Some(user))
+    }
+}
@@ -1,64 +1,159 @@
 use crate::entities::git_branch::GitBranchModel;
 use crate::entities::git_commit::GitCommitModel;
+use crate::entities::git_tags::GitTags;
 use crate::entities::repository::RepositoryModel;
 use crate::error::AppResult;
 use crate::App;
-use git::commit::list::GitCommitListParam;
+use git::commit::list::GitCommit;
+use git::receive::GitRefUpdate;
 use git::AppGit;
+use std::collections::HashMap;
+use uuid::Uuid;
 
 impl App {
-    
-    pub async fn sync_hook_with_owner_repo(&self, owner: String, repo: String) {
-        match RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, owner, repo).await {
-            Ok(Some(x)) => {
-                self.sync_hook(x).await.ok();
+    /// Brings the database in line with the repository when the updates that
+    /// led to its refs are not known, such as right after a fork. Only the
+    /// refs are compared; histories are walked just for those that differ.
+    pub async fn sync_hook(&self, repo: RepositoryModel) -> AppResult<()> {
+        let git = AppGit::new(repo.to_path());
+        let branches = GitBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
+        let tags = GitTags::get_by_repo_uid(&self.db, repo.uid).await?;
+        let branch_list = git.branch_list()?;
+        let tag_list = git.tag_list()?;
+        let mut updates = vec![];
+        for branch in &branch_list {
+            let old = branches.iter().find(|x| x.name == branch.name).map(|x| x.head.as_str());
+            if old != Some(branch.head.as_str()) {
+                updates.push(GitRefUpdate::new(format!("refs/heads/{}", branch.name), old, Some(branch.head.as_str())));
+            }
+        }
+        for branch in branches.iter().filter(|x| !branch_list.iter().any(|y| y.name == x.name)) {
+            updates.push(GitRefUpdate::new(format!("refs/heads/{}", branch.name), Some(branch.head.as_str()), None));
+        }
+        for tag in &tag_list {
+            let old = tags.iter().find(|x| x.name == tag.name).map(|x| x.sha.as_str());
+            if old != Some(tag.target.as_str()) {
+                updates.push(GitRefUpdate::new(format!("refs/tags/{}", tag.name), old, Some(tag.oid.as_str())));
             }
-            Err(_) => {},
-            Ok(None) => {}
         }
+        for tag in tags.iter().filter(|x| !tag_list.iter().any(|y| y.name == x.name)) {
+            updates.push(GitRefUpdate::new(format!("refs/tags/{}", tag.name), Some(tag.sha.as_str()), None));
+        }
+        self.sync_refs(&repo, None, &updates).await
     }
-    pub async fn sync_hook(&self, repo: RepositoryModel) -> AppResult<()> {
-        let branches = GitBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
-        // let tags = GitTags::get_by_repo_uid(&self.db, repo.uid).await?;
+
+    /// Applies ref updates made by `sender` to the branches, tags and commits
+    /// recorded for `repo`, in one transaction, skipping those the refs on
+    /// disk no longer agree with, then refreshes pull requests, records the
+    /// references of commits that landed on the default branch and sends
+    /// webhooks.
+    ///
+    /// The recorded commits are those reachable from the recorded branches,
+    /// so only commits reachable from none of them are walked and inserted.
+    /// Deleting a branch drops the commits no other branch reaches and moves
+    /// each of the rest to a branch reaching it, the default branch first.
+    pub async fn sync_refs(&self, repo: &RepositoryModel, sender: Option<Uuid>, updates: &[GitRefUpdate]) -> AppResult<()> {
+        if updates.is_empty() {
+            return Ok(());
+        }
         let git = AppGit::new(repo.to_path());
+        let branches = GitBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
         let branch_list = git.branch_list()?;
-        for branch in branch_list {
-            if branches.iter().find(|x|x.name == branch.name).is_some() {
-                if branches.iter().find(|x|x.name == branch.name).unwrap().head != branch.head {
-                    GitBranchModel::update(&self.db, branches.iter().find(|x|x.name == branch.name).unwrap().uid, None, Some(&branch.head)).await?;
-                }
+        let tag_list = git.tag_list()?;
+        let default = branch_list.iter().find(|x| x.default).map(|x| x.name.clone());
+
+        // Syncs may run out of order, so an update the ref on disk has since
+        // moved past is left to the sync of the later update, which walks its
+        // commits too; recording it would move the head back.
+        let current = updates
+            .iter()
+            .filter(|update| {
+                let on_disk = if let Some(name) = update.branch() {
+                    branch_list.iter().find(|x| x.name == name).map(|x| x.head.as_str())
+                } else if let Some(name) = update.tag() {
+                    tag_list.iter().find(|x| x.name == name).map(|x| x.oid.as_str())
+                } else {
+                    return false;
+                };
+                on_disk == (!update.is_delete()).then_some(update.new.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        let recorded = branches.iter().map(|x| x.head.as_str()).collect::<Vec<_>>();
+        let mut heads = branches
+            .iter()
+            .map(|x| (x.name.as_str(), x.head.as_str()))
+            .collect::<HashMap<_, _>>();
+        let mut pushed = vec![];
+        for update in &current {
+            let Some(name) = update.branch() else {
+                continue;
+            };
+            if update.is_delete() {
+                heads.remove(name);
             } else {
-                GitBranchModel::create(&self.db, repo.uid, &branch.name, &branch.head).await?;
+                heads.insert(name, update.new.as_str());
+                pushed.push((name, git.commit_range_excluding(&update.new, &recorded)?));
             }
         }
-        let branches = GitBranchModel::get_by_repo_uid(&self.db, repo.uid).await?;
-        for branch in branches {
-            if let Ok(commit_list) = git.commit_list(GitCommitListParam {
-                start: None,
-                end: None,
-                limit: None,
-                branch: Some(branch.name.clone()),
-            }) {
-                for cmt in commit_list.data {
-                    if let None = GitCommitModel::get_by_sha(&self.db, &cmt.hash).await? {
-                        GitCommitModel::create(
-                            &self.db,
-                            cmt.hash.as_str(),
-                            branch.uid,
-                            repo.uid,
-                            branch.name.clone().as_str(),
-                            cmt.message.as_str(),
-                            cmt.author.as_str(),
-                            cmt.email.as_str(),
-                            cmt.committer.as_str(),
-                            cmt.committer_email.as_str(),
-                        )
-                            .await?;
-                    }
+        let mut deleted = vec![];
+        for update in current.iter().filter(|x| x.is_delete()) {
+            let Some(branch) = update.branch().and_then(|x| branches.iter().find(|y| y.name == x)) else {
+                continue;
+            };
+            let mut others = heads.iter().map(|(name, head)| (*name, *head)).collect::<Vec<_>>();
+            others.sort_by_key(|(name, _)| (Some(*name) != default.as_deref(), *name));
+            let ranges = git.commit_range_split(&branch.head, &others.iter().map(|x| x.1).collect::<Vec<_>>())?;
+            let unreachable = match ranges.last() {
+                Some(range) => range.clone(),
+                None => git.commit_range_excluding(&branch.head, &[] as &[&str])?,
+            };
+            let hashes = |commits: Vec<GitCommit>| commits.into_iter().map(|x| x.hash).collect::<Vec<_>>();
+            let moves = others.iter().map(|x| x.0).zip(ranges.into_iter().map(hashes)).collect::<Vec<_>>();
+            deleted.push((branch.name.as_str(), hashes(unreachable), moves));
+        }
+
+        let mut tx = self.db.begin().await?;
+        let mut uids = branches.iter().map(|x| (x.name.clone(), x.uid)).collect::<HashMap<_, _>>();
+        for (name, commits) in &pushed {
+            let head = heads[name];
+            let branch = GitBranchModel::upsert(&mut tx, repo.uid, name, head).await?;
+            GitCommitModel::create_batch(&mut tx, repo.uid, branch.uid, name, commits).await?;
+            uids.insert(branch.name, branch.uid);
+        }
+        for (name, unreachable, moves) in &deleted {
+            GitCommitModel::delete_by_shas(&mut tx, repo.uid, unreachable).await?;
+            // Each branch takes the commits it reaches of those still left;
+            // the rows of the deleted branch go with it.
+            for (to, keep) in moves {
+                if let Some(to_uid) = uids.get(*to) {
+                    GitCommitModel::move_branch(&mut tx, uids[*name], *to_uid, to, keep).await?;
                 }
             }
+            GitBranchModel::delete_by_name(&mut tx, repo.uid, name).await?;
         }
+        for update in &current {
+            let Some(name) = update.tag() else {
+                continue;
+            };
+            if update.is_delete() {
+                GitTags::delete_by_name(&mut tx, repo.uid, name).await?;
+            } else if let Ok(tag) = git.tag_get(name) {
+                GitTags::upsert(&mut tx, repo.uid, name, &tag.target).await?;
+            }
+        }
+        tx.commit().await?;
 
+        let landed = updates
+            .iter()
+            .find(|x| !x.is_delete() && x.branch().is_some() && x.branch() == default.as_deref());
+        if let Some(update) = landed {
+            let old = (!update.is_create()).then_some(update.old.as_str());
+            let commits = git.commit_range(old, &update.new)?;
+            self.commit_references_sync(repo, sender, &commits).await?;
+        }
+        self.pull_request_sync(repo).await?;
+        self.webhook_refs(repo, &git, sender, updates).await;
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,58 @@
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::App;
+use chrono::Local;
+use git::receive::GitRefUpdate;
+use git::tag::create::GitTagCreateParam;
+use git::tag::list::GitTagListResult;
+use git::tree::msg_tree::GitTreeAuthors;
+use git::AppGit;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct RepositoryTagCreateParam {
+    pub name: String,
+    pub target: String,
+    pub message: Option<String>,
+}
+
+impl App {
+    pub async fn repository_tags(&self, user: Option<Uuid>, repo: String, owner: String) -> AppResult<Vec<GitTagListResult>> {
+        let repo = self.repository_authorized(user, repo, owner, AccessLevel::Read).await?;
+        let git = AppGit::new(repo.to_path());
+        Ok(git.tag_list()?)
+    }
+
+    pub async fn repository_tag_create(&self, user: Uuid, repo: String, owner: String, param: RepositoryTagCreateParam) -> AppResult<GitTagListResult> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let Some(tagger) = UsersModel::get_by_uid(&self.db, user).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        let git = AppGit::new(repo.to_path());
+        let tag = git.tag_create(GitTagCreateParam {
+            name: param.name,
+            target: param.target,
+            message: param.message,
+            tagger: GitTreeAuthors {
+                name: tagger.username,
+                email: tagger.email,
+                time: Local::now().timestamp(),
+            },
+        })?;
+        let update = GitRefUpdate::new(format!("refs/tags/{}", tag.name), None, Some(tag.oid.as_str()));
+        self.sync_refs(&repo, Some(user), &[update]).await?;
+        Ok(tag)
+    }
+
+    pub async fn repository_tag_delete(&self, user: Uuid, repo: String, owner: String, name: String) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Write).await?;
+        let git = AppGit::new(repo.to_path());
+        let tag = git.tag_get(&name)?;
+        git.tag_delete(&name)?;
+        let update = GitRefUpdate::new(format!("refs/tags/{}", name), Some(tag.oid.as_str()), None);
+        self.sync_refs(&repo, Some(user), &[update]).await?;
+        Ok(())
+    }
+}
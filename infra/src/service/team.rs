@@ -0,0 +1,173 @@
+use crate::entities::organization::OrganizationModel;
+use crate::entities::organization_member::OrganizationMemberModel;
+use crate::entities::repository::RepositoryModel;
+use crate::entities::team::TeamModel;
+use crate::entities::team_member::TeamMemberModel;
+use crate::entities::team_repository::TeamRepositoryModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::collaborator::collaborator_role;
+use crate::App;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TeamCreateParam {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TeamMemberParam {
+    pub username: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TeamRepositoryParam {
+    pub repo: String,
+    pub role: String,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TeamMember {
+    pub uid: Uuid,
+    pub user_uid: Uuid,
+    pub username: String,
+    pub email: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TeamRepository {
+    pub uid: Uuid,
+    pub repo_uid: Uuid,
+    pub repo_name: String,
+    pub role: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl App {
+    async fn team_find(&self, org: &OrganizationModel, team: &str) -> AppResult<TeamModel> {
+        TeamModel::get_by_org_uid_and_name(&self.db, org.uid, team).await?
+            .ok_or(AppError::Custom("Team not found".to_string()))
+    }
+
+    async fn team_repository_find(&self, org: &OrganizationModel, repo: String) -> AppResult<RepositoryModel> {
+        RepositoryModel::repository_find_by_owner_name_and_repo_name(&self.db, org.name.clone(), repo).await?
+            .filter(|x| x.owner == org.uid)
+            .ok_or(AppError::Custom("Repository not found".to_string()))
+    }
+
+    pub async fn team_list(&self, user: Uuid, org: String) -> AppResult<Vec<TeamModel>> {
+        let (org, _) = self.org_authorized(user, &org, false).await?;
+        Ok(TeamModel::get_by_org_uid(&self.db, org.uid).await?)
+    }
+
+    pub async fn team_create(&self, user: Uuid, org: String, param: TeamCreateParam) -> AppResult<TeamModel> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let name = param.name.trim();
+        if name.is_empty() || name.contains('/') {
+            return Err(AppError::Custom("Invalid team name".to_string()));
+        }
+        if TeamModel::get_by_org_uid_and_name(&self.db, org.uid, name).await?.is_some() {
+            return Err(AppError::Custom("Team already exists".to_string()));
+        }
+        Ok(TeamModel::create(&self.db, org.uid, name, &param.description).await?)
+    }
+
+    pub async fn team_delete(&self, user: Uuid, org: String, team: String) -> AppResult<()> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let team = self.team_find(&org, &team).await?;
+        TeamModel::delete(&self.db, team.uid).await?;
+        Ok(())
+    }
+
+    pub async fn team_members(&self, user: Uuid, org: String, team: String) -> AppResult<Vec<TeamMember>> {
+        let (org, _) = self.org_authorized(user, &org, false).await?;
+        let team = self.team_find(&org, &team).await?;
+        let mut result = vec![];
+        for member in TeamMemberModel::get_by_team_uid(&self.db, team.uid).await? {
+            let Some(user) = UsersModel::get_by_uid(&self.db, member.user_uid).await? else {
+                continue;
+            };
+            result.push(TeamMember {
+                uid: member.uid,
+                user_uid: user.uid,
+                username: user.username,
+                email: user.email,
+                created_at: member.created_at,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Adds an organization member to a team.
+    pub async fn team_member_add(&self, user: Uuid, org: String, team: String, param: TeamMemberParam) -> AppResult<TeamMemberModel> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let team = self.team_find(&org, &team).await?;
+        let Some(target) = UsersModel::get_by_username(&self.db, &param.username).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        if OrganizationMemberModel::get_by_org_uid_and_user_uid(&self.db, org.uid, target.uid).await?.is_none() {
+            return Err(AppError::Custom("User is not a member of the organization".to_string()));
+        }
+        if TeamMemberModel::get_by_team_uid_and_user_uid(&self.db, team.uid, target.uid).await?.is_some() {
+            return Err(AppError::Custom("User is already a team member".to_string()));
+        }
+        Ok(TeamMemberModel::create(&self.db, team.uid, target.uid).await?)
+    }
+
+    pub async fn team_member_remove(&self, user: Uuid, org: String, team: String, username: String) -> AppResult<()> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let team = self.team_find(&org, &team).await?;
+        let Some(target) = UsersModel::get_by_username(&self.db, &username).await? else {
+            return Err(AppError::Custom("User not found".to_string()));
+        };
+        let Some(member) = TeamMemberModel::get_by_team_uid_and_user_uid(&self.db, team.uid, target.uid).await? else {
+            return Err(AppError::Custom("Team member not found".to_string()));
+        };
+        TeamMemberModel::delete(&self.db, member.uid).await?;
+        Ok(())
+    }
+
+    pub async fn team_repositories(&self, user: Uuid, org: String, team: String) -> AppResult<Vec<TeamRepository>> {
+        let (org, _) = self.org_authorized(user, &org, false).await?;
+        let team = self.team_find(&org, &team).await?;
+        let mut result = vec![];
+        for grant in TeamRepositoryModel::get_by_team_uid(&self.db, team.uid).await? {
+            let Some(repo) = RepositoryModel::get_by_uid(&self.db, grant.repo_uid).await? else {
+                continue;
+            };
+            result.push(TeamRepository {
+                uid: grant.uid,
+                repo_uid: repo.uid,
+                repo_name: repo.name,
+                role: grant.role,
+                created_at: grant.created_at,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Grants a team a role on one of the organization's repositories, replacing any earlier grant.
+    pub async fn team_repository_grant(&self, user: Uuid, org: String, team: String, param: TeamRepositoryParam) -> AppResult<TeamRepositoryModel> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let team = self.team_find(&org, &team).await?;
+        let role = collaborator_role(&param.role)?;
+        let repo = self.team_repository_find(&org, param.repo).await?;
+        Ok(TeamRepositoryModel::upsert(&self.db, team.uid, repo.uid, role.as_role()).await?)
+    }
+
+    pub async fn team_repository_revoke(&self, user: Uuid, org: String, team: String, repo: String) -> AppResult<()> {
+        let (org, _) = self.org_authorized(user, &org, true).await?;
+        let team = self.team_find(&org, &team).await?;
+        let repo = self.team_repository_find(&org, repo).await?;
+        let Some(grant) = TeamRepositoryModel::get_by_team_uid_and_repo_uid(&self.db, team.uid, repo.uid).await? else {
+            return Err(AppError::Custom("Team has no access to this repository".to_string()));
+        };
+        TeamRepositoryModel::delete(&self.db, grant.uid).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use crate::entities::personal_access_tokens::PersonalAccessTokenModel;
+use crate::entities::users::UsersModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::types::session::AuthSession;
+use crate::App;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha256::Sha256Digest;
+use uuid::Uuid;
+
+pub const TOKEN_PREFIX: &str = "jzfs_";
+pub const TOKEN_SCOPES: [&str; 4] = ["repo:read", "repo:write", "admin", "user"];
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TokenCreateParam {
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// Lifetime in days; tokens without one never expire.
+    pub expires_in: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TokenCreateResult {
+    /// The plain token, only ever returned here.
+    pub token: String,
+    pub model: PersonalAccessTokenModel,
+}
+
+/// An authenticated caller. `scopes` is set when a personal access token was
+/// used and limits what the caller may do; password and cookie logins are unrestricted.
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct AuthCredential {
+    pub session: AuthSession,
+    pub scopes: Option<Vec<String>>,
+}
+
+impl AuthCredential {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        let Some(scopes) = &self.scopes else {
+            return true;
+        };
+        let implied: &[&str] = match scope {
+            "repo:read" => &["repo:read", "repo:write", "admin"],
+            "repo:write" => &["repo:write", "admin"],
+            scope => &[scope],
+        };
+        scopes.iter().any(|x| implied.contains(&x.as_str()))
+    }
+
+    /// Whether the credential may be used for an operation needing `level` on a repository.
+    pub fn allows(&self, level: AccessLevel) -> bool {
+        match level {
+            AccessLevel::None | AccessLevel::Read => self.has_scope("repo:read"),
+            AccessLevel::Triage | AccessLevel::Write | AccessLevel::Maintain => self.has_scope("repo:write"),
+            AccessLevel::Admin => self.has_scope("admin"),
+        }
+    }
+}
+
+impl App {
+    pub async fn token_list(&self, user: Uuid) -> AppResult<Vec<PersonalAccessTokenModel>> {
+        Ok(PersonalAccessTokenModel::get_by_owner(&self.db, user).await?)
+    }
+
+    pub async fn token_create(&self, user: Uuid, param: TokenCreateParam) -> AppResult<TokenCreateResult> {
+        let name = param.name.trim();
+        if name.is_empty() {
+            return Err(AppError::Custom("Name is required".to_string()));
+        }
+        if param.scopes.is_empty() {
+            return Err(AppError::Custom("At least one scope is required".to_string()));
+        }
+        if let Some(scope) = param.scopes.iter().find(|x| !TOKEN_SCOPES.contains(&x.as_str())) {
+            return Err(AppError::Custom(format!("Unknown scope: {}", scope)));
+        }
+        let expires_at = match param.expires_in {
+            Some(days) if days <= 0 => {
+                return Err(AppError::Custom("Expiry must be in the future".to_string()));
+            }
+            Some(days) => Some(Utc::now().naive_utc() + Duration::days(days)),
+            None => None,
+        };
+        let token = format!(
+            "{}{}{}",
+            TOKEN_PREFIX,
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        let model = PersonalAccessTokenModel::create(
+            &self.db,
+            user,
+            name,
+            &token.as_str().digest(),
+            &param.scopes,
+            expires_at,
+        ).await?;
+        Ok(TokenCreateResult { token, model })
+    }
+
+    pub async fn token_revoke(&self, user: Uuid, uid: Uuid) -> AppResult<()> {
+        match PersonalAccessTokenModel::get_by_uid(&self.db, uid).await? {
+            Some(token) if token.owner == user => {
+                PersonalAccessTokenModel::delete(&self.db, uid).await?;
+                Ok(())
+            }
+            _ => Err(AppError::Custom("Token not found".to_string())),
+        }
+    }
+
+    /// Authenticates a plain personal access token, recording its use.
+    pub async fn token_auth(&self, token: &str) -> AppResult<AuthCredential> {
+        let Some(model) = PersonalAccessTokenModel::get_by_token_hash(&self.db, &token.digest()).await? else {
+            return Err(AppError::UnAuth);
+        };
+        if model.is_expired() {
+            return Err(AppError::UnAuth);
+        }
+        let Some(user) = UsersModel::get_by_uid(&self.db, model.owner).await? else {
+            return Err(AppError::UnAuth);
+        };
+        if user.deleted_at.is_some() {
+            return Err(AppError::UnAuth);
+        }
+        PersonalAccessTokenModel::touch(&self.db, model.uid).await?;
+        Ok(AuthCredential {
+            session: AuthSession::from(user),
+            scopes: Some(model.scopes),
+        })
+    }
+}
@@ -0,0 +1,533 @@
+use crate::entities::pull_request::{PullRequestModel, PULL_REQUEST_MERGED};
+use crate::entities::repository::RepositoryModel;
+use crate::entities::users::UsersModel;
+use crate::entities::webhook::{WebhookModel, WEBHOOK_CONTENT_FORM, WEBHOOK_CONTENT_JSON};
+use crate::entities::webhook_delivery::WebhookDeliveryModel;
+use crate::error::{AppError, AppResult};
+use crate::service::access::AccessLevel;
+use crate::service::issue::IssueDetail;
+use crate::service::job::Job;
+use crate::types::pager::QueryPager;
+use crate::App;
+use git::commit::list::GitCommit;
+use git::receive::GitRefUpdate;
+use git::AppGit;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+pub const WEBHOOK_EVENT_PING: &str = "ping";
+pub const WEBHOOK_EVENT_PUSH: &str = "push";
+pub const WEBHOOK_EVENT_CREATE: &str = "create";
+pub const WEBHOOK_EVENT_DELETE: &str = "delete";
+pub const WEBHOOK_EVENT_PULL_REQUEST: &str = "pull_request";
+pub const WEBHOOK_EVENT_ISSUES: &str = "issues";
+
+/// Events a webhook can subscribe to; every webhook receives `ping`.
+const WEBHOOK_EVENTS: &[&str] = &[
+    WEBHOOK_EVENT_PUSH,
+    WEBHOOK_EVENT_CREATE,
+    WEBHOOK_EVENT_DELETE,
+    WEBHOOK_EVENT_PULL_REQUEST,
+    WEBHOOK_EVENT_ISSUES,
+];
+
+/// Push payloads list at most this many commits, the newest ones.
+const PUSH_COMMITS_LIMIT: usize = 20;
+
+/// Response bodies are stored up to this many bytes.
+const RESPONSE_BODY_LIMIT: usize = 64 * 1024;
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn webhook_events_default() -> Vec<String> {
+    vec![WEBHOOK_EVENT_PUSH.to_string()]
+}
+
+fn webhook_content_type_default() -> String {
+    WEBHOOK_CONTENT_JSON.to_string()
+}
+
+fn webhook_active_default() -> bool {
+    true
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct WebhookCreateParam {
+    pub url: String,
+    #[serde(default)]
+    pub secret: String,
+    /// `json` or `form`, which sends the payload as the `payload` form field.
+    #[serde(default = "webhook_content_type_default")]
+    pub content_type: String,
+    /// Defaults to `push`; `*` selects every event.
+    #[serde(default = "webhook_events_default")]
+    pub events: Vec<String>,
+    #[serde(default = "webhook_active_default")]
+    pub active: bool,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct WebhookUpdateParam {
+    pub url: Option<String>,
+    /// An empty string stops signing deliveries.
+    pub secret: Option<String>,
+    pub content_type: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+/// The short name of an updated ref, and `branch` or `tag`.
+fn webhook_ref(update: &GitRefUpdate) -> (&str, &str) {
+    match update.tag() {
+        Some(name) => (name, "tag"),
+        None => (update.branch().unwrap_or(&update.name), "branch"),
+    }
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `body` keyed with `secret`,
+/// sent as `X-Jzfs-Signature-256`.
+pub fn webhook_signature(secret: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Reads a response body up to [`RESPONSE_BODY_LIMIT`] bytes, leaving the
+/// rest unread however much the receiver sends.
+async fn webhook_response_body(mut response: reqwest::Response) -> reqwest::Result<Vec<u8>> {
+    let mut body = vec![];
+    while body.len() < RESPONSE_BODY_LIMIT
+        && let Some(chunk) = response.chunk().await?
+    {
+        body.extend_from_slice(&chunk);
+    }
+    body.truncate(RESPONSE_BODY_LIMIT);
+    Ok(body)
+}
+
+/// Posts a payload to a webhook as delivery `uid`, returning the status code,
+/// the start of the response body and the error that ended the attempt.
+async fn webhook_send(hook: &WebhookModel, uid: Uuid, event: &str, payload: &str) -> AppResult<(Option<i32>, Option<String>, Option<String>)> {
+    let (content_type, body) = if hook.content_type == WEBHOOK_CONTENT_FORM {
+        let body = serde_urlencoded::to_string([("payload", payload)])
+            .map_err(|e| AppError::Custom(e.to_string()))?;
+        ("application/x-www-form-urlencoded", body)
+    } else {
+        ("application/json", payload.to_string())
+    };
+    let mut request = reqwest::Client::new()
+        .post(&hook.url)
+        .timeout(DELIVERY_TIMEOUT)
+        .header("Content-Type", content_type)
+        .header("User-Agent", "jzfs-webhook")
+        .header("X-Jzfs-Event", event)
+        .header("X-Jzfs-Delivery", uid.to_string());
+    if !hook.secret.is_empty() {
+        request = request.header("X-Jzfs-Signature-256", webhook_signature(&hook.secret, body.as_bytes()));
+    }
+    Ok(match request.body(body).send().await {
+        Ok(response) => {
+            let status = response.status().as_u16() as i32;
+            match webhook_response_body(response).await {
+                Ok(bytes) => (Some(status), Some(String::from_utf8_lossy(&bytes).into_owned()), None),
+                Err(e) => (Some(status), None, Some(e.to_string())),
+            }
+        }
+        Err(e) => (None, None, Some(e.to_string())),
+    })
+}
+
+fn webhook_url(url: &str) -> AppResult<&str> {
+    let url = url.trim();
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(url),
+        _ => Err(AppError::Custom(format!("Invalid url: {}, expected http(s)://", url))),
+    }
+}
+
+fn webhook_content_type(content_type: &str) -> AppResult<&str> {
+    if content_type != WEBHOOK_CONTENT_JSON && content_type != WEBHOOK_CONTENT_FORM {
+        return Err(AppError::Custom(format!("Invalid content type: {}, expected json or form", content_type)));
+    }
+    Ok(content_type)
+}
+
+fn webhook_events(events: &[String]) -> AppResult<Vec<String>> {
+    if events.is_empty() {
+        return Err(AppError::Custom("At least one event is required".to_string()));
+    }
+    if let Some(event) = events.iter().find(|x| *x != "*" && !WEBHOOK_EVENTS.contains(&x.as_str())) {
+        return Err(AppError::Custom(format!("Unknown event: {}, expected one of {} or *", event, WEBHOOK_EVENTS.join(", "))));
+    }
+    let mut events = events.to_vec();
+    events.sort();
+    events.dedup();
+    Ok(events)
+}
+
+fn commit_payload(commit: &GitCommit) -> Value {
+    json!({
+        "id": commit.hash,
+        "message": commit.message,
+        "timestamp": commit.timestamp,
+        "author": {"name": commit.author, "email": commit.email},
+        "committer": {"name": commit.committer, "email": commit.committer_email},
+    })
+}
+
+impl App {
+    async fn webhook_find(&self, repo: &RepositoryModel, uid: Uuid) -> AppResult<WebhookModel> {
+        WebhookModel::get_by_uid(&self.db, uid).await?
+            .filter(|x| x.repo_uid == repo.uid)
+            .ok_or(AppError::Custom("Webhook not found".to_string()))
+    }
+
+    pub async fn webhook_list(&self, user: Uuid, repo: String, owner: String) -> AppResult<Vec<WebhookModel>> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        Ok(WebhookModel::get_by_repo_uid(&self.db, repo.uid).await?)
+    }
+
+    /// Creates a webhook and sends it a `ping` event.
+    pub async fn webhook_create(&self, user: Uuid, repo: String, owner: String, param: WebhookCreateParam) -> AppResult<WebhookModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let url = webhook_url(&param.url)?;
+        let content_type = webhook_content_type(&param.content_type)?;
+        let events = webhook_events(&param.events)?;
+        let hook = WebhookModel::create(&self.db, repo.uid, url, &param.secret, content_type, &events, param.active).await?;
+        let payload = json!({
+            "hook": hook,
+            "repository": self.webhook_repository(&repo).await?,
+            "sender": self.webhook_sender(Some(user)).await?,
+        });
+        self.webhook_enqueue(&repo, &hook, WEBHOOK_EVENT_PING, payload.to_string()).await?;
+        Ok(hook)
+    }
+
+    pub async fn webhook_update(&self, user: Uuid, repo: String, owner: String, uid: Uuid, param: WebhookUpdateParam) -> AppResult<WebhookModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let hook = self.webhook_find(&repo, uid).await?;
+        let url = param.url.as_deref().map(webhook_url).transpose()?;
+        let content_type = param.content_type.as_deref().map(webhook_content_type).transpose()?;
+        let events = param.events.as_deref().map(webhook_events).transpose()?;
+        WebhookModel::update(
+            &self.db,
+            hook.uid,
+            url,
+            param.secret.as_deref(),
+            content_type,
+            events.as_deref(),
+            param.active,
+        ).await?
+            .ok_or(AppError::Custom("Webhook not found".to_string()))
+    }
+
+    pub async fn webhook_delete(&self, user: Uuid, repo: String, owner: String, uid: Uuid) -> AppResult<()> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let hook = self.webhook_find(&repo, uid).await?;
+        WebhookModel::delete(&self.db, hook.uid).await?;
+        Ok(())
+    }
+
+    pub async fn webhook_deliveries(&self, user: Uuid, repo: String, owner: String, uid: Uuid, pager: QueryPager) -> AppResult<Vec<WebhookDeliveryModel>> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let hook = self.webhook_find(&repo, uid).await?;
+        Ok(WebhookDeliveryModel::get_by_webhook_uid(&self.db, hook.uid, pager.limit, pager.page * pager.limit).await?)
+    }
+
+    async fn webhook_delivery_find(&self, hook: &WebhookModel, uid: Uuid) -> AppResult<WebhookDeliveryModel> {
+        WebhookDeliveryModel::get_by_uid(&self.db, uid).await?
+            .filter(|x| x.webhook_uid == hook.uid)
+            .ok_or(AppError::Custom("Delivery not found".to_string()))
+    }
+
+    pub async fn webhook_delivery(&self, user: Uuid, repo: String, owner: String, uid: Uuid, delivery: Uuid) -> AppResult<WebhookDeliveryModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let hook = self.webhook_find(&repo, uid).await?;
+        self.webhook_delivery_find(&hook, delivery).await
+    }
+
+    /// Sends the payload of a past delivery again with the current settings of
+    /// the webhook, waiting for the outcome.
+    pub async fn webhook_redeliver(&self, user: Uuid, repo: String, owner: String, uid: Uuid, delivery: Uuid) -> AppResult<WebhookDeliveryModel> {
+        let repo = self.repository_authorized(Some(user), repo, owner, AccessLevel::Admin).await?;
+        let hook = self.webhook_find(&repo, uid).await?;
+        let delivery = self.webhook_delivery_find(&hook, delivery).await?;
+        self.webhook_deliver(&hook, &delivery.event, &delivery.payload, true).await
+    }
+
+    /// Posts a payload to a webhook and records the attempt, successful or not.
+    async fn webhook_deliver(&self, hook: &WebhookModel, event: &str, payload: &str, redelivery: bool) -> AppResult<WebhookDeliveryModel> {
+        let uid = Uuid::new_v4();
+        let start = Instant::now();
+        let (status_code, response_body, error) = webhook_send(hook, uid, event, payload).await?;
+        let duration_ms = start.elapsed().as_millis() as i64;
+        Ok(WebhookDeliveryModel::create(
+            &self.db,
+            uid,
+            hook.uid,
+            event,
+            payload,
+            redelivery,
+            status_code,
+            response_body.as_deref(),
+            error.as_deref(),
+            duration_ms,
+        ).await?)
+    }
+
+    /// Delivers in the background so that the triggering request does not wait
+    /// on the receiver; failed deliveries are retried.
+    async fn webhook_enqueue(&self, repo: &RepositoryModel, hook: &WebhookModel, event: &str, payload: String) -> AppResult<()> {
+        let job = Job::WebhookDelivery {
+            webhook_uid: hook.uid,
+            event: event.to_string(),
+            payload,
+        };
+        self.job_enqueue(repo, job).await?;
+        Ok(())
+    }
+
+    /// Runs a queued delivery, failing unless the receiver accepted it. Hooks
+    /// deleted or deactivated since are skipped.
+    pub(crate) async fn webhook_job(&self, webhook_uid: Uuid, event: &str, payload: &str, redelivery: bool) -> AppResult<()> {
+        let Some(hook) = WebhookModel::get_by_uid(&self.db, webhook_uid).await?.filter(|x| x.active) else {
+            return Ok(());
+        };
+        let delivery = self.webhook_deliver(&hook, event, payload, redelivery).await?;
+        if delivery.is_success() {
+            return Ok(());
+        }
+        Err(AppError::Custom(match (delivery.status_code, delivery.error) {
+            (_, Some(error)) => format!("Delivery {} failed: {}", delivery.uid, error),
+            (Some(status), None) => format!("Delivery {} got status {}", delivery.uid, status),
+            (None, None) => format!("Delivery {} failed", delivery.uid),
+        }))
+    }
+
+    async fn webhook_repository(&self, repo: &RepositoryModel) -> AppResult<Value> {
+        let owner = self.repository_owner_name(repo).await?;
+        Ok(json!({
+            "uid": repo.uid,
+            "owner": owner,
+            "name": repo.name,
+            "full_name": format!("{}/{}", owner, repo.name),
+            "description": repo.description,
+            "private": !repo.is_public,
+        }))
+    }
+
+    async fn webhook_sender(&self, user: Option<Uuid>) -> AppResult<Value> {
+        let Some(user) = user else {
+            return Ok(Value::Null);
+        };
+        Ok(match UsersModel::get_by_uid(&self.db, user).await? {
+            Some(user) => json!({"uid": user.uid, "username": user.username}),
+            None => Value::Null,
+        })
+    }
+
+    async fn webhook_subscribers(&self, repo: &RepositoryModel, event: &str) -> AppResult<Vec<WebhookModel>> {
+        Ok(WebhookModel::get_by_repo_uid(&self.db, repo.uid).await?
+            .into_iter()
+            .filter(|x| x.active && x.subscribes(event))
+            .collect())
+    }
+
+    async fn webhook_try_emit(&self, repo: &RepositoryModel, sender: Option<Uuid>, event: &'static str, mut payload: Value) -> AppResult<()> {
+        let hooks = self.webhook_subscribers(repo, event).await?;
+        if hooks.is_empty() {
+            return Ok(());
+        }
+        payload["repository"] = self.webhook_repository(repo).await?;
+        payload["sender"] = self.webhook_sender(sender).await?;
+        let payload = payload.to_string();
+        for hook in hooks {
+            self.webhook_enqueue(repo, &hook, event, payload.clone()).await?;
+        }
+        Ok(())
+    }
+
+    /// Sends `event` to the active webhooks of `repo` subscribed to it, adding
+    /// the `repository` and `sender` fields to `payload`. Failing to notify
+    /// never fails the operation that triggered the event.
+    pub(crate) async fn webhook_emit(&self, repo: &RepositoryModel, sender: Option<Uuid>, event: &'static str, payload: Value) {
+        self.webhook_try_emit(repo, sender, event, payload).await.ok();
+    }
+
+    /// Commits new to a ref: those not already on the ref it replaced, or for a
+    /// new branch, those not on the default branch.
+    fn webhook_push_commits(&self, git: &AppGit, update: &GitRefUpdate) -> anyhow::Result<Vec<GitCommit>> {
+        if update.is_delete() {
+            return Ok(vec![]);
+        }
+        let from = if update.is_create() {
+            match git.branch_list()?.into_iter().find(|x| x.default && x.head != update.new) {
+                Some(default) => git.merge_base(&default.head, &update.new)?,
+                None => None,
+            }
+        } else {
+            Some(update.old.clone())
+        };
+        git.commit_range(from.as_deref(), &update.new)
+    }
+
+    /// Emits `push` for each ref update, and `create` or `delete` for refs that
+    /// appeared or went away.
+    pub(crate) async fn webhook_refs(&self, repo: &RepositoryModel, git: &AppGit, sender: Option<Uuid>, updates: &[GitRefUpdate]) {
+        for update in updates {
+            let (name, ref_type) = webhook_ref(update);
+            if update.is_create() {
+                let payload = json!({"ref": name, "ref_type": ref_type, "sha": update.new});
+                self.webhook_emit(repo, sender, WEBHOOK_EVENT_CREATE, payload).await;
+            } else if update.is_delete() {
+                let payload = json!({"ref": name, "ref_type": ref_type, "sha": update.old});
+                self.webhook_emit(repo, sender, WEBHOOK_EVENT_DELETE, payload).await;
+            }
+            if self.webhook_subscribers(repo, WEBHOOK_EVENT_PUSH).await.map_or(true, |x| x.is_empty()) {
+                continue;
+            }
+            let commits = if ref_type == "branch" {
+                self.webhook_push_commits(git, update).unwrap_or_default()
+            } else {
+                vec![]
+            };
+            let forced = !update.is_create()
+                && !update.is_delete()
+                && git.merge_base(&update.old, &update.new).ok().flatten().as_deref() != Some(update.old.as_str());
+            let payload = json!({
+                "ref": update.name,
+                "before": update.old,
+                "after": update.new,
+                "created": update.is_create(),
+                "deleted": update.is_delete(),
+                "forced": forced,
+                "total_commits": commits.len(),
+                "commits": commits.iter().rev().take(PUSH_COMMITS_LIMIT).rev().map(commit_payload).collect::<Vec<_>>(),
+                "head_commit": commits.last().map(commit_payload),
+            });
+            self.webhook_emit(repo, sender, WEBHOOK_EVENT_PUSH, payload).await;
+        }
+    }
+
+    /// Emits `pull_request` with `action` such as `opened`, `edited`,
+    /// `synchronize`, `closed` or `reopened`.
+    pub(crate) async fn webhook_pull_request(&self, repo: &RepositoryModel, sender: Option<Uuid>, action: &str, pull: &PullRequestModel) {
+        let mut pull_request = json!(pull);
+        pull_request["merged"] = json!(pull.state == PULL_REQUEST_MERGED);
+        let payload = json!({"action": action, "number": pull.number, "pull_request": pull_request});
+        self.webhook_emit(repo, sender, WEBHOOK_EVENT_PULL_REQUEST, payload).await;
+    }
+
+    /// Emits `issues` with `action` such as `opened`, `edited`, `closed` or `reopened`.
+    pub(crate) async fn webhook_issue(&self, repo: &RepositoryModel, sender: Option<Uuid>, action: &str, issue: &IssueDetail) {
+        let payload = json!({"action": action, "number": issue.issue.number, "issue": issue});
+        self.webhook_emit(repo, sender, WEBHOOK_EVENT_ISSUES, payload).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_webhook_signature() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            webhook_signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    /// Accepts one request, answers it with `response_size` bytes and returns
+    /// the request's head, lower-cased, and body.
+    async fn receive_one(listener: TcpListener, response_size: usize) -> (String, String) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = vec![];
+        let mut buffer = [0; 4096];
+        let (head, length) = loop {
+            let n = stream.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..n]);
+            let text = String::from_utf8_lossy(&request).into_owned();
+            if let Some((head, _)) = text.split_once("\r\n\r\n") {
+                let head = head.to_lowercase();
+                let length = head
+                    .lines()
+                    .find_map(|x| x.strip_prefix("content-length: "))
+                    .map(|x| x.trim().parse::<usize>().unwrap())
+                    .unwrap_or(0);
+                break (head, length);
+            }
+        };
+        while request.len() < head.len() + 4 + length {
+            let n = stream.read(&mut buffer).await.unwrap();
+            request.extend_from_slice(&buffer[..n]);
+        }
+        let body = String::from_utf8(request[head.len() + 4..].to_vec()).unwrap();
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", response_size);
+        stream.write_all(response.as_bytes()).await.unwrap();
+        stream.write_all(&vec![b'x'; response_size]).await.ok();
+        (head, body)
+    }
+
+    fn hook(url: String, content_type: &str) -> WebhookModel {
+        let now = Local::now().naive_local();
+        WebhookModel {
+            uid: Uuid::new_v4(),
+            repo_uid: Uuid::new_v4(),
+            url,
+            secret: "secret".to_string(),
+            content_type: content_type.to_string(),
+            events: vec!["*".to_string()],
+            active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_send_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(receive_one(listener, RESPONSE_BODY_LIMIT * 2));
+        let payload = r#"{"zen":"ok"}"#;
+        let (status, body, error) = webhook_send(&hook(url, WEBHOOK_CONTENT_JSON), Uuid::new_v4(), "ping", payload)
+            .await
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!((status, error), (Some(200), None));
+        assert_eq!(body.unwrap().len(), RESPONSE_BODY_LIMIT);
+
+        let (head, received) = server.await.unwrap();
+        assert!(head.starts_with("post /hook "));
+        assert!(head.contains("content-type: application/json\r\n"));
+        assert!(head.contains("x-jzfs-event: ping\r\n"));
+        let signature = webhook_signature("secret", payload.as_bytes());
+        assert!(head.contains(&format!("x-jzfs-signature-256: {}\r\n", signature)));
+        assert_eq!(received, payload);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_send_form() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(receive_one(listener, 2));
+        let payload = r#"{"a":"b c&d"}"#;
+        let (status, body, _) = webhook_send(&hook(url, WEBHOOK_CONTENT_FORM), Uuid::new_v4(), "push", payload)
+            .await
+            .unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!((status, body.as_deref()), (Some(200), Some("xx")));
+
+        let (head, received) = server.await.unwrap();
+        assert!(head.contains("content-type: application/x-www-form-urlencoded\r\n"));
+        assert!(head.contains("x-jzfs-event: push\r\n"));
+        assert_eq!(received, "payload=%7B%22a%22%3A%22b+c%26d%22%7D");
+        let signature = webhook_signature("secret", received.as_bytes());
+        assert!(head.contains(&format!("x-jzfs-signature-256: {}\r\n", signature)));
+    }
+}
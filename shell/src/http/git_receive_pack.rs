@@ -1,24 +1,61 @@
 use std::io;
 use std::io::Read;
 use std::process::{Command, Stdio};
-use crate::http::{verify_repo_access};
+use crate::http::{access_denied, http_user, verify_repo_access};
+use crate::receive::pipeline::{ReceiveContext, ReceivePipeline};
+use crate::receive::{max_push_size, report, ReceiveRequest};
 use actix_web::{HttpRequest, HttpResponse, Responder};
 use actix_web::web::{Data, Path, Payload};
 use async_stream::stream;
 use bytes::Bytes;
 use futures_util::StreamExt;
+use infra::service::access::AccessLevel;
 use infra::App;
 
 pub async fn git_receive_pack(
-    _request: HttpRequest,
+    request: HttpRequest,
     mut payload: Payload,
     path: Path<(String, String)>,
     core: Data<App>,
 ) -> impl Responder {
     let (owner, repo) = path.into_inner();
-    let repo_path = match verify_repo_access(&core, &owner, &repo, true).await {
+    let user = match http_user(&core, &request).await {
+        Ok(user) => user,
+        Err(e) => return access_denied(e),
+    };
+    let (model, repo_path) = match verify_repo_access(&core, &owner, &repo, user.as_ref(), AccessLevel::Write).await {
         Ok(p) => p,
-        Err(e) => return HttpResponse::Forbidden().body(e.to_string()),
+        Err(e) => return access_denied(e),
+    };
+    let user = user.map(|x| x.session.uid);
+    let limit = max_push_size();
+    let mut data = vec![];
+    while let Some(bytes) = payload.next().await {
+        let bytes = match bytes {
+            Ok(bytes) => bytes,
+            Err(e) => return HttpResponse::BadRequest().body(e.to_string()),
+        };
+        if data.len() + bytes.len() > limit {
+            return HttpResponse::PayloadTooLarge().body(format!("Push is over the {} byte limit", limit));
+        }
+        data.extend_from_slice(&bytes);
+    }
+    let request = match ReceiveRequest::parse(&data) {
+        Ok(Some(request)) => request,
+        Ok(None) => return HttpResponse::BadRequest().body("Incomplete receive-pack request"),
+        Err(e) => return HttpResponse::BadRequest().body(e.to_string()),
+    };
+    let pipeline = ReceivePipeline::standard();
+    let ctx = ReceiveContext::new(&core, &model, user, &data[request.pack_offset..]);
+    let outcome = match pipeline.check(&ctx, &request).await {
+        Ok(outcome) => outcome,
+        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+    };
+    drop(ctx);
+    let input = if outcome.rejected.is_empty() {
+        data
+    } else {
+        request.with_updates(&data, &outcome.accepted)
     };
     let mut child = match Command::new("git")
         .arg("receive-pack")
@@ -33,19 +70,33 @@ pub async fn git_receive_pack(
     };
     if let Some(mut stdin) = child.stdin.take() {
         use std::io::Write;
-        while let Some(Ok(bytes)) = payload.next().await {
-            stdin.write_all(&bytes).ok();
-        }
+        stdin.write_all(&input).ok();
     }
     let mut stdout = child.stdout.unwrap();
+
+    // With refused updates git's report lacks them, so it is completed before
+    // being sent; otherwise the output streams through as git writes it.
+    if !outcome.rejected.is_empty() {
+        let mut output = vec![];
+        stdout.read_to_end(&mut output).ok();
+        let applied = outcome.applied(&output);
+        tokio::spawn(async move {
+            pipeline.finish(&core, &model, user, &applied).await;
+        });
+        return HttpResponse::Ok()
+            .content_type("application/x-git-receive-pack-result")
+            .body(report::respond(&outcome.request, &output, &outcome.rejected));
+    }
     let body = actix_web::body::BodyStream::new(stream! {
         let mut buffer = [0; 8192];
+        let mut output = vec![];
         loop {
             match stdout.read(&mut buffer) {
                 Ok(0) => {
                     break;
                 }
                 Ok(n) => {
+                    output.extend_from_slice(&buffer[..n]);
                     yield Ok::<_, io::Error>(Bytes::copy_from_slice(&buffer[..n]));
                 }
                 Err(_e) => {
@@ -53,11 +104,12 @@ pub async fn git_receive_pack(
                 }
             }
         }
-    });
-    tokio::spawn(async move {
-        core.sync_hook_with_owner_repo(repo.replace(".git", ""), owner).await;
+        let applied = outcome.applied(&output);
+        tokio::spawn(async move {
+            pipeline.finish(&core, &model, user, &applied).await;
+        });
     });
     HttpResponse::Ok()
         .content_type("application/x-git-receive-pack-result")
         .body(body)
-}
\ No newline at end of file
+}
@@ -5,9 +5,9 @@ use actix_web::web::{Data, Path};
 use tokio::process::Command;
 use tracing::{info};
 use crate::GitPack;
-use git::AppGit;
+use crate::http::{access_denied, http_user, verify_repo_access};
 use infra::App;
-use infra::entities::repository::RepositoryModel;
+use infra::service::access::AccessLevel;
 
 pub async fn git_refs(
     request: HttpRequest,
@@ -41,13 +41,19 @@ pub async fn git_refs(
         return HttpResponseBuilder::new(StatusCode::BAD_REQUEST)
             .body("Protoc Not Support");
     };
-    let repo = repo.replace(".git", "");
     info!("repository ops: {}", format!("{}/{}", owner, repo));
-    let Ok(Some(repo)) = RepositoryModel::repository_find_by_owner_name_and_repo_name(&status.db,owner, repo).await else {
-        return HttpResponseBuilder::new(StatusCode::NOT_FOUND)
-            .body("Repository Not Found");
+    let level = match server {
+        GitPack::UploadPack => AccessLevel::Read,
+        GitPack::ReceivePack => AccessLevel::Write,
+    };
+    let user = match http_user(&status, &request).await {
+        Ok(user) => user,
+        Err(e) => return access_denied(e),
+    };
+    let path = match verify_repo_access(&status, &owner, &repo, user.as_ref(), level).await {
+        Ok((_, path)) => path,
+        Err(e) => return access_denied(e),
     };
-    let path = AppGit::new(repo.to_path()).path_buf;
     if !path.exists() {
         return HttpResponseBuilder::new(StatusCode::NOT_FOUND)
             .body("repository not found");
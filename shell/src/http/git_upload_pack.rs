@@ -1,27 +1,29 @@
 use std::io;
 use std::io::Read;
 use std::process::{Command, Stdio};
-use crate::http::{verify_repo_access};
+use crate::http::{access_denied, http_user, verify_repo_access};
 use actix_web::{HttpRequest, HttpResponse, Responder};
 use actix_web::web::{Data, Path, Payload};
 use async_stream::stream;
 use bytes::Bytes;
 use futures_util::StreamExt;
+use infra::service::access::AccessLevel;
 use infra::App;
 
 pub async fn git_upload_pack(
-    _request: HttpRequest,
+    request: HttpRequest,
     mut payload: Payload,
     path: Path<(String, String)>,
     core: Data<App>,
 ) -> impl Responder {
     let (owner, repo) = path.into_inner();
-    let repo_path = match verify_repo_access(&core, &owner, &repo, false).await {
-        Ok(p) => p,
-        Err(e) => {
-            dbg!(&e);
-            return HttpResponse::Forbidden().body(e.to_string())
-        },
+    let user = match http_user(&core, &request).await {
+        Ok(user) => user,
+        Err(e) => return access_denied(e),
+    };
+    let repo_path = match verify_repo_access(&core, &owner, &repo, user.as_ref(), AccessLevel::Read).await {
+        Ok((_, p)) => p,
+        Err(e) => return access_denied(e),
     };
     let mut child = match Command::new("git")
         .arg("upload-pack")
@@ -57,9 +59,6 @@ pub async fn git_upload_pack(
             }
         }
     });
-    tokio::spawn(async move {
-        core.sync_hook_with_owner_repo(repo.replace(".git", ""), owner).await;
-    });
     HttpResponse::Ok()
         .content_type("application/x-git-upload-pack-result")
         .body(body)
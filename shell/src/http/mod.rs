@@ -1,9 +1,15 @@
 use crate::http::git_receive_pack::git_receive_pack;
 use crate::http::git_refs::git_refs;
 use crate::http::git_upload_pack::git_upload_pack;
-use actix_web::web;
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use actix_web::web::{get, post, scope, Data};
 use infra::entities::repository::RepositoryModel;
+use infra::error::{AppError, AppResult};
+use infra::service::access::AccessLevel;
+use infra::service::token::AuthCredential;
 use infra::App;
 use std::path::PathBuf;
 use git::AppGit;
@@ -12,17 +18,53 @@ pub mod git_refs;
 pub mod git_receive_pack;
 pub mod git_upload_pack;
 
+/// Resolves the repository behind a git URL and checks that `user` holds `level` on it.
 pub async fn verify_repo_access(
     core: &Data<App>,
     owner: &str,
     repo: &str,
-    _require_write: bool
-) -> anyhow::Result<PathBuf> {
-    let repo_path = RepositoryModel::repository_find_by_owner_name_and_repo_name(&core.db,owner.to_string(), repo.to_string().replace(".git", ""))
-        .await
-        .map_err(|_| anyhow::anyhow!("Repo not found"))?
-        .ok_or(anyhow::anyhow!("Repo not found"))?;
-    Ok(AppGit::new(repo_path.to_path()).path_buf)
+    user: Option<&AuthCredential>,
+    level: AccessLevel,
+) -> AppResult<(RepositoryModel, PathBuf)> {
+    if user.is_some_and(|x| !x.allows(level)) {
+        return Err(AppError::Custom("Token scope does not allow this operation".to_string()));
+    }
+    let repo = core
+        .repository_authorized(user.map(|x| x.session.uid), repo.replace(".git", ""), owner.to_string(), level)
+        .await?;
+    let path = AppGit::new(repo.to_path()).path_buf;
+    Ok((repo, path))
+}
+
+/// Resolves the caller from `Authorization: Basic` credentials. Requests without
+/// the header are anonymous; wrong credentials fail with `AppError::UnAuth`.
+pub async fn http_user(core: &Data<App>, request: &HttpRequest) -> AppResult<Option<AuthCredential>> {
+    let Some(header) = request.headers().get(header::AUTHORIZATION) else {
+        return Ok(None);
+    };
+    let credentials = header
+        .to_str()
+        .ok()
+        .and_then(|x| x.strip_prefix("Basic "))
+        .and_then(|x| STANDARD.decode(x.trim()).ok())
+        .and_then(|x| String::from_utf8(x).ok())
+        .ok_or(AppError::UnAuth)?;
+    let (username, password) = credentials.split_once(':').ok_or(AppError::UnAuth)?;
+    let credential = core
+        .service_auth_basic(username.to_string(), password.to_string())
+        .await?;
+    Ok(Some(credential))
+}
+
+/// Maps a failed access check to the HTTP status git clients expect, asking
+/// for credentials when the request was anonymous or its credentials were wrong.
+pub fn access_denied(error: AppError) -> HttpResponse {
+    match error {
+        AppError::UnAuth => HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Basic realm=\"jzfs\", charset=\"UTF-8\""))
+            .body("Authentication required"),
+        error => HttpResponse::Forbidden().body(error.to_string()),
+    }
 }
 
 
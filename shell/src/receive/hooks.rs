@@ -0,0 +1,131 @@
+use crate::receive::pipeline::{PostReceiveHook, PreReceiveHook, ReceiveContext, RefRejection};
+use async_trait::async_trait;
+use git::receive::policy::GitCommitPolicy;
+use git::receive::GitRefUpdate;
+use infra::entities::repository::RepositoryModel;
+use infra::service::job::Job;
+use infra::App;
+use uuid::Uuid;
+
+/// Refuses updates that break the repository's protected branch rules.
+pub struct BranchProtection;
+
+#[async_trait]
+impl PreReceiveHook for BranchProtection {
+    async fn check(&self, ctx: &ReceiveContext<'_>, updates: &[GitRefUpdate]) -> anyhow::Result<Vec<RefRejection>> {
+        let violations = ctx
+            .app
+            .protected_branch_check(ctx.repo, ctx.user, updates, ctx.pack)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        Ok(violations
+            .into_iter()
+            .map(|x| RefRejection {
+                refname: x.refname,
+                reason: x.reason,
+            })
+            .collect())
+    }
+}
+
+/// Refuses updates whose new commits add a file larger than the
+/// `max_file_size` of `policy`.
+pub struct FileSizeLimit {
+    pub policy: GitCommitPolicy,
+}
+
+impl FileSizeLimit {
+    /// Reads the limit from `JZFS_MAX_FILE_SIZE`, in bytes, as
+    /// [`GitCommitPolicy::from_env`] does.
+    pub fn from_env() -> Self {
+        FileSizeLimit {
+            policy: GitCommitPolicy::from_env(),
+        }
+    }
+}
+
+#[async_trait]
+impl PreReceiveHook for FileSizeLimit {
+    async fn check(&self, ctx: &ReceiveContext<'_>, updates: &[GitRefUpdate]) -> anyhow::Result<Vec<RefRejection>> {
+        let mut rejected = vec![];
+        for update in updates.iter().filter(|x| !x.is_delete()) {
+            let quarantine = ctx.quarantine()?;
+            for commit in quarantine.new_commits(&update.new)? {
+                if let Some(violation) = quarantine.file_violation(&commit.hash, &self.policy)? {
+                    rejected.push(RefRejection {
+                        refname: update.name.clone(),
+                        reason: violation,
+                    });
+                    break;
+                }
+            }
+        }
+        Ok(rejected)
+    }
+}
+
+/// Refuses branch updates bringing commits whose message breaks the message
+/// rules of `policy`.
+pub struct CommitMessagePolicy {
+    pub policy: GitCommitPolicy,
+}
+
+impl CommitMessagePolicy {
+    /// Reads the policy from `JZFS_COMMIT_SUBJECT_REQUIRED` (`true` or `1`)
+    /// and `JZFS_COMMIT_SUBJECT_MAX`; without either there is none.
+    pub fn from_env() -> Option<Self> {
+        let policy = GitCommitPolicy::from_env();
+        policy.checks_messages().then_some(CommitMessagePolicy { policy })
+    }
+}
+
+#[async_trait]
+impl PreReceiveHook for CommitMessagePolicy {
+    async fn check(&self, ctx: &ReceiveContext<'_>, updates: &[GitRefUpdate]) -> anyhow::Result<Vec<RefRejection>> {
+        let mut rejected = vec![];
+        for update in updates.iter().filter(|x| x.branch().is_some() && !x.is_delete()) {
+            let commits = ctx.quarantine()?.new_commits(&update.new)?;
+            if let Some((commit, violation)) = commits
+                .iter()
+                .find_map(|x| Some((x, self.policy.message_violation(&x.message)?)))
+            {
+                rejected.push(RefRejection {
+                    refname: update.name.clone(),
+                    reason: format!("commit {}: {}", &commit.hash[..7], violation),
+                });
+            }
+        }
+        Ok(rejected)
+    }
+}
+
+/// Queues the job bringing the database in line with the pushed refs, which
+/// in turn updates pull requests, closes referenced issues and sends webhooks.
+pub struct RepositorySync;
+
+#[async_trait]
+impl PostReceiveHook for RepositorySync {
+    async fn run(&self, app: &App, repo: &RepositoryModel, user: Option<Uuid>, updates: &[GitRefUpdate]) -> anyhow::Result<()> {
+        let job = Job::SyncRefs {
+            sender: user,
+            updates: updates.to_vec(),
+        };
+        app.job_enqueue(repo, job)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Queues a `git gc --auto` of the repository.
+pub struct RepositoryGc;
+
+#[async_trait]
+impl PostReceiveHook for RepositoryGc {
+    async fn run(&self, app: &App, repo: &RepositoryModel, _: Option<Uuid>, _: &[GitRefUpdate]) -> anyhow::Result<()> {
+        app.job_enqueue(repo, Job::RepositoryGc)
+            .await
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+use git::receive::GitRefUpdate;
+
+pub mod hooks;
+pub mod pipeline;
+pub mod report;
+
+/// The command section of a receive-pack request, split from the pack that follows it.
+#[derive(Clone, Debug)]
+pub struct ReceiveRequest {
+    pub updates: Vec<GitRefUpdate>,
+    pub capabilities: Vec<String>,
+    /// `shallow <oid>` lines sent ahead of the commands by shallow clients.
+    pub shallow: Vec<String>,
+    /// Offset of what follows the command list (push options, then the pack).
+    pub commands_end: usize,
+    /// Offset of the pack data within the request body.
+    pub pack_offset: usize,
+}
+
+impl ReceiveRequest {
+    /// Parses the ref update commands (and push options, when negotiated) at
+    /// the start of a receive-pack request. Returns `None` while `data` does
+    /// not yet hold the whole command section.
+    pub fn parse(data: &[u8]) -> anyhow::Result<Option<ReceiveRequest>> {
+        let mut offset = 0;
+        let mut updates = vec![];
+        let mut capabilities = vec![];
+        let mut shallow = vec![];
+        loop {
+            let Some((line, next)) = read_pkt_line(data, offset)? else {
+                return Ok(None);
+            };
+            offset = next;
+            let PktLine::Data(line) = line else {
+                break;
+            };
+            let line = String::from_utf8_lossy(line);
+            let line = line.trim_end_matches('\n');
+            if line.starts_with("shallow ") {
+                shallow.push(line.to_string());
+                continue;
+            }
+            let (command, caps) = match line.split_once('\0') {
+                Some((command, caps)) => (command, Some(caps)),
+                None => (line, None),
+            };
+            if let Some(caps) = caps {
+                capabilities = caps.split(' ').filter(|x| !x.is_empty()).map(|x| x.to_string()).collect();
+            }
+            let mut parts = command.splitn(3, ' ');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(old), Some(new), Some(name)) => updates.push(GitRefUpdate {
+                    old: old.to_string(),
+                    new: new.to_string(),
+                    name: name.to_string(),
+                }),
+                _ => return Err(anyhow::anyhow!("Invalid ref update command: {}", command)),
+            }
+        }
+        let commands_end = offset;
+        if capabilities.iter().any(|x| x == "push-options") {
+            loop {
+                let Some((line, next)) = read_pkt_line(data, offset)? else {
+                    return Ok(None);
+                };
+                offset = next;
+                if let PktLine::Flush = line {
+                    break;
+                }
+            }
+        }
+        Ok(Some(ReceiveRequest {
+            updates,
+            capabilities,
+            shallow,
+            commands_end,
+            pack_offset: offset,
+        }))
+    }
+
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|x| x == capability)
+    }
+
+    /// Whether the client asked for a report-status section in either version.
+    pub fn reports_status(&self) -> bool {
+        self.has_capability("report-status") || self.has_capability("report-status-v2")
+    }
+
+    /// Rebuilds the request body `data` with only `updates` in its command
+    /// list, keeping the capabilities, push options and pack. Without updates
+    /// the request is a bare flush, which git answers without reading further.
+    pub fn with_updates(&self, data: &[u8], updates: &[GitRefUpdate]) -> Vec<u8> {
+        if updates.is_empty() {
+            return b"0000".to_vec();
+        }
+        let mut body = vec![];
+        for line in &self.shallow {
+            body.extend(pkt_line(format!("{}\n", line).as_bytes()));
+        }
+        for (index, update) in updates.iter().enumerate() {
+            let mut line = format!("{} {} {}", update.old, update.new, update.name);
+            if index == 0 {
+                line.push('\0');
+                line.push_str(&self.capabilities.join(" "));
+            }
+            line.push('\n');
+            body.extend(pkt_line(line.as_bytes()));
+        }
+        body.extend(b"0000");
+        body.extend_from_slice(&data[self.commands_end..]);
+        body
+    }
+}
+
+pub const DEFAULT_MAX_PUSH_SIZE: usize = 2 * 1024 * 1024 * 1024;
+
+/// Largest receive-pack request, pack included, held in memory for the
+/// pre-receive checks, from `JZFS_MAX_PUSH_SIZE` in bytes.
+pub fn max_push_size() -> usize {
+    std::env::var("JZFS_MAX_PUSH_SIZE")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(DEFAULT_MAX_PUSH_SIZE)
+}
+
+/// The offset just past the first flush-pkt in `data`, such as the end of the
+/// ref advertisement; `None` while `data` does not reach it yet.
+pub fn flush_end(data: &[u8]) -> anyhow::Result<Option<usize>> {
+    let mut offset = 0;
+    while let Some((line, next)) = read_pkt_line(data, offset)? {
+        if let PktLine::Flush = line {
+            return Ok(Some(next));
+        }
+        offset = next;
+    }
+    Ok(None)
+}
+
+enum PktLine<'a> {
+    Flush,
+    Data(&'a [u8]),
+}
+
+/// Reads one pkt-line at `offset` along with the offset past it, or `Ok(None)`
+/// if `data` ends before the line does.
+fn read_pkt_line(data: &[u8], offset: usize) -> anyhow::Result<Option<(PktLine<'_>, usize)>> {
+    let Some(header) = data.get(offset..offset + 4) else {
+        return Ok(None);
+    };
+    let len = usize::from_str_radix(std::str::from_utf8(header)?, 16)?;
+    if len == 0 {
+        return Ok(Some((PktLine::Flush, offset + 4)));
+    }
+    if len < 4 {
+        return Err(anyhow::anyhow!("Invalid pkt-line length: {}", len));
+    }
+    match data.get(offset + 4..offset + len) {
+        Some(line) => Ok(Some((PktLine::Data(line), offset + len))),
+        None => Ok(None),
+    }
+}
+
+/// Encodes `data` as a single pkt-line.
+pub fn pkt_line(data: &[u8]) -> Vec<u8> {
+    let mut line = format!("{:04x}", data.len() + 4).into_bytes();
+    line.extend_from_slice(data);
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "1111111111111111111111111111111111111111";
+    const NEW: &str = "2222222222222222222222222222222222222222";
+
+    fn request_body() -> Vec<u8> {
+        let mut body = vec![];
+        body.extend(pkt_line(format!("{} {} refs/heads/main\0report-status side-band-64k\n", OLD, NEW).as_bytes()));
+        body.extend(pkt_line(format!("{} {} refs/heads/dev\n", GitRefUpdate::ZERO, NEW).as_bytes()));
+        body.extend(b"0000");
+        body.extend(b"PACK\0\0\0\x02pack data");
+        body
+    }
+
+    #[test]
+    fn test_receive_request_parse() {
+        let body = request_body();
+        let request = ReceiveRequest::parse(&body).unwrap().unwrap();
+        assert_eq!(request.capabilities, vec!["report-status", "side-band-64k"]);
+        assert_eq!(
+            request.updates.iter().map(|x| (x.old.as_str(), x.new.as_str(), x.name.as_str())).collect::<Vec<_>>(),
+            vec![(OLD, NEW, "refs/heads/main"), (GitRefUpdate::ZERO, NEW, "refs/heads/dev")]
+        );
+        assert!(request.reports_status());
+        assert_eq!(&body[request.pack_offset..], b"PACK\0\0\0\x02pack data");
+        assert_eq!(request.commands_end, request.pack_offset);
+
+        // the command list is not complete until its flush arrives
+        let end = body.len() - b"0000PACK\0\0\0\x02pack data".len();
+        assert!(ReceiveRequest::parse(&body[..end]).unwrap().is_none());
+        assert!(ReceiveRequest::parse(b"00zz").is_err());
+    }
+
+    #[test]
+    fn test_receive_request_with_updates() {
+        let body = request_body();
+        let request = ReceiveRequest::parse(&body).unwrap().unwrap();
+        let kept = vec![request.updates[1].clone()];
+        let rewritten = request.with_updates(&body, &kept);
+
+        let command = format!("{} {} refs/heads/dev\0report-status side-band-64k\n", GitRefUpdate::ZERO, NEW);
+        let mut expected = format!("{:04x}", command.len() + 4).into_bytes();
+        expected.extend(command.as_bytes());
+        expected.extend(b"0000PACK\0\0\0\x02pack data");
+        assert_eq!(rewritten, expected);
+
+        let reparsed = ReceiveRequest::parse(&rewritten).unwrap().unwrap();
+        assert_eq!(reparsed.updates.len(), 1);
+        assert_eq!(reparsed.updates[0].name, "refs/heads/dev");
+        assert_eq!(reparsed.capabilities, request.capabilities);
+        assert_eq!(request.with_updates(&body, &[]), b"0000");
+    }
+}
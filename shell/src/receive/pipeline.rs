@@ -0,0 +1,161 @@
+use crate::receive::hooks::{BranchProtection, CommitMessagePolicy, FileSizeLimit, RepositoryGc, RepositorySync};
+use crate::receive::{report, ReceiveRequest};
+use async_trait::async_trait;
+use git::receive::quarantine::GitQuarantine;
+use git::receive::GitRefUpdate;
+use git::AppGit;
+use infra::entities::repository::RepositoryModel;
+use infra::App;
+use std::sync::{Arc, OnceLock};
+use tracing::error;
+use uuid::Uuid;
+
+/// A ref update refused before it reached git.
+#[derive(Clone, Debug)]
+pub struct RefRejection {
+    pub refname: String,
+    pub reason: String,
+}
+
+/// What a pre-receive check sees of a push.
+pub struct ReceiveContext<'a> {
+    pub app: &'a App,
+    pub repo: &'a RepositoryModel,
+    pub user: Option<Uuid>,
+    /// The pack sent along with the commands; its objects are not in the
+    /// repository yet.
+    pub pack: &'a [u8],
+    quarantine: OnceLock<GitQuarantine>,
+}
+
+impl<'a> ReceiveContext<'a> {
+    pub fn new(app: &'a App, repo: &'a RepositoryModel, user: Option<Uuid>, pack: &'a [u8]) -> Self {
+        ReceiveContext {
+            app,
+            repo,
+            user,
+            pack,
+            quarantine: OnceLock::new(),
+        }
+    }
+
+    /// The repository with the pushed objects visible, indexed on first use
+    /// and shared by the checks of one push.
+    pub fn quarantine(&self) -> anyhow::Result<&GitQuarantine> {
+        if let Some(quarantine) = self.quarantine.get() {
+            return Ok(quarantine);
+        }
+        let quarantine = AppGit::new(self.repo.to_path()).quarantine(self.pack)?;
+        Ok(self.quarantine.get_or_init(|| quarantine))
+    }
+}
+
+/// Runs before git receives a push and may refuse any of its ref updates.
+#[async_trait]
+pub trait PreReceiveHook: Send + Sync {
+    /// Returns the updates to refuse, each with the reason shown to the pusher.
+    async fn check(&self, ctx: &ReceiveContext<'_>, updates: &[GitRefUpdate]) -> anyhow::Result<Vec<RefRejection>>;
+}
+
+/// Runs after git applied a push, with the ref updates it accepted.
+#[async_trait]
+pub trait PostReceiveHook: Send + Sync {
+    async fn run(&self, app: &App, repo: &RepositoryModel, user: Option<Uuid>, updates: &[GitRefUpdate]) -> anyhow::Result<()>;
+}
+
+/// The verdict of the pre-receive checks on a push.
+#[derive(Clone, Debug)]
+pub struct ReceiveOutcome {
+    pub request: ReceiveRequest,
+    pub accepted: Vec<GitRefUpdate>,
+    pub rejected: Vec<RefRejection>,
+}
+
+impl ReceiveOutcome {
+    /// The accepted updates git went on to apply, going by the report in its
+    /// `output`.
+    pub fn applied(&self, output: &[u8]) -> Vec<GitRefUpdate> {
+        match report::updated_refs(&self.request, output) {
+            Some(refs) => self.accepted.iter().filter(|x| refs.contains(&x.name)).cloned().collect(),
+            None => self.accepted.clone(),
+        }
+    }
+}
+
+/// The hooks run around every push, in registration order.
+#[derive(Clone, Default)]
+pub struct ReceivePipeline {
+    pre: Vec<Arc<dyn PreReceiveHook>>,
+    post: Vec<Arc<dyn PostReceiveHook>>,
+}
+
+impl ReceivePipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Branch protection and file size checks, plus the commit message policy
+    /// when one is configured, then the jobs syncing the repository, which
+    /// feeds pull requests, references and webhooks, and collecting its garbage.
+    pub fn standard() -> Self {
+        let mut pipeline = Self::new()
+            .pre_receive(BranchProtection)
+            .pre_receive(FileSizeLimit::from_env());
+        if let Some(policy) = CommitMessagePolicy::from_env() {
+            pipeline = pipeline.pre_receive(policy);
+        }
+        pipeline.post_receive(RepositorySync).post_receive(RepositoryGc)
+    }
+
+    pub fn pre_receive(mut self, hook: impl PreReceiveHook + 'static) -> Self {
+        self.pre.push(Arc::new(hook));
+        self
+    }
+
+    pub fn post_receive(mut self, hook: impl PostReceiveHook + 'static) -> Self {
+        self.post.push(Arc::new(hook));
+        self
+    }
+
+    /// Runs the pre-receive checks; each sees only the updates no earlier
+    /// check refused. An `atomic` push is refused as a whole.
+    pub async fn check(&self, ctx: &ReceiveContext<'_>, request: &ReceiveRequest) -> anyhow::Result<ReceiveOutcome> {
+        let mut accepted = request.updates.clone();
+        let mut rejected: Vec<RefRejection> = vec![];
+        for hook in &self.pre {
+            if accepted.is_empty() {
+                break;
+            }
+            for rejection in hook.check(ctx, &accepted).await? {
+                if !rejected.iter().any(|x| x.refname == rejection.refname) {
+                    accepted.retain(|x| x.name != rejection.refname);
+                    rejected.push(rejection);
+                }
+            }
+        }
+        if !rejected.is_empty() && request.has_capability("atomic") {
+            rejected.extend(accepted.drain(..).map(|x| RefRejection {
+                refname: x.name,
+                reason: "atomic push failed".to_string(),
+            }));
+        }
+        Ok(ReceiveOutcome {
+            request: request.clone(),
+            accepted,
+            rejected,
+        })
+    }
+
+    /// Runs every post-receive hook, unless nothing was updated; a failing
+    /// hook does not stop the others.
+    pub async fn finish(&self, app: &App, repo: &RepositoryModel, user: Option<Uuid>, updates: &[GitRefUpdate]) {
+        if updates.is_empty() {
+            return;
+        }
+        for hook in &self.post {
+            if let Err(e) = hook.run(app, repo, user, updates).await {
+                error!("Post-receive hook failed for repo {}: {}", repo.uid, e);
+            }
+        }
+    }
+}
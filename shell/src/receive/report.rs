@@ -0,0 +1,176 @@
+use crate::receive::pipeline::RefRejection;
+use crate::receive::{pkt_line, read_pkt_line, PktLine, ReceiveRequest};
+
+/// The parts of git's receive-pack response: the report-status lines and the
+/// progress and error packets (band byte included) sent alongside them.
+struct GitReport {
+    status: Vec<String>,
+    progress: Vec<Vec<u8>>,
+}
+
+/// Reads pkt-lines from `data` until a flush or its end.
+fn pkt_lines(data: &[u8]) -> Vec<&[u8]> {
+    let mut lines = vec![];
+    let mut offset = 0;
+    while let Ok(Some((PktLine::Data(line), next))) = read_pkt_line(data, offset) {
+        lines.push(line);
+        offset = next;
+    }
+    lines
+}
+
+fn demux(request: &ReceiveRequest, output: &[u8]) -> GitReport {
+    let mut progress = vec![];
+    let status = if request.has_capability("side-band-64k") || request.has_capability("side-band") {
+        let mut status = vec![];
+        for line in pkt_lines(output) {
+            match line.split_first() {
+                Some((1, data)) => status.extend_from_slice(data),
+                Some(_) => progress.push(line.to_vec()),
+                None => {}
+            }
+        }
+        status
+    } else {
+        output.to_vec()
+    };
+    let status = pkt_lines(&status)
+        .into_iter()
+        .map(|x| String::from_utf8_lossy(x).trim_end_matches('\n').to_string())
+        .collect();
+    GitReport { status, progress }
+}
+
+/// Builds the response to a push from the output of git receive-pack, which
+/// saw only the accepted updates, adding the refused ones to its report and
+/// their reasons to the messages shown by the client. `output` is empty when
+/// git was not given any update.
+pub fn respond(request: &ReceiveRequest, output: &[u8], rejected: &[RefRejection]) -> Vec<u8> {
+    let report = demux(request, output);
+    let mut status = vec![];
+    if request.reports_status() {
+        if report.status.is_empty() {
+            status.extend(pkt_line(b"unpack ok\n"));
+        }
+        for line in &report.status {
+            status.extend(pkt_line(format!("{}\n", line).as_bytes()));
+        }
+        for rejection in rejected {
+            status.extend(pkt_line(format!("ng {} {}\n", rejection.refname, rejection.reason).as_bytes()));
+        }
+        status.extend(b"0000");
+    }
+
+    let band_size = if request.has_capability("side-band-64k") {
+        65515
+    } else if request.has_capability("side-band") {
+        995
+    } else {
+        return status;
+    };
+    let mut response = vec![];
+    for rejection in rejected {
+        let mut message = vec![2u8];
+        message.extend(format!("error: {}: {}\n", rejection.refname, rejection.reason).as_bytes());
+        response.extend(pkt_line(&message));
+    }
+    for packet in &report.progress {
+        response.extend(pkt_line(packet));
+    }
+    for chunk in status.chunks(band_size) {
+        let mut data = vec![1u8];
+        data.extend_from_slice(chunk);
+        response.extend(pkt_line(&data));
+    }
+    response.extend(b"0000");
+    response
+}
+
+/// The refs git reported as updated, or `None` when the client did not ask
+/// for a report and git's verdict is unknown.
+pub fn updated_refs(request: &ReceiveRequest, output: &[u8]) -> Option<Vec<String>> {
+    if !request.reports_status() {
+        return None;
+    }
+    let report = demux(request, output);
+    Some(
+        report
+            .status
+            .iter()
+            .filter_map(|x| x.strip_prefix("ok "))
+            .map(|x| x.to_string())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(capabilities: &str) -> ReceiveRequest {
+        let command = format!("{} {} refs/heads/main\0{}\n", "1".repeat(40), "2".repeat(40), capabilities);
+        let mut body = pkt_line(command.as_bytes());
+        body.extend(b"0000");
+        ReceiveRequest::parse(&body).unwrap().unwrap()
+    }
+
+    fn rejection() -> RefRejection {
+        RefRejection {
+            refname: "refs/heads/dev".to_string(),
+            reason: "protected branch".to_string(),
+        }
+    }
+
+    /// git's own report for an accepted push to main.
+    fn git_status() -> Vec<u8> {
+        let mut status = pkt_line(b"unpack ok\n");
+        status.extend(pkt_line(b"ok refs/heads/main\n"));
+        status.extend(b"0000");
+        status
+    }
+
+    #[test]
+    fn test_respond_merges_rejections() {
+        let request = request("report-status");
+        let response = respond(&request, &git_status(), &[rejection()]);
+        assert_eq!(
+            pkt_lines(&response),
+            vec![
+                b"unpack ok\n".as_slice(),
+                b"ok refs/heads/main\n".as_slice(),
+                b"ng refs/heads/dev protected branch\n".as_slice(),
+            ]
+        );
+        assert!(response.ends_with(b"0000"));
+        assert_eq!(updated_refs(&request, &git_status()), Some(vec!["refs/heads/main".to_string()]));
+
+        // git saw no update at all, so the report is made up entirely here
+        let response = respond(&request, b"", &[rejection()]);
+        assert_eq!(
+            pkt_lines(&response),
+            vec![b"unpack ok\n".as_slice(), b"ng refs/heads/dev protected branch\n".as_slice()]
+        );
+    }
+
+    #[test]
+    fn test_respond_side_band() {
+        let request = request("report-status side-band-64k");
+        let mut progress = vec![2u8];
+        progress.extend(b"Resolving deltas\n");
+        let mut status = vec![1u8];
+        status.extend(git_status());
+        let mut output = pkt_line(&progress);
+        output.extend(pkt_line(&status));
+        output.extend(b"0000");
+
+        let response = respond(&request, &output, &[rejection()]);
+        let packets = pkt_lines(&response);
+        assert_eq!(packets[0], b"\x02error: refs/heads/dev: protected branch\n".as_slice());
+        assert_eq!(packets[1], progress.as_slice());
+        let report = demux(&request, &response);
+        assert_eq!(
+            report.status,
+            vec!["unpack ok", "ok refs/heads/main", "ng refs/heads/dev protected branch"]
+        );
+    }
+}
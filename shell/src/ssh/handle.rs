@@ -4,15 +4,21 @@ use std::path::PathBuf;
 use std::process::{ExitStatus, Stdio};
 use std::str::FromStr;
 use russh::{Channel, ChannelId, CryptoVec, Disconnect, MethodKind, MethodSet};
-use russh::keys::PublicKey;
+use russh::keys::{HashAlg, PublicKey};
 use russh::server::{Auth, Handle, Msg, Session};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use tokio::process::ChildStdin;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot;
 use tracing::error;
+use uuid::Uuid;
 use git::AppGit;
 use infra::App;
 use infra::entities::repository::RepositoryModel;
+use infra::entities::users::UsersModel;
+use infra::service::access::AccessLevel;
+use crate::receive::pipeline::{ReceiveContext, ReceiveOutcome, ReceivePipeline};
+use crate::receive::{flush_end, max_push_size, report, ReceiveRequest};
 
 pub struct SSHandle {
     pub app: App,
@@ -21,6 +27,13 @@ pub struct SSHandle {
     pub branch: Option<String>,
     pub repo: Option<RepositoryModel>,
     pub service: Option<GitService>,
+    pub receive: HashMap<ChannelId, Vec<u8>>,
+    /// Size above which a buffered push is refused.
+    pub max_push_size: usize,
+    /// Hands the verdict on a push to the task forwarding git's output.
+    pub outcome: HashMap<ChannelId, oneshot::Sender<ReceiveOutcome>>,
+    pub pipeline: ReceivePipeline,
+    pub user: Option<UsersModel>,
 }
 
 impl SSHandle {
@@ -32,8 +45,49 @@ impl SSHandle {
             branch: None,
             repo: None,
             service: None,
+            receive: HashMap::new(),
+            max_push_size: max_push_size(),
+            outcome: HashMap::new(),
+            pipeline: ReceivePipeline::standard(),
+            user: None,
         }
     }
+
+    /// Hands the buffered receive-pack request to git with the ref updates
+    /// that passed the pre-receive checks; the refused ones are reported by
+    /// the task forwarding git's output.
+    async fn receive_pack(&mut self, channel: ChannelId, session: &mut Session) -> Result<(), russh::Error> {
+        let Some(data) = self.receive.remove(&channel) else {
+            return Ok(());
+        };
+        // dropped unused when the request cannot be read
+        let sender = self.outcome.remove(&channel);
+        let mut input = data.clone();
+        if let (Ok(Some(request)), Some(repo)) = (ReceiveRequest::parse(&data), &self.repo) {
+            let user = self.user.as_ref().map(|x| x.uid);
+            let ctx = ReceiveContext::new(&self.app, repo, user, &data[request.pack_offset..]);
+            match self.pipeline.check(&ctx, &request).await {
+                Ok(outcome) => {
+                    if !outcome.rejected.is_empty() {
+                        input = request.with_updates(&data, &outcome.accepted);
+                    }
+                    if let Some(sender) = sender {
+                        sender.send(outcome).ok();
+                    }
+                }
+                Err(e) => {
+                    error!("Pre-receive check failed: {}", e);
+                    session.disconnect(Disconnect::ByApplication, "Pre-receive check failed", "").ok();
+                    return Err(russh::Error::Disconnect);
+                }
+            }
+        }
+        if let Some(stdin) = self.stdin.get_mut(&channel) {
+            stdin.write_all(&input).await?;
+            stdin.flush().await.ok();
+        }
+        Ok(())
+    }
 }
 
 impl russh::server::Handler for SSHandle {
@@ -60,36 +114,21 @@ impl russh::server::Handler for SSHandle {
             return Err(russh::Error::NotAuthenticated);
         }
 
-        let public = public_key.to_string();
-        if public.len() < 64 {
-            return Err(russh::Error::NotAuthenticated);
-        }
-
-        // let model = ssh::Entity::find()
-        //     .filter(ssh::Column::Content.contains(&public))
-        //     .one(&self.app.read)
-        //     .await
-        //     .map_err(|e| {
-        //         error!("Database query failed: {}", e);
-        //         russh::Error::WrongServerSig
-        //     })?
-        //     .ok_or_else(|| {
-        //         russh::Error::NotAuthenticated
-        //     })?;
-        //
-        // let user = users::Entity::find()
-        //     .filter(users::Column::Uid.eq(model.user_id))
-        //     .one(&self.app.read)
-        //     .await
-        //     .map_err(|e| {
-        //         error!("User query failed: {}", e);
-        //         russh::Error::WrongServerSig
-        //     })?
-        //     .ok_or_else(|| {
-        //         russh::Error::NotAuthenticated
-        //     })?;
-        //
-        // self.user = Some(user);
+        let fingerprint = public_key.fingerprint(HashAlg::Sha256).to_string();
+        let user = match self.app.ssh_key_auth(&fingerprint).await {
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                return Ok(Auth::Reject {
+                    proceed_with_methods: None,
+                    partial_success: false,
+                });
+            }
+            Err(e) => {
+                error!("SSH key lookup failed: {}", e);
+                return Err(russh::Error::NotAuthenticated);
+            }
+        };
+        self.user = Some(user);
         Ok(Auth::Accept)
     }
 
@@ -99,19 +138,14 @@ impl russh::server::Handler for SSHandle {
         }
         Ok(())
     }
-    async fn channel_eof(&mut self, channel: ChannelId, _: &mut Session) -> Result<(), Self::Error> {
+    async fn channel_eof(&mut self, channel: ChannelId, session: &mut Session) -> Result<(), Self::Error> {
+        self.receive_pack(channel, session).await?;
         if let Some(mut stdin) = self.stdin.remove(&channel) {
             let _ = stdin.shutdown().await;
         }
         if let Some(eof) = self.eof.get_mut(&channel){
             eof.send(true).await.ok();
         }
-        if let Some(repo) = self.repo.clone() {
-            let app = self.app.clone();
-            tokio::spawn(async move {
-                app.sync_hook(repo).await.ok();
-            });
-        }
         Ok(())
     }
 
@@ -119,32 +153,27 @@ impl russh::server::Handler for SSHandle {
         Ok(true)
     }
 
-    async fn data(&mut self, channel: ChannelId, data: &[u8], _: &mut Session) -> Result<(), Self::Error> {
+    async fn data(&mut self, channel: ChannelId, data: &[u8], session: &mut Session) -> Result<(), Self::Error> {
+        if let Some(buffer) = self.receive.get_mut(&channel) {
+            if buffer.len() + data.len() > self.max_push_size {
+                self.receive.remove(&channel);
+                let msg = format!("Push is over the {} byte limit", self.max_push_size);
+                error!("{}", msg);
+                session.disconnect(Disconnect::ByApplication, &msg, "").ok();
+                return Err(russh::Error::Disconnect);
+            }
+            buffer.extend_from_slice(data);
+            // Delete-only pushes send no pack and wait for the report without closing stdin.
+            let commands_only = matches!(
+                ReceiveRequest::parse(buffer),
+                Ok(Some(request)) if request.updates.iter().all(|x| x.is_delete())
+            );
+            if commands_only {
+                self.receive_pack(channel, session).await?;
+            }
+            return Ok(());
+        }
         if let Some(stdin) = self.stdin.get_mut(&channel) {
-            // if let Ok(str) = std::str::from_utf8(data) && self.branch.is_none() {
-                // let branch = str.split(" ")
-                //     .map(|x|x.to_string())
-                //     .filter(|x|x.starts_with("refs/"))
-                //     .map(|x|x.replace("refs/", ""))
-                //     .map(|x|x.replace("heads/", ""))
-                //     .map(|x|x.replace("\0", ""))
-                //     .collect::<Vec<_>>();
-                // if branch.len() > 0 {
-                //     let branch = branch[0].clone();
-                //     self.branch = Some(branch.clone());
-                //     if let Some(repo) = self.repo.clone() {
-                //         if let Ok(branches) = self.app
-                //             .service_repo_git_branch_list(repo.uid)
-                //             .await {
-                //             if let Some(db_branch) = branches.iter().find(|x|x.name ==  branch) {
-                //                 if db_branch.protect {
-                //                     session.disconnect(Disconnect::ByApplication, "Branch is protected", "en").ok();
-                //                 }
-                //             }
-                //         }
-                //     }
-                // }
-            // }
             let _ = stdin.write_all(data).await;
             stdin.flush().await.ok();
         }
@@ -198,34 +227,16 @@ impl russh::server::Handler for SSHandle {
         };
         self.repo = Some(repo.clone());
 
-        // let user = match &self.user {
-        //     Some(user) => user,
-        //     None => {
-        //         error!("User not resolved after authentication");
-        //         session.disconnect(Disconnect::ByApplication, "Authentication error", "").ok();
-        //         return Err(russh::Error::Disconnect);
-        //     }
-        // };
-        //
-        // let access = match self.app.user_access_owner_model(user.uid).await {
-        //     Ok(access) => access,
-        //     Err(e) => {
-        //         error!("Access check failed: {}", e);
-        //         session.disconnect(Disconnect::ByApplication, "Access check failed", "").ok();
-        //         return Err(russh::Error::Disconnect);
-        //     }
-        // };
-        //
-        // let allowed_repos: HashSet<_> = access.iter()
-        //     .flat_map(|a| &a.repos)
-        //     .map(|r| r.uid)
-        //     .collect();
-        //
-        // if !allowed_repos.contains(&repo.uid) {
-        //     error!("Access denied to repo {} for user {}", repo.uid, user.uid);
-        //     session.disconnect(Disconnect::ByApplication, "Access denied", "").ok();
-        //     return Err(russh::Error::Disconnect);
-        // }
+        let level = match service {
+            GitService::ReceivePack => AccessLevel::Write,
+            GitService::UploadPack | GitService::UploadArchive => AccessLevel::Read,
+        };
+        let user = self.user.as_ref().map(|x| x.uid);
+        if let Err(e) = self.app.repository_authorize(&repo, user, level).await {
+            error!("Access denied to repo {} for user {:?}: {}", repo.uid, user, e);
+            session.disconnect(Disconnect::ByApplication, "Access denied", "").ok();
+            return Err(russh::Error::Disconnect);
+        }
 
         let path = AppGit::new(repo.to_path()).path_buf;
         let mut cmd = build_git_command(service, path);
@@ -248,6 +259,19 @@ impl russh::server::Handler for SSHandle {
         let session_handle = session.handle();
         let stdin = shell.stdin.take().unwrap();
         self.stdin.insert(channel_id, stdin);
+        let mut receive = None;
+        if service == GitService::ReceivePack {
+            self.receive.insert(channel_id, vec![]);
+            let (sender, outcome) = oneshot::channel();
+            self.outcome.insert(channel_id, sender);
+            receive = Some(PostReceive {
+                outcome,
+                pipeline: self.pipeline.clone(),
+                app: self.app.clone(),
+                repo: repo.clone(),
+                user,
+            });
+        }
         let mut shell_stdout = shell.stdout.take().unwrap();
         let mut shell_stderr = shell.stderr.take().unwrap();
 
@@ -289,12 +313,20 @@ impl russh::server::Handler for SSHandle {
 
             use futures::future::FutureExt;
 
-            let stdout_fut = forward(
-                &session_handle,
-                channel_id,
-                &mut shell_stdout,
-                |handle, chan, data| async move { handle.data(chan, data).await },
-            )
+            let stdout_fut = async {
+                match receive {
+                    Some(receive) => forward_receive(&session_handle, channel_id, &mut shell_stdout, receive).await,
+                    None => {
+                        forward(
+                            &session_handle,
+                            channel_id,
+                            &mut shell_stdout,
+                            |handle, chan, data| async move { handle.data(chan, data).await },
+                        )
+                            .await
+                    }
+                }
+            }
                 .fuse();
 
             tokio::pin!(stdout_fut);
@@ -311,6 +343,7 @@ impl russh::server::Handler for SSHandle {
                 .fuse();
 
             tokio::pin!(stderr_fut);
+            let mut stdout_done = false;
             loop {
                 enum Pipe {
                     Stdout(Result<(), russh::Error>),
@@ -326,6 +359,7 @@ impl russh::server::Handler for SSHandle {
 
                 match result {
                     Pipe::Stdout(result) => {
+                        stdout_done = true;
                         result?;
                     }
                     Pipe::Stderr(result) => {
@@ -333,6 +367,10 @@ impl russh::server::Handler for SSHandle {
                     }
                     Pipe::Exit(result) => {
                         let status = result?;
+                        // git may exit before all of its output was forwarded
+                        if !stdout_done {
+                            (&mut stdout_fut).await?;
+                        }
 
                         while let Some(eof) = eof_rx.recv().await {
                             if eof {
@@ -359,6 +397,70 @@ impl russh::server::Handler for SSHandle {
     }
 }
 
+/// What the forwarding task of a receive-pack channel needs to finish a push.
+struct PostReceive {
+    outcome: oneshot::Receiver<ReceiveOutcome>,
+    pipeline: ReceivePipeline,
+    app: App,
+    repo: RepositoryModel,
+    user: Option<Uuid>,
+}
+
+/// Forwards the output of git receive-pack: the ref advertisement as is, then
+/// the response to the push, completed with the updates the pre-receive
+/// checks refused. Runs the post-receive hooks once git is done.
+async fn forward_receive<R>(handle: &Handle, channel: ChannelId, stdout: &mut R, receive: PostReceive) -> Result<(), russh::Error>
+where
+    R: AsyncRead + Send + Unpin,
+{
+    let mut buf = [0u8; 1024 * 32];
+    let mut output = vec![];
+    let advertised = loop {
+        let read = stdout.read(&mut buf).await?;
+        if read == 0 {
+            handle.data(channel, CryptoVec::from_slice(&output)).await.ok();
+            return Ok(());
+        }
+        output.extend_from_slice(&buf[..read]);
+        match flush_end(&output) {
+            Ok(Some(end)) => break end,
+            Ok(None) => {}
+            Err(_) => break output.len(),
+        }
+    };
+    handle.data(channel, CryptoVec::from_slice(&output[..advertised])).await.ok();
+    output.drain(..advertised);
+
+    // no verdict means the request could not be read, so git gets it as is
+    let outcome = receive.outcome.await.ok();
+    let buffered = outcome.as_ref().is_some_and(|x| !x.rejected.is_empty());
+    if !buffered && !output.is_empty() {
+        handle.data(channel, CryptoVec::from_slice(&output)).await.ok();
+    }
+    loop {
+        let read = stdout.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        if !buffered {
+            handle.data(channel, CryptoVec::from_slice(&buf[..read])).await.ok();
+        }
+        output.extend_from_slice(&buf[..read]);
+    }
+    let Some(outcome) = outcome else {
+        return Ok(());
+    };
+    if buffered {
+        let response = report::respond(&outcome.request, &output, &outcome.rejected);
+        handle.data(channel, CryptoVec::from(response)).await.ok();
+    }
+    let applied = outcome.applied(&output);
+    tokio::spawn(async move {
+        receive.pipeline.finish(&receive.app, &receive.repo, receive.user, &applied).await;
+    });
+    Ok(())
+}
+
 fn parse_git_command(cmd: &str) -> Option<(GitService, &str)> {
     let (svc, path) = match cmd.split_once(' ') {
         Some(("git-receive-pack", path)) => (GitService::ReceivePack, path),